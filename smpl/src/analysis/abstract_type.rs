@@ -42,7 +42,18 @@ pub enum AbstractTypeX<X> {
     Array {
         data: X,
         element_type: Box<AbstractTypeX<X>>,
-        size: u64,
+        /// None for growable arrays
+        size: Option<u64>,
+    },
+
+    Ref {
+        data: X,
+        inner: Box<AbstractTypeX<X>>,
+    },
+
+    Tuple {
+        data: X,
+        elements: Vec<AbstractTypeX<X>>,
     },
 
     UncheckedFunction {
@@ -69,6 +80,15 @@ pub enum AbstractTypeX<X> {
 
     TypeVar(X, TypeVarId),
 
+    /// An applied type alias. The alias is only kept for diagnostics, everything else
+    ///   looks through it with `expanded()`.
+    Alias {
+        data: X,
+        name: Ident,
+        args: Vec<AbstractTypeX<X>>,
+        aliased: Box<AbstractTypeX<X>>,
+    },
+
     Int(X),
     Float(X),
     String(X),
@@ -136,6 +156,28 @@ impl<T> AbstractTypeX<T> {
                 }
             }
 
+            Ref {
+                inner,
+                ..
+            } => {
+                Ref {
+                    data: (),
+                    inner: Box::new(inner.downcast()),
+                }
+            }
+
+            Tuple {
+                elements,
+                ..
+            } => {
+                Tuple {
+                    data: (),
+                    elements: elements.into_iter()
+                        .map(|t| t.downcast())
+                        .collect(),
+                }
+            }
+
             UncheckedFunction {
                 return_type,
                 ..
@@ -186,6 +228,18 @@ impl<T> AbstractTypeX<T> {
 
             TypeVar(_, type_var) => TypeVar((), type_var),
 
+            Alias {
+                name,
+                args,
+                aliased,
+                ..
+            } => Alias {
+                data: (),
+                name,
+                args: args.into_iter().map(|a| a.downcast()).collect(),
+                aliased: Box::new(aliased.downcast()),
+            },
+
             Int(_) => Int(()),
             Float(_) => Float(()),
             String(_) => String(()),
@@ -219,6 +273,16 @@ impl<T> AbstractTypeX<T> {
                 ..
             } => data,
 
+            Ref {
+                ref data,
+                ..
+            } => data,
+
+            Tuple {
+                ref data,
+                ..
+            } => data,
+
             UncheckedFunction {
                 ref data,
                 ..
@@ -241,6 +305,11 @@ impl<T> AbstractTypeX<T> {
 
             TypeVar(ref data, _) => data,
 
+            Alias {
+                ref data,
+                ..
+            } => data,
+
             Int(ref data) => data,
             Float(ref data) => data,
             String(ref data) => data,
@@ -248,6 +317,86 @@ impl<T> AbstractTypeX<T> {
             Unit(ref data) => data,
         }
     }
+
+    pub fn data_mut(&mut self) -> &mut T {
+        use self::AbstractTypeX::*;
+        match *self {
+            Any(ref mut data) => data,
+
+            Record {
+                ref mut data,
+                ..
+            } => data,
+
+            App {
+                ref mut data,
+                ..
+            } => data,
+
+            App2 {
+                ref mut data,
+                ..
+            } => data,
+
+            Array {
+                ref mut data,
+                ..
+            } => data,
+
+            Ref {
+                ref mut data,
+                ..
+            } => data,
+
+            Tuple {
+                ref mut data,
+                ..
+            } => data,
+
+            UncheckedFunction {
+                ref mut data,
+                ..
+            } => data,
+
+            Function {
+                ref mut data,
+                ..
+            } => data,
+
+            WidthConstraint {
+                ref mut data,
+                ..
+            } => data,
+
+            Opaque {
+                ref mut data,
+                ..
+            } => data,
+
+            TypeVar(ref mut data, _) => data,
+
+            Alias {
+                ref mut data,
+                ..
+            } => data,
+
+            Int(ref mut data) => data,
+            Float(ref mut data) => data,
+            String(ref mut data) => data,
+            Bool(ref mut data) => data,
+            Unit(ref mut data) => data,
+        }
+    }
+
+    ///
+    /// The type an alias stands for. Any other type is returned as is.
+    ///
+    pub fn expanded(&self) -> &AbstractTypeX<T> {
+        match *self {
+            AbstractTypeX::Alias { ref aliased, .. } => aliased.expanded(),
+            ref t => t,
+        }
+    }
 }
 
 impl AbstractTypeX<Span> {
@@ -425,6 +574,37 @@ impl AbstractTypeX<Span> {
                 })
             }
 
+            TypeCons::Alias {
+                ref name,
+                ref type_params,
+                ref aliased,
+            } => {
+                // Expansions point at the use of the alias rather than its declaration
+                let mut expanded = aliased.substitute_internal(
+                    universe,
+                    scoped_data,
+                    typing_context,
+                    map,
+                )?;
+                *expanded.data_mut() = app_span.clone();
+
+                let args = type_params
+                    .iter()
+                    .map(|(param_id, _)| {
+                        map.get(&type_params.placeholder_type_var(param_id))
+                            .expect("Missing placeholder type var in map")
+                            .clone()
+                    })
+                    .collect();
+
+                Ok(AbstractType::Alias {
+                    data: app_span.clone(),
+                    name: name.clone(),
+                    args,
+                    aliased: Box::new(expanded),
+                })
+            }
+
             TypeCons::Int => Ok(AbstractType::Int(app_span.clone())),
             TypeCons::Float => Ok(AbstractType::Float(app_span.clone())),
             TypeCons::Bool => Ok(AbstractType::Bool(app_span.clone())),
@@ -673,6 +853,37 @@ impl AbstractTypeX<Span> {
                 size: *size,
             }),
 
+            AbstractType::Ref {
+                data: ref ref_ty_span,
+                ref inner,
+            } => Ok(AbstractType::Ref {
+                data: ref_ty_span.clone(),
+                inner: Box::new(inner.substitute_internal(
+                    universe,
+                    scoped_data,
+                    typing_context,
+                    map,
+                )?),
+            }),
+
+            AbstractType::Tuple {
+                data: ref tuple_ty_span,
+                ref elements,
+            } => Ok(AbstractType::Tuple {
+                data: tuple_ty_span.clone(),
+                elements: elements
+                    .iter()
+                    .map(|e| {
+                        e.substitute_internal(
+                            universe,
+                            scoped_data,
+                            typing_context,
+                            map,
+                        )
+                    })
+                    .collect::<Result<_, _>>()?,
+            }),
+
             AbstractType::Function {
                 data: ref fn_ty_span,
                 ref parameters,
@@ -839,6 +1050,33 @@ impl AbstractTypeX<Span> {
                 })
             }
 
+            AbstractType::Alias {
+                data: ref span,
+                ref name,
+                ref args,
+                ref aliased,
+            } => Ok(AbstractType::Alias {
+                data: span.clone(),
+                name: name.clone(),
+                args: args
+                    .iter()
+                    .map(|a| {
+                        a.substitute_internal(
+                            universe,
+                            scoped_data,
+                            typing_context,
+                            map,
+                        )
+                    })
+                    .collect::<Result<_, _>>()?,
+                aliased: Box::new(aliased.substitute_internal(
+                    universe,
+                    scoped_data,
+                    typing_context,
+                    map,
+                )?),
+            }),
+
             AbstractType::Int(ref s) => Ok(AbstractType::Int(s.clone())),
             AbstractType::Float(ref s) => Ok(AbstractType::Float(s.clone())),
             AbstractType::String(ref s) => Ok(AbstractType::String(s.clone())),
@@ -978,7 +1216,8 @@ impl AbstractWidthConstraint {
 
                 // Gather field constraints from bases
                 for base in struct_bases.into_iter() {
-                    match base.substitute(universe, scope, typing_context)? {
+                    let base = base.substitute(universe, scope, typing_context)?;
+                    match *base.expanded() {
                         AbstractType::Record {
                             abstract_field_map:
                                 AbstractFieldMapX {
@@ -1094,9 +1333,13 @@ pub fn type_from_ann(
                     .map(|node| node.data().clone())
                     .collect(),
             );
-            let type_cons = scope.type_cons(&type_cons_path).ok_or(
-                AnalysisError::UnknownType(typed_path.module_path().clone(), anno.span()),
-            )?;
+            let type_cons = scope.type_cons(&type_cons_path).ok_or_else(|| {
+                scope.private_or(
+                    typed_path.module_path(),
+                    anno.span(),
+                    AnalysisError::UnknownType(typed_path.module_path().clone(), anno.span()),
+                )
+            })?;
 
             let type_args = typed_path.annotations().map(|ref vec| {
                 vec.iter()
@@ -1133,6 +1376,27 @@ pub fn type_from_ann(
             })
         }
 
+        TypeAnnotation::Ref(ref inner) => {
+            let inner = type_from_ann(scope, typing_context, inner)?;
+
+            Ok(AbstractType::Ref {
+                data: anno.span(),
+                inner: Box::new(inner),
+            })
+        }
+
+        TypeAnnotation::Tuple(ref elements) => {
+            let elements = elements
+                .iter()
+                .map(|e| type_from_ann(scope, typing_context, e))
+                .collect::<Result<_, _>>()?;
+
+            Ok(AbstractType::Tuple {
+                data: anno.span(),
+                elements,
+            })
+        }
+
         TypeAnnotation::FnType(ref tp, ref params, ref return_type) => {
             let (_local_type_params, new_scope) = match tp {
                 Some(_local_type_params) => {
@@ -1264,11 +1528,13 @@ where I: Iterator<Item=&'a AbstractType> + Clone {
         .expect("Always at least one constraint");
 
     let mut constraint_span = first_constraint.span().clone();
-    let is_first_non_width_constraint = match first_constraint {
+    let is_first_non_width_constraint = match first_constraint.expanded() {
         AbstractType::Record { .. }
         | AbstractType::App { .. }
         | AbstractType::App2 { .. }
         | AbstractType::Array { .. }
+        | AbstractType::Ref { .. }
+        | AbstractType::Tuple { .. }
         | AbstractType::Function { .. }
         | AbstractType::UncheckedFunction { .. }
         | AbstractType::Int(_)
@@ -1282,6 +1548,8 @@ where I: Iterator<Item=&'a AbstractType> + Clone {
         AbstractType::TypeVar(..) => true, // TODO: Check the type var in the context?
 
         AbstractType::WidthConstraint {..} => false,
+
+        AbstractType::Alias { .. } => unreachable!(),
     };
 
     let found_non_width_constraint = is_first_non_width_constraint;
@@ -1292,7 +1560,7 @@ where I: Iterator<Item=&'a AbstractType> + Clone {
     for constraint in constraint_iter {
         constraint_span = Span::combine(constraint_span, constraint.span().clone());
 
-        match constraint {
+        match constraint.expanded() {
             AbstractType::WidthConstraint {
                 data: ref span,
                 width: ref inner_awc,
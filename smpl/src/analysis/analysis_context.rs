@@ -9,9 +9,10 @@ use super::semantic_data::*;
 use super::control_flow::CFG;
 use super::type_checker::TypingContext;
 use super::resolve_scope::ScopedData;
-use super::type_cons::TypeCons;
+use super::abstract_type::AbstractType;
+use super::type_cons::{TraitCons, TypeCons};
 use super::semantic_data::{
-    FieldId, FnId, Program, TypeId, TypeParamId, TypeVarId, LoopId,
+    FieldId, FnId, Program, TraitId, TypeId, TypeParamId, TypeVarId, LoopId,
     BranchingId, TmpId, VarId, Module, ModuleId, ModulePath,
     AnonymousFn as ResolvedAnonymousFn, SMPLFunction, BuiltinFunction
 };
@@ -72,8 +73,17 @@ pub struct GlobalData {
 impl GlobalData {
 
     pub fn new() -> GlobalData {
+        GlobalData::starting_at(0)
+    }
+
+    ///
+    /// Start generating IDs at `start`.
+    ///
+    /// Used to avoid collisions with the IDs of a previously analyzed program.
+    ///
+    pub fn starting_at(start: u64) -> GlobalData {
         let mut g = GlobalData {
-            id_counter: Cell::new(0),
+            id_counter: Cell::new(start),
             int_type_id: TypeId(0),
             float_type_id: TypeId(0),
             bool_type_id: TypeId(0),
@@ -118,6 +128,13 @@ impl GlobalData {
         curr
     }
 
+    ///
+    /// All IDs generated so far are less than the watermark
+    ///
+    pub fn id_watermark(&self) -> u64 {
+        self.id_counter.get()
+    }
+
     pub fn new_type_id(&self) -> TypeId {
         TypeId(self.inc_counter())
     }
@@ -137,6 +154,14 @@ impl GlobalData {
     pub fn new_fn_id(&self) -> FnId {
         FnId(self.inc_counter())
     }
+
+    pub fn new_trait_id(&self) -> TraitId {
+        TraitId(self.inc_counter())
+    }
+
+    pub fn new_static_id(&self) -> StaticId {
+        StaticId(self.inc_counter())
+    }
 }
 
 pub struct LocalData {
@@ -225,6 +250,9 @@ impl AnalysisContext {
 pub struct AnalysisUniverse {
     type_cons_map: HashMap<TypeId, TypeCons>,
     fn_type_cons: HashMap<FnId, TypeCons>,
+    trait_cons: HashMap<TraitId, TraitCons>,
+    static_types: HashMap<StaticId, AbstractType>,
+    field_defaults: HashMap<FieldId, Literal>,
     anon_fn_map: HashMap<FnId, AnalyzableAnonymousFn>,
     builtin_fn_set: HashSet<FnId>,
     module_map: HashMap<ModuleId, Module>,
@@ -273,6 +301,9 @@ impl AnalysisUniverse {
             fn_type_cons: HashMap::new(),
             anon_fn_map: HashMap::new(),
             builtin_fn_set: HashSet::new(),
+            trait_cons: HashMap::new(),
+            static_types: HashMap::new(),
+            field_defaults: HashMap::new(),
             module_map: HashMap::new(),
             module_name: HashMap::new(),
             std_scope: ScopedData::new(
@@ -356,6 +387,41 @@ impl AnalysisUniverse {
         self.fn_type_cons.get(&fn_id)
     }
 
+    pub fn insert_trait_cons(&mut self, trait_id: TraitId, cons: TraitCons) {
+        if self.trait_cons.insert(trait_id, cons).is_some() {
+            panic!("Duplicate trait constructor for trait id");
+        }
+    }
+
+    pub fn get_trait_cons(&self, trait_id: TraitId) -> &TraitCons {
+        self.trait_cons
+            .get(&trait_id)
+            .expect("Expected TraitId to always resolve to a TraitCons")
+    }
+
+    pub fn insert_static_type(&mut self, static_id: StaticId, static_type: AbstractType) {
+        if self.static_types.insert(static_id, static_type).is_some() {
+            panic!("Duplicate type for static id");
+        }
+    }
+
+    pub fn get_static_type(&self, static_id: StaticId) -> &AbstractType {
+        self.static_types
+            .get(&static_id)
+            .expect("Expected StaticId to always resolve to a type")
+    }
+
+    pub fn insert_field_default(&mut self, field_id: FieldId, value: Literal) {
+        if self.field_defaults.insert(field_id, value).is_some() {
+            panic!("Duplicate default for field id");
+        }
+    }
+
+    /// Folded default value of a struct field
+    pub fn field_default(&self, field_id: FieldId) -> Option<&Literal> {
+        self.field_defaults.get(&field_id)
+    }
+
     pub fn get_type_cons(&self, id: TypeId) -> &TypeCons {
         self.type_cons_map
             .get(&id)
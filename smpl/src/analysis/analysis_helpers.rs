@@ -10,7 +10,7 @@ use super::semantic_data::{
     FieldId, FnId, Program, TypeId, TypeParamId, TypeVarId,
     ModuleId,
 };
-use super::type_checker::TypingContext;
+use super::type_checker::{dictionary_param, TypingContext};
 use super::type_cons::{TypeCons, TypeParams};
 use super::abstract_type::AbstractType;
 use super::analysis_context::{
@@ -187,6 +187,14 @@ where
                         .insert(formal_param_var_id, formal_param_type);
                 }
             }
+
+            // Trait dictionaries are passed after the formal parameters
+            for index in 0..type_params.bounds().len() {
+                param_order.push((
+                    ast::Ident(dictionary_param(index)),
+                    local_data.new_var_id(),
+                ));
+            }
         }
 
         _ => unreachable!("Only pass in a function type constructor"),
@@ -0,0 +1,310 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ast::{
+    AstNode, BinOp, Expr, Ident, Literal, ModulePath as AstModulePath, TypedPath, UniOp,
+};
+use crate::span::Span;
+
+use super::abstract_type::AbstractType;
+use super::error::{AnalysisError, TopLevelError, TypeError};
+use super::mod_resolver::{RawModData, ResolvedImport};
+use super::resolve_scope::ScopedData;
+use super::semantic_data::{ModuleId, ModulePath};
+
+///
+/// Folds every module constant into a literal.
+///
+/// Constant initializers may contain literals, unary and binary operators, constants of the
+///   same module and imported constants (i.e. 'module::NAME').
+///
+pub(super) fn evaluate_consts(
+    module_map: &HashMap<ModuleId, RawModData>,
+    import_map: &HashMap<ModuleId, Vec<ResolvedImport>>,
+) -> Result<HashMap<(ModuleId, Ident), Literal>, AnalysisError> {
+    let mut evaluator = ConstEvaluator {
+        module_map,
+        import_map,
+        values: HashMap::new(),
+        in_progress: HashSet::new(),
+    };
+
+    for (mod_id, raw_mod) in module_map.iter() {
+        for name in raw_mod.reserved_consts.keys() {
+            evaluator.evaluate_const(*mod_id, name)?;
+        }
+    }
+
+    Ok(evaluator.values)
+}
+
+///
+/// Folds a constant expression (i.e. a struct field default) referring to constants already
+///   in `scope`.
+///
+pub(super) fn evaluate_scoped(scope: &ScopedData, expr: &AstNode<Expr>) -> Result<Literal, AnalysisError> {
+    fold(expr.data(), expr.span(), &mut |path| {
+        scope
+            .get_const(path)
+            .cloned()
+            .ok_or_else(|| TopLevelError::NonConstantExpr(path.span()).into())
+    })
+}
+
+///
+/// Type of the literal a constant was folded into
+///
+pub(super) fn literal_type(literal: &Literal, span: Span) -> AbstractType {
+    match *literal {
+        Literal::Int(_) => AbstractType::Int(span),
+        Literal::Float(_) => AbstractType::Float(span),
+        Literal::Bool(_) => AbstractType::Bool(span),
+        Literal::String(_) => AbstractType::String(span),
+    }
+}
+
+struct ConstEvaluator<'a> {
+    module_map: &'a HashMap<ModuleId, RawModData>,
+    import_map: &'a HashMap<ModuleId, Vec<ResolvedImport>>,
+    values: HashMap<(ModuleId, Ident), Literal>,
+    in_progress: HashSet<(ModuleId, Ident)>,
+}
+
+impl<'a> ConstEvaluator<'a> {
+    fn evaluate_const(&mut self, mod_id: ModuleId, name: &Ident) -> Result<Literal, AnalysisError> {
+        let key = (mod_id, name.clone());
+        if let Some(value) = self.values.get(&key) {
+            return Ok(value.clone());
+        }
+
+        let module_map = self.module_map;
+        let global = module_map[&mod_id]
+            .reserved_consts
+            .get(name)
+            .expect("Missing constant")
+            .data();
+
+        if !self.in_progress.insert(key.clone()) {
+            return Err(TopLevelError::CyclicConst(name.clone(), global.name.span()).into());
+        }
+
+        let value = self.evaluate(mod_id, global.value.data(), global.value.span())?;
+
+        self.in_progress.remove(&key);
+        self.values.insert(key, value.clone());
+
+        Ok(value)
+    }
+
+    fn evaluate(
+        &mut self,
+        mod_id: ModuleId,
+        expr: &Expr,
+        span: Span,
+    ) -> Result<Literal, AnalysisError> {
+        fold(expr, span, &mut |path| {
+            if path.0.len() == 1 {
+                self.reference(mod_id, &path.0[0])
+            } else {
+                self.imported(mod_id, path)
+            }
+        })
+    }
+
+    ///
+    /// Constant of an imported module bound to `path`
+    ///
+    fn imported(&mut self, mod_id: ModuleId, path: &AstModulePath) -> Result<Literal, AnalysisError> {
+        let bound: ModulePath = path.clone().into();
+        let import_map = self.import_map;
+        let module_map = self.module_map;
+        let imported = import_map[&mod_id].iter().find_map(|import| {
+            module_map[&import.module]
+                .reserved_consts
+                .keys()
+                .find(|name| import.paths(&(*name).clone().into()).contains(&bound))
+                .map(|name| (import.module, name))
+        });
+
+        let private = import_map[&mod_id]
+            .iter()
+            .any(|import| import.private_paths().contains(&bound));
+
+        match imported {
+            Some((dependency, name)) => self.evaluate_const(dependency, name),
+            None if private => Err(AnalysisError::PrivateItem(path.clone(), path.span())),
+            None => Err(TopLevelError::NonConstantExpr(path.span()).into()),
+        }
+    }
+
+    fn reference(&mut self, mod_id: ModuleId, name: &AstNode<Ident>) -> Result<Literal, AnalysisError> {
+        if self.module_map[&mod_id].reserved_consts.contains_key(name.data()) {
+            self.evaluate_const(mod_id, name.data())
+        } else {
+            self.imported(mod_id, &AstModulePath(vec![name.clone()]))
+        }
+    }
+}
+
+///
+/// Folds `expr` into a literal. Constants bound to a path are looked up with `constant`.
+///
+fn fold<F>(expr: &Expr, span: Span, constant: &mut F) -> Result<Literal, AnalysisError>
+where
+    F: FnMut(&AstModulePath) -> Result<Literal, AnalysisError>,
+{
+    match *expr {
+        Expr::Literal(ref literal) => Ok(literal.data().clone()),
+
+        Expr::Binding(ref name) => constant(&AstModulePath(vec![name.clone()])),
+
+        Expr::Path(ref path) => match *path.data() {
+            TypedPath::NillArity(ref path) => constant(path),
+
+            _ => Err(TopLevelError::NonConstantExpr(path.span()).into()),
+        },
+
+        Expr::Bin(ref bin_expr) => {
+            let (bin_expr, span) = (bin_expr.data(), bin_expr.span());
+            let lhs = fold(&bin_expr.lhs, span.clone(), constant)?;
+            let rhs = fold(&bin_expr.rhs, span.clone(), constant)?;
+
+            fold_bin_op(&bin_expr.op, lhs, rhs, span)
+        }
+
+        Expr::Uni(ref uni_expr) => {
+            let (uni_expr, span) = (uni_expr.data(), uni_expr.span());
+            let operand = fold(&uni_expr.expr, span.clone(), constant)?;
+
+            fold_uni_op(&uni_expr.op, operand, span)
+        }
+
+        _ => Err(TopLevelError::NonConstantExpr(span).into()),
+    }
+}
+
+fn fold_bin_op(op: &BinOp, lhs: Literal, rhs: Literal, span: Span) -> Result<Literal, AnalysisError> {
+    use crate::ast::BinOp::*;
+
+    let expected = match *op {
+        Add | Sub | Mul | Div | Mod | GreaterEq | LesserEq | Greater | Lesser => {
+            vec![AbstractType::Int(span.clone()), AbstractType::Float(span.clone())]
+        }
+
+        LogicalAnd | LogicalOr => vec![AbstractType::Bool(span.clone())],
+
+        BitAnd | BitOr | BitXor | Shl | Shr => vec![AbstractType::Int(span.clone())],
+
+        Eq | InEq => Vec::new(),
+    };
+
+    let folded = match (op, &lhs, &rhs) {
+        (&Add, &Literal::Int(l), &Literal::Int(r)) => l.checked_add(r).map(Literal::Int),
+        (&Sub, &Literal::Int(l), &Literal::Int(r)) => l.checked_sub(r).map(Literal::Int),
+        (&Mul, &Literal::Int(l), &Literal::Int(r)) => l.checked_mul(r).map(Literal::Int),
+        (&Div, &Literal::Int(l), &Literal::Int(r)) => l.checked_div(r).map(Literal::Int),
+        (&Mod, &Literal::Int(l), &Literal::Int(r)) => l.checked_rem(r).map(Literal::Int),
+
+        (&Add, &Literal::Float(l), &Literal::Float(r)) => Some(Literal::Float(l + r)),
+        (&Sub, &Literal::Float(l), &Literal::Float(r)) => Some(Literal::Float(l - r)),
+        (&Mul, &Literal::Float(l), &Literal::Float(r)) => Some(Literal::Float(l * r)),
+        (&Div, &Literal::Float(l), &Literal::Float(r)) => Some(Literal::Float(l / r)),
+        (&Mod, &Literal::Float(l), &Literal::Float(r)) => Some(Literal::Float(l % r)),
+
+        (&BitAnd, &Literal::Int(l), &Literal::Int(r)) => Some(Literal::Int(l & r)),
+        (&BitOr, &Literal::Int(l), &Literal::Int(r)) => Some(Literal::Int(l | r)),
+        (&BitXor, &Literal::Int(l), &Literal::Int(r)) => Some(Literal::Int(l ^ r)),
+        (&Shl, &Literal::Int(l), &Literal::Int(r)) => shift_left(l, r).map(Literal::Int),
+        (&Shr, &Literal::Int(l), &Literal::Int(r)) => shift_right(l, r).map(Literal::Int),
+
+        (&LogicalAnd, &Literal::Bool(l), &Literal::Bool(r)) => Some(Literal::Bool(l && r)),
+        (&LogicalOr, &Literal::Bool(l), &Literal::Bool(r)) => Some(Literal::Bool(l || r)),
+
+        (&GreaterEq, &Literal::Int(l), &Literal::Int(r)) => Some(Literal::Bool(l >= r)),
+        (&LesserEq, &Literal::Int(l), &Literal::Int(r)) => Some(Literal::Bool(l <= r)),
+        (&Greater, &Literal::Int(l), &Literal::Int(r)) => Some(Literal::Bool(l > r)),
+        (&Lesser, &Literal::Int(l), &Literal::Int(r)) => Some(Literal::Bool(l < r)),
+
+        (&GreaterEq, &Literal::Float(l), &Literal::Float(r)) => Some(Literal::Bool(l >= r)),
+        (&LesserEq, &Literal::Float(l), &Literal::Float(r)) => Some(Literal::Bool(l <= r)),
+        (&Greater, &Literal::Float(l), &Literal::Float(r)) => Some(Literal::Bool(l > r)),
+        (&Lesser, &Literal::Float(l), &Literal::Float(r)) => Some(Literal::Bool(l < r)),
+
+        (&Eq, _, _) | (&InEq, _, _) => {
+            let lhs_type = literal_type(&lhs, span.clone());
+            let rhs_type = literal_type(&rhs, span.clone());
+            if std::mem::discriminant(&lhs) != std::mem::discriminant(&rhs) {
+                return Err(TypeError::LhsRhsInEq(lhs_type, rhs_type, span).into());
+            }
+
+            let equal = lhs == rhs;
+            Some(Literal::Bool(if *op == Eq { equal } else { !equal }))
+        }
+
+        _ => {
+            return Err(TypeError::BinOp {
+                op: op.clone(),
+                expected,
+                lhs: literal_type(&lhs, span.clone()),
+                rhs: literal_type(&rhs, span.clone()),
+                span,
+            }
+            .into());
+        }
+    };
+
+    folded.ok_or_else(|| TopLevelError::ConstArithmetic(span).into())
+}
+
+fn fold_uni_op(op: &UniOp, operand: Literal, span: Span) -> Result<Literal, AnalysisError> {
+    let folded = match (op, &operand) {
+        (&UniOp::Negate, &Literal::Int(i)) => i.checked_neg().map(Literal::Int),
+        (&UniOp::Negate, &Literal::Float(f)) => Some(Literal::Float(-f)),
+        (&UniOp::LogicalInvert, &Literal::Bool(b)) => Some(Literal::Bool(!b)),
+        (&UniOp::BitNot, &Literal::Int(i)) => Some(Literal::Int(!i)),
+
+        // References have no constant value
+        (&UniOp::Ref, _) | (&UniOp::Deref, _) => {
+            return Err(TopLevelError::NonConstantExpr(span).into());
+        }
+
+        (&UniOp::Negate, _) | (&UniOp::LogicalInvert, _) | (&UniOp::BitNot, _) => {
+            let expected = match *op {
+                UniOp::Negate => {
+                    vec![AbstractType::Int(span.clone()), AbstractType::Float(span.clone())]
+                }
+                UniOp::BitNot => vec![AbstractType::Int(span.clone())],
+                _ => vec![AbstractType::Bool(span.clone())],
+            };
+
+            return Err(TypeError::UniOp {
+                op: op.clone(),
+                expected,
+                expr: literal_type(&operand, span.clone()),
+                span,
+            }
+            .into());
+        }
+    };
+
+    folded.ok_or_else(|| TopLevelError::ConstArithmetic(span).into())
+}
+
+///
+/// Shifts with the interpreter's semantics: shifting by 64 or more bits shifts out every bit
+///   and negative shift amounts are errors.
+///
+fn shift_left(value: i64, amount: i64) -> Option<i64> {
+    match amount {
+        0..=63 => Some(value << amount),
+        64..=std::i64::MAX => Some(0),
+        _ => None,
+    }
+}
+
+fn shift_right(value: i64, amount: i64) -> Option<i64> {
+    match amount {
+        0..=63 => Some(value >> amount),
+        64..=std::i64::MAX => Some(value >> 63),
+        _ => None,
+    }
+}
@@ -106,6 +106,8 @@ pub struct LocalVarDeclData {
 pub struct LoopData {
     pub loop_id: LoopId,
     pub span: Span,
+    /// `loop` without a `break`. Only set on the Node::LoopFoot.
+    pub infinite: bool,
 }
 
 #[derive(Debug, Clone)]
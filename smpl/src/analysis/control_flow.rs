@@ -2,6 +2,8 @@ use petgraph::graph;
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
 
+use std::collections::VecDeque;
+
 use crate::ast;
 
 use crate::span::Span;
@@ -26,7 +28,16 @@ use super::analysis_context::{
 use super::control_data::*;
 use super::anon_storage::AnonStorage;
 
-type InternalLoopData = Option<(graph::NodeIndex, graph::NodeIndex, LoopId)>;
+///
+/// Loop enclosing the statements being generated. Targeted by `break` and `continue`.
+///
+struct LoopScope {
+    loop_id: LoopId,
+    label: Option<ast::Ident>,
+    /// Hidden local receiving the `break` values of a `loop` expression
+    value: Option<ast::Ident>,
+    broken: bool,
+}
 
 macro_rules! node_w {
     ($CFG: expr, $node: expr) => {
@@ -275,7 +286,7 @@ impl CFG {
             _ => panic!("Should only be given a Node::Break"),
         }
 
-        let neighbors = neighbors!(self, id);
+        let mut neighbors = neighbors!(self, id);
         let neighbor_count = neighbors.clone().count();
 
         if neighbor_count == 2 {
@@ -293,6 +304,7 @@ impl CFG {
                 }
             }
         } else if neighbor_count == 1 {
+            return neighbors.next().unwrap();
         } else {
             panic!("Node::Continue points to {} neighbors. Nodes should never point towards more than 2 neighbors but at least 1 (except Node::End).", neighbor_count);
         }
@@ -359,7 +371,7 @@ impl CFG {
             local_data,
             &mut anonymous_fns,
             instructions.into_iter(),
-            None,
+            &mut Vec::new(),
         )?;
 
         let mut previous = Some(cfg.start);
@@ -385,7 +397,7 @@ impl CFG {
                 &outer_scope,
                 &outer_context,
             )?;
-            if let AbstractType::Unit(_) = return_type {
+            if let AbstractType::Unit(_) = *return_type.expanded() {
                 append_node!(
                     cfg,
                     head,
@@ -418,7 +430,7 @@ impl CFG {
         local_data: &'b mut LocalData,
         anonymous_fns: &mut AnonStorage<ReservedAnonymousFn>,
         mut instructions: T,
-        loop_data: InternalLoopData,
+        loops: &mut Vec<LoopScope>,
     ) -> Result<BranchData, ControlFlowError>
     where
         T: Iterator<Item = ast::Stmt>,
@@ -430,7 +442,11 @@ impl CFG {
 
         let mut current_block = BasicBlock::new();
 
-        while let Some(next) = instructions.next() {
+        // Statements generated by desugaring (i.e. 'for' loops) that run before the rest of the
+        //   block
+        let mut desugared: VecDeque<Stmt> = VecDeque::new();
+
+        while let Some(next) = desugared.pop_front().or_else(|| instructions.next()) {
             match next {
                 // Added to current basic block
                 Stmt::Expr(expr) => {
@@ -447,6 +463,45 @@ impl CFG {
                 Stmt::ExprStmt(expr_stmt) => {
                     let (expr_stmt, expr_stmt_span) = expr_stmt.to_data();
                     match expr_stmt {
+                        // 'if' and block expressions write to a hidden local before the
+                        //   statement using their value
+                        ExprStmt::LocalVarDecl(mut decl)
+                            if decl.var_init.as_ref().is_some_and(is_value_expr) => {
+                            let init = decl.var_init.as_mut().unwrap();
+                            let mut lowered = desugar_value_expr(init, expr_stmt_span.clone());
+                            lowered.push(Stmt::ExprStmt(AstNode::new(
+                                ExprStmt::LocalVarDecl(decl),
+                                expr_stmt_span,
+                            )));
+                            for stmt in lowered.into_iter().rev() {
+                                desugared.push_front(stmt);
+                            }
+                        }
+
+                        ExprStmt::Assignment(mut assignment)
+                            if is_value_expr(&assignment.value) => {
+                            let value = &mut assignment.value;
+                            let mut lowered = desugar_value_expr(value, expr_stmt_span.clone());
+                            lowered.push(Stmt::ExprStmt(AstNode::new(
+                                ExprStmt::Assignment(assignment),
+                                expr_stmt_span,
+                            )));
+                            for stmt in lowered.into_iter().rev() {
+                                desugared.push_front(stmt);
+                            }
+                        }
+
+                        ExprStmt::Return(span, Some(mut value)) if is_value_expr(&value) => {
+                            let mut lowered = desugar_value_expr(&mut value, span.clone());
+                            lowered.push(Stmt::ExprStmt(AstNode::new(
+                                ExprStmt::Return(span, Some(value)),
+                                expr_stmt_span,
+                            )));
+                            for stmt in lowered.into_iter().rev() {
+                                desugared.push_front(stmt);
+                            }
+                        }
+
                         // Append assignment node to current basic block
                         ExprStmt::Assignment(assignment) => {
                             let (mut anon_fn, assignment) = typed_ast::Assignment::new(
@@ -509,70 +564,114 @@ impl CFG {
                             );
                         }
 
+                        // `break value;` becomes `value_local = value; break;`
+                        ExprStmt::Break(span, label, Some(value)) => {
+                            let target = target_loop(
+                                loops,
+                                label.as_ref(),
+                                ControlFlowError::BadBreak(span.clone()),
+                            )?;
+                            let hidden = loops[target]
+                                .value
+                                .clone()
+                                .ok_or_else(|| ControlFlowError::BreakValue(span.clone()))?;
+
+                            let name = Path(vec![PathSegment::Ident(AstNode::new(
+                                hidden.clone(),
+                                span.clone(),
+                            ))]);
+                            let assignment = Stmt::ExprStmt(AstNode::new(
+                                ExprStmt::Assignment(Assignment {
+                                    name: AstNode::new(name, span.clone()),
+                                    value,
+                                    deref: false,
+                                }),
+                                span.clone(),
+                            ));
+
+                            // Targets the loop by its value local
+                            let target_label = Some(AstNode::new(hidden, span.clone()));
+                            let br = Stmt::ExprStmt(AstNode::new(
+                                ExprStmt::Break(span, target_label, None),
+                                expr_stmt_span,
+                            ));
+
+                            desugared.push_front(br);
+                            desugared.push_front(assignment);
+                        }
+
                         // Append break node to current basic block
-                        ExprStmt::Break(span) => match loop_data {
-                            Some((_, _foot, loop_id)) => {
-                                if current_block.is_empty() == false {
-                                    append_node!(
-                                        self,
-                                        head,
-                                        previous,
-                                        Node::Block(current_block)
-                                    );
-                                    current_block = BasicBlock::new();
-                                }
+                        ExprStmt::Break(span, label, None) => {
+                            let target = target_loop(
+                                loops,
+                                label.as_ref(),
+                                ControlFlowError::BadBreak(span.clone()),
+                            )?;
+
+                            let loop_scope = &mut loops[target];
+                            let lowered = match (&label, &loop_scope.value) {
+                                (Some(label), Some(value)) => label.data() == value,
+                                _ => false,
+                            };
+                            if loop_scope.value.is_some() && !lowered {
+                                return Err(ControlFlowError::MissingBreakValue(span));
+                            }
+                            loop_scope.broken = true;
+                            let loop_id = loop_scope.loop_id;
+
+                            if current_block.is_empty() == false {
                                 append_node!(
                                     self,
                                     head,
                                     previous,
-                                    Node::Break(LoopData {
-                                        loop_id: loop_id,
-                                        span: span,
-                                    })
+                                    Node::Block(current_block)
                                 );
+                                current_block = BasicBlock::new();
                             }
-
-                            None => {
-                                return Err(ControlFlowError::BadBreak(span))
-                            }
-                        },
+                            append_node!(
+                                self,
+                                head,
+                                previous,
+                                Node::Break(LoopData {
+                                    loop_id: loop_id,
+                                    span: span,
+                                    infinite: false,
+                                })
+                            );
+                        }
 
                         // Append continue node to current basic block
-                        ExprStmt::Continue(span) => match loop_data {
-                            Some((_loop_head, _, loop_id)) => {
-                                if current_block.is_empty() == false {
-                                    append_node!(
-                                        self,
-                                        head,
-                                        previous,
-                                        Node::Block(current_block)
-                                    );
-                                    current_block = BasicBlock::new();
-                                }
+                        ExprStmt::Continue(span, label) => {
+                            let target = target_loop(
+                                loops,
+                                label.as_ref(),
+                                ControlFlowError::BadContinue(span.clone()),
+                            )?;
+                            let loop_id = loops[target].loop_id;
 
+                            if current_block.is_empty() == false {
                                 append_node!(
                                     self,
                                     head,
                                     previous,
-                                    Node::Continue(LoopData {
-                                        loop_id: loop_id,
-                                        span: span,
-                                    })
+                                    Node::Block(current_block)
                                 );
+                                current_block = BasicBlock::new();
                             }
 
-                            None => {
-                                return Err(ControlFlowError::BadContinue(span))
-                            }
-                        },
+                            append_node!(
+                                self,
+                                head,
+                                previous,
+                                Node::Continue(LoopData {
+                                    loop_id: loop_id,
+                                    span: span,
+                                    infinite: false,
+                                })
+                            );
+                        }
 
                         ExprStmt::While(while_data) => {
-                            // Generate a fragment of a CFG beginning with LoopHead and ending with
-                            //   LoopFoot.
-                            // LoopHead connects to the loop body via a TRUE edge (or to the LoopFoot
-                            //   in the case of an empty body)
-                            // LoopHead connects to the LoopFoot via a FALSE edge
-
                             // Append current basic block if not empty
                             if current_block.is_empty() == false {
                                 append_node!(
@@ -584,102 +683,70 @@ impl CFG {
                                 current_block = BasicBlock::new();
                             }
 
-                            let (block, _) = while_data.block.to_data();
-
-                            let loop_id = local_data.new_loop_id();
-
-                            let expr_data = {
-                                let (conditional, con_span) =
-                                    while_data.conditional.to_data();
-                                let (mut anon_fn, expr) =
-                                    expr_flow::flatten(global_data, local_data, conditional);
-                                anonymous_fns.append(&mut anon_fn);
-
-                                ExprData {
-                                    expr: expr,
-                                    span: con_span,
-                                }
-                            };
+                            let (loop_head, loop_foot) = self.generate_loop(
+                                universe,
+                                global_data,
+                                local_data,
+                                anonymous_fns,
+                                loops,
+                                Some(while_data.conditional),
+                                ast::AstNode::new(
+                                    ast::Loop {
+                                        label: while_data.label,
+                                        block: while_data.block,
+                                        value: None,
+                                    },
+                                    expr_stmt_span,
+                                ),
+                            )?;
 
-                            let loop_data = LoopData {
-                                loop_id: loop_id,
-                                span: expr_stmt_span.clone(),
-                            };
+                            append_node_index!(self, head, previous, loop_head);
+                            previous = Some(loop_foot);
+                        }
 
-                            let loop_head = self.graph.add_node(
-                                Node::LoopHead(loop_data.clone(), expr_data),
-                            );
-                            let loop_foot =
-                                self.graph.add_node(Node::LoopFoot(loop_data));
-
-                            // Connect loop foot to loop head with a backedge
-                            self.graph.add_edge(
-                                loop_foot,
-                                loop_head,
-                                Edge::BackEdge,
-                            );
+                        ExprStmt::Loop(loop_data) => {
+                            // Append current basic block if not empty
+                            if current_block.is_empty() == false {
+                                append_node!(
+                                    self,
+                                    head,
+                                    previous,
+                                    Node::Block(current_block)
+                                );
+                                current_block = BasicBlock::new();
+                            }
 
-                            // Append the loop head to the graph
-                            append_node_index!(self, head, previous, loop_head);
-                            let instructions = block.0;
-                            let loop_body = self.generate_scoped_block(
+                            let (loop_head, loop_foot) = self.generate_loop(
                                 universe,
                                 global_data,
                                 local_data,
                                 anonymous_fns,
-                                instructions.into_iter(),
-                                Some((loop_head, loop_foot, loop_id)),
+                                loops,
+                                None,
+                                ast::AstNode::new(loop_data, expr_stmt_span),
                             )?;
 
-                            // Connect the condition node to the loop foot by the FALSE path
-                            self.graph.add_edge(
-                                loop_head,
-                                loop_foot,
-                                Edge::False,
-                            );
-
-                            if let Some(loop_body_head) = loop_body.head {
-                                let scope_enter =
-                                    self.graph.add_node(Node::EnterScope);
-                                let scope_exit =
-                                    self.graph.add_node(Node::ExitScope);
-
-                                // Connect the scope enter/exit to the loop body by the TRUE path
-                                self.graph.add_edge(
-                                    loop_head,
-                                    scope_enter,
-                                    Edge::True,
-                                );
+                            append_node_index!(self, head, previous, loop_head);
+                            previous = Some(loop_foot);
+                        }
 
-                                // Connect the loop body to the scope enter and exit
-                                self.graph.add_edge(
-                                    scope_enter,
-                                    loop_body_head,
-                                    Edge::Normal,
-                                );
-                                self.graph.add_edge(
-                                    loop_body.foot.unwrap(),
-                                    scope_exit,
-                                    Edge::Normal,
-                                );
+                        ExprStmt::For(for_data) => {
+                            for stmt in desugar_for(for_data, expr_stmt_span).into_iter().rev() {
+                                desugared.push_front(stmt);
+                            }
+                        }
 
-                                // Connect scope exit to loop foot
-                                self.graph.add_edge(
-                                    scope_exit,
-                                    loop_foot,
-                                    Edge::Normal,
-                                );
-                            } else {
-                                // Empty loop body
-                                // Connect the condition node to the loop foot by the TRUE path
-                                self.graph.add_edge(
-                                    loop_head,
-                                    loop_foot,
-                                    Edge::True,
-                                );
+                        ExprStmt::LocalTupleDecl(decl) => {
+                            for stmt in desugar_tuple_decl(decl, expr_stmt_span).into_iter().rev() {
+                                desugared.push_front(stmt);
                             }
+                        }
 
-                            previous = Some(loop_foot);
+                        ExprStmt::CompoundAssignment(assignment) => {
+                            let lowered = desugar_compound_assignment(assignment, expr_stmt_span);
+                            for stmt in lowered.into_iter().rev() {
+                                desugared.push_front(stmt);
+                            }
                         }
 
                         ExprStmt::If(if_data) => {
@@ -705,7 +772,7 @@ impl CFG {
                                 anonymous_fns: &mut AnonStorage<ReservedAnonymousFn>,
                                 body: AstNode<Block>,
                                 condition: Option<AstNode<Expr>>,
-                                loop_data: InternalLoopData,
+                                loops: &mut Vec<LoopScope>,
                             ) -> Result<BranchData, ControlFlowError>
                             {
                                 let (block, _) = body.to_data();
@@ -717,7 +784,7 @@ impl CFG {
                                     local_data,
                                     anonymous_fns,
                                     instructions.into_iter(),
-                                    loop_data,
+                                    loops,
                                 )?;
 
                                 let scope_enter =
@@ -849,7 +916,7 @@ impl CFG {
                                 anonymous_fns,
                                 first_branch.block,
                                 Some(first_branch.conditional),
-                                loop_data,
+                                loops,
                             )?;
 
                             // Append the first branch to the overall CFG
@@ -877,7 +944,7 @@ impl CFG {
                                     anonymous_fns,
                                     branch.block,
                                     Some(branch.conditional),
-                                    loop_data,
+                                    loops,
                                 )?;
 
                                 let branch_head = branch.head
@@ -930,7 +997,7 @@ impl CFG {
                                         anonymous_fns,
                                         block,
                                         None,
-                                        loop_data,
+                                        loops,
                                     )?;
                                     let else_head = else_branch.head
                                         .expect("generate_branch() head should always be Some");
@@ -979,6 +1046,512 @@ impl CFG {
             foot: previous,
         })
     }
+
+    ///
+    /// Generate a fragment of a CFG beginning with LoopHead and ending with LoopFoot.
+    /// LoopHead connects to the loop body via a TRUE edge (or to the LoopFoot
+    ///   in the case of an empty body)
+    /// LoopHead connects to the LoopFoot via a FALSE edge
+    ///
+    /// `loop` has no conditional and always takes the TRUE edge.
+    ///
+    /// Returns the (LoopHead, LoopFoot) of the fragment.
+    ///
+    fn generate_loop(
+        &mut self,
+        universe: &AnalysisUniverse,
+        global_data: &mut GlobalData,
+        local_data: &mut LocalData,
+        anonymous_fns: &mut AnonStorage<ReservedAnonymousFn>,
+        loops: &mut Vec<LoopScope>,
+        conditional: Option<ast::AstNode<ast::Expr>>,
+        loop_node: ast::AstNode<ast::Loop>,
+    ) -> Result<(graph::NodeIndex, graph::NodeIndex), ControlFlowError> {
+        let (loop_node, span) = loop_node.to_data();
+        let (block, _) = loop_node.block.to_data();
+        let label = loop_node.label.map(|label| label.to_data().0);
+        let value = loop_node.value;
+
+        let loop_id = local_data.new_loop_id();
+        let is_loop = conditional.is_none();
+
+        let expr_data = {
+            let (conditional, con_span) = match conditional {
+                Some(conditional) => conditional.to_data(),
+                None => (
+                    ast::Expr::Literal(ast::AstNode::new(ast::Literal::Bool(true), span.clone())),
+                    span.clone(),
+                ),
+            };
+            let (mut anon_fn, expr) =
+                expr_flow::flatten(global_data, local_data, conditional);
+            anonymous_fns.append(&mut anon_fn);
+
+            ExprData {
+                expr: expr,
+                span: con_span,
+            }
+        };
+
+        let loop_data = LoopData {
+            loop_id: loop_id,
+            span: span.clone(),
+            infinite: false,
+        };
+
+        let loop_head = self.graph.add_node(
+            Node::LoopHead(loop_data.clone(), expr_data),
+        );
+        let loop_foot =
+            self.graph.add_node(Node::LoopFoot(loop_data));
+
+        // Connect loop foot to loop head with a backedge
+        self.graph.add_edge(
+            loop_foot,
+            loop_head,
+            Edge::BackEdge,
+        );
+
+        loops.push(LoopScope {
+            loop_id,
+            label,
+            value,
+            broken: false,
+        });
+
+        let instructions = block.0;
+        let loop_body = self.generate_scoped_block(
+            universe,
+            global_data,
+            local_data,
+            anonymous_fns,
+            instructions.into_iter(),
+            loops,
+        )?;
+
+        let loop_scope = loops.pop().unwrap();
+        if loop_scope.value.is_some() && !loop_scope.broken {
+            return Err(ControlFlowError::MissingBreakValue(span));
+        }
+
+        if is_loop && !loop_scope.broken {
+            if let Node::LoopFoot(ref mut data) = *self.node_weight_mut(loop_foot) {
+                data.infinite = true;
+            }
+        }
+
+        // Connect the condition node to the loop foot by the FALSE path
+        self.graph.add_edge(
+            loop_head,
+            loop_foot,
+            Edge::False,
+        );
+
+        if let Some(loop_body_head) = loop_body.head {
+            let scope_enter =
+                self.graph.add_node(Node::EnterScope);
+            let scope_exit =
+                self.graph.add_node(Node::ExitScope);
+
+            // Connect the scope enter/exit to the loop body by the TRUE path
+            self.graph.add_edge(
+                loop_head,
+                scope_enter,
+                Edge::True,
+            );
+
+            // Connect the loop body to the scope enter and exit
+            self.graph.add_edge(
+                scope_enter,
+                loop_body_head,
+                Edge::Normal,
+            );
+            self.graph.add_edge(
+                loop_body.foot.unwrap(),
+                scope_exit,
+                Edge::Normal,
+            );
+
+            // Connect scope exit to loop foot
+            self.graph.add_edge(
+                scope_exit,
+                loop_foot,
+                Edge::Normal,
+            );
+        } else {
+            // Empty loop body
+            // Connect the condition node to the loop foot by the TRUE path
+            self.graph.add_edge(
+                loop_head,
+                loop_foot,
+                Edge::True,
+            );
+        }
+
+        Ok((loop_head, loop_foot))
+    }
+}
+
+///
+/// Finds the loop targeted by a `break` or `continue`. Unlabeled targets are the innermost loop.
+///
+/// Breaks lowered from `break value;` target their loop by its value local.
+///
+fn target_loop(
+    loops: &[LoopScope],
+    label: Option<&ast::AstNode<ast::Ident>>,
+    outside_loop: ControlFlowError,
+) -> Result<usize, ControlFlowError> {
+    match label {
+        Some(label) => loops
+            .iter()
+            .rposition(|loop_scope| {
+                loop_scope.label.as_ref() == Some(label.data())
+                    || loop_scope.value.as_ref() == Some(label.data())
+            })
+            .ok_or_else(|| ControlFlowError::UnknownLabel(label.data().clone(), label.span())),
+
+        None if loops.is_empty() => Err(outside_loop),
+
+        None => Ok(loops.len() - 1),
+    }
+}
+
+///
+/// Rewrites a 'for' loop into a 'while' loop over a hidden index.
+///
+/// for x in a..b { ... }
+///
+/// let __for_index = a;
+/// let __for_end = b;
+/// while __for_index < __for_end {
+///     let x = __for_index;
+///     __for_index = __for_index + 1;
+///     ...
+/// }
+///
+/// Collections bind the iterable to a hidden variable and use the iteration protocol
+///   (IterOp::Len, IterOp::Get) for the bounds and elements.
+///
+/// The index is incremented before the body so 'continue' advances the loop.
+///
+fn desugar_for(for_data: ast::For, span: Span) -> Vec<ast::Stmt> {
+    use crate::ast::*;
+
+    // Hidden variables are unique by the location of the loop
+    let id = span.start().byte_index;
+    let hidden = |name: &str| Ident(format!("__for_{}_{}", name, id));
+    fn node<T: std::fmt::Debug>(span: &Span, data: T) -> AstNode<T> {
+        AstNode::new(data, span.clone())
+    }
+
+    let binding = |ident: Ident| Expr::Binding(AstNode::new(ident, span.clone()));
+    let let_stmt = |name: Ident, init: Expr| {
+        Stmt::ExprStmt(AstNode::new(
+            ExprStmt::LocalVarDecl(LocalVarDecl {
+                var_type: None,
+                var_name: AstNode::new(name, span.clone()),
+                var_init: Some(init),
+                tuple_arity: None,
+            }),
+            span.clone(),
+        ))
+    };
+
+    let index = hidden("index");
+    let end = hidden("end");
+
+    let mut stmts = Vec::new();
+
+    let element_expr = match for_data.iterable {
+        // The bounds are evaluated left to right
+        ForIterable::Range(start, stop) => {
+            stmts.push(let_stmt(index.clone(), start.to_data().0));
+            stmts.push(let_stmt(end.clone(), stop.to_data().0));
+            binding(index.clone())
+        }
+
+        ForIterable::Collection(iterable) => {
+            let iter = hidden("iter");
+            stmts.push(let_stmt(iter.clone(), iterable.to_data().0));
+
+            let len = IterOp::Len(Box::new(binding(iter.clone())));
+            stmts.push(let_stmt(end.clone(), Expr::Iter(node(&span, len))));
+
+            stmts.push(let_stmt(index.clone(), Expr::Literal(node(&span, Literal::Int(0)))));
+
+            let get = IterOp::Get(
+                Box::new(binding(iter)),
+                Box::new(binding(index.clone())),
+            );
+            Expr::Iter(node(&span, get))
+        }
+    };
+
+    let conditional = Expr::Bin(node(&span, BinExpr {
+        op: BinOp::Lesser,
+        lhs: Box::new(binding(index.clone())),
+        rhs: Box::new(binding(end)),
+    }));
+
+    let increment = Stmt::ExprStmt(node(&span, ExprStmt::Assignment(Assignment {
+        name: node(&span, Path(vec![PathSegment::Ident(node(&span, index.clone()))])),
+        value: Expr::Bin(node(&span, BinExpr {
+            op: BinOp::Add,
+            lhs: Box::new(binding(index)),
+            rhs: Box::new(Expr::Literal(node(&span, Literal::Int(1)))),
+        })),
+        deref: false,
+    })));
+
+    let (body, body_span) = for_data.block.to_data();
+    let mut loop_body = vec![let_stmt(for_data.var_name.data().clone(), element_expr), increment];
+    loop_body.extend(body.0);
+
+    stmts.push(Stmt::ExprStmt(node(&span, ExprStmt::While(While {
+        label: for_data.label,
+        conditional: node(&span, conditional),
+        block: AstNode::new(Block(loop_body), body_span),
+    }))));
+
+    stmts
+}
+
+///
+/// Desugars `let (a, b) = init;` into:
+///
+/// ```text
+/// let tuple = init;
+/// let a = tuple.0;
+/// let b = tuple.1;
+/// ```
+///
+fn desugar_tuple_decl(decl: ast::LocalTupleDecl, span: Span) -> Vec<ast::Stmt> {
+    use crate::ast::*;
+
+    let tuple = Ident(format!("__tuple_{}", span.start().byte_index));
+    let let_stmt = |name: AstNode<Ident>, var_type, init: Expr, tuple_arity| {
+        Stmt::ExprStmt(AstNode::new(
+            ExprStmt::LocalVarDecl(LocalVarDecl {
+                var_type,
+                var_name: name,
+                var_init: Some(init),
+                tuple_arity,
+            }),
+            span.clone(),
+        ))
+    };
+
+    let mut stmts = vec![let_stmt(
+        AstNode::new(tuple.clone(), span.clone()),
+        decl.var_type,
+        decl.var_init,
+        Some(decl.var_names.len()),
+    )];
+
+    for (index, name) in decl.var_names.into_iter().enumerate() {
+        let name_span = name.span();
+        let field = Path(vec![
+            PathSegment::Ident(AstNode::new(tuple.clone(), span.clone())),
+            PathSegment::Ident(AstNode::new(Ident(index.to_string()), name_span.clone())),
+        ]);
+        let access = Expr::FieldAccess(AstNode::new(field, name_span));
+        stmts.push(let_stmt(name, None, access, None));
+    }
+
+    stmts
+}
+
+///
+/// Desugars `a[i].b[j] op= value;` into:
+///
+/// ```text
+/// let index_0 = i;
+/// let index_1 = j;
+/// a[index_0].b[index_1] = a[index_0].b[index_1] op value;
+/// ```
+///
+/// Indexing expressions are bound to hidden variables so they are evaluated once.
+///
+fn desugar_compound_assignment(assignment: ast::CompoundAssignment, span: Span) -> Vec<ast::Stmt> {
+    use crate::ast::*;
+
+    let (path, path_span) = assignment.name.to_data();
+
+    let mut stmts = Vec::new();
+    let mut segments = Vec::new();
+    for (index, segment) in path.0.into_iter().enumerate() {
+        match segment {
+            PathSegment::Ident(ident) => segments.push(PathSegment::Ident(ident)),
+
+            PathSegment::Indexing(ident, indexer) => {
+                let hidden = Ident(format!("__compound_{}_{}", span.start().byte_index, index));
+                stmts.push(Stmt::ExprStmt(AstNode::new(
+                    ExprStmt::LocalVarDecl(LocalVarDecl {
+                        var_type: None,
+                        var_name: AstNode::new(hidden.clone(), span.clone()),
+                        var_init: Some(*indexer),
+                        tuple_arity: None,
+                    }),
+                    span.clone(),
+                )));
+
+                let indexer = Expr::Binding(AstNode::new(hidden, span.clone()));
+                segments.push(PathSegment::Indexing(ident, Box::new(indexer)));
+            }
+        }
+    }
+
+    let path = Path(segments);
+    let current = match path.0.as_slice() {
+        [PathSegment::Ident(ident)] => Expr::Binding(ident.clone()),
+        [PathSegment::Indexing(ident, indexer)] => Expr::Indexing(AstNode::new(
+            Indexing {
+                array: Box::new(Expr::Binding(ident.clone())),
+                indexer: indexer.clone(),
+            },
+            path_span.clone(),
+        )),
+        _ => Expr::FieldAccess(AstNode::new(path.clone(), path_span.clone())),
+    };
+
+    let current = if assignment.deref {
+        Expr::Uni(AstNode::new(
+            UniExpr {
+                op: UniOp::Deref,
+                expr: Box::new(current),
+            },
+            path_span.clone(),
+        ))
+    } else {
+        current
+    };
+
+    let value = Expr::Bin(AstNode::new(
+        BinExpr {
+            op: assignment.op,
+            lhs: Box::new(current),
+            rhs: Box::new(assignment.value),
+        },
+        span.clone(),
+    ));
+
+    stmts.push(Stmt::ExprStmt(AstNode::new(
+        ExprStmt::Assignment(Assignment {
+            name: AstNode::new(path, path_span),
+            value,
+            deref: assignment.deref,
+        }),
+        span,
+    )));
+
+    stmts
+}
+
+fn is_value_expr(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::If(_) | ast::Expr::Block(_) | ast::Expr::Loop(_) => true,
+        _ => false,
+    }
+}
+
+///
+/// Desugars an `if`, block or `loop` expression into statements writing its value to a hidden
+///   local. The expression is replaced by the hidden local.
+///
+/// ```text
+/// let x = if c { s; a } else { b };
+///
+/// let value;
+/// if c { s; value = a; } else { value = b; }
+/// let x = value;
+/// ```
+///
+/// Block expressions become `if true { ... }` to keep their scope. `loop` expressions keep the
+///   hidden local and their `break value;` statements assign to it. The hidden local takes the
+///   type of its first assignment, so every branch's value must be compatible with the first
+///   branch. Nested value expressions are desugared when their branch is generated.
+///
+/// The parser only accepts value expressions as a `let` initializer, assigned, returned or
+///   `break` value, and rejects blocks ending in `return`, `break` or `continue`, so there is
+///   always a statement to lower them in front of and every branch assigns the hidden local.
+///
+fn desugar_value_expr(value: &mut ast::Expr, span: Span) -> Vec<ast::Stmt> {
+    use crate::ast::*;
+
+    let hidden = Ident(format!("__value_{}", span.start().byte_index));
+    let binding = Expr::Binding(AstNode::new(hidden.clone(), span.clone()));
+
+    let assign_value = |block: AstNode<BlockExpr>| {
+        let (block, block_span) = block.to_data();
+        let (value, value_span) = block.value.to_data();
+
+        let name = Path(vec![PathSegment::Ident(AstNode::new(hidden.clone(), value_span.clone()))]);
+        let mut stmts = block.stmts;
+        stmts.push(Stmt::ExprStmt(AstNode::new(
+            ExprStmt::Assignment(Assignment {
+                name: AstNode::new(name, value_span.clone()),
+                value,
+                deref: false,
+            }),
+            value_span,
+        )));
+
+        AstNode::new(Block(stmts), block_span)
+    };
+
+    let value_stmt = match std::mem::replace(value, binding) {
+        Expr::If(if_expr) => {
+            let (if_expr, _) = if_expr.to_data();
+            let branches = if_expr
+                .branches
+                .into_iter()
+                .map(|branch| Branch {
+                    conditional: branch.conditional,
+                    block: assign_value(branch.block),
+                })
+                .collect();
+
+            ExprStmt::If(If {
+                branches,
+                default_block: Some(assign_value(if_expr.default_block)),
+            })
+        }
+
+        Expr::Block(block) => {
+            let conditional = Expr::Literal(AstNode::new(Literal::Bool(true), span.clone()));
+            let branch = Branch {
+                conditional: AstNode::new(conditional, span.clone()),
+                block: assign_value(block),
+            };
+
+            ExprStmt::If(If {
+                branches: vec![branch],
+                default_block: None,
+            })
+        }
+
+        Expr::Loop(loop_expr) => {
+            let (mut loop_expr, _) = loop_expr.to_data();
+            loop_expr.value = Some(hidden.clone());
+
+            ExprStmt::Loop(loop_expr)
+        }
+
+        _ => unreachable!("Only 'if', block and 'loop' expressions are desugared"),
+    };
+
+    let decl = Stmt::ExprStmt(AstNode::new(
+        ExprStmt::LocalVarDecl(LocalVarDecl {
+            var_type: None,
+            var_name: AstNode::new(hidden, span.clone()),
+            var_init: None,
+            tuple_arity: None,
+        }),
+        span.clone(),
+    ));
+
+    vec![decl, Stmt::ExprStmt(AstNode::new(value_stmt, span))]
 }
 
 #[cfg(test)]
@@ -11,7 +11,16 @@ pub enum AnalysisError {
     UnknownType(ModulePath, Span),
     UnknownBinding(Ident, Span),
     UnknownFn(ModulePath, Span),
+    UnknownTrait(ModulePath, Span),
+    /// Constants are folded at compile time and cannot be assigned to
+    ConstAssignment(Ident, Span),
     UnresolvedUses(Vec<(Ident, Span)>),
+    /// An imported item the used module does not declare
+    UnknownImport(Ident, Span),
+    /// An import binding a name already bound by a declaration or another import
+    AmbiguousImport(Ident, Span),
+    /// An item of another module not declared 'pub'
+    PrivateItem(ModulePath, Span),
     UnresolvedStructs(Vec<(Ident, Span)>),
     UnresolvedFns(Vec<(Ident, Span)>),
     TopLevelError(TopLevelError),
@@ -39,6 +48,24 @@ impl From<TopLevelError> for AnalysisError {
 pub enum TopLevelError {
     DuplicateTypes(Ident, Span),
     DuplicateFns(Ident, Span),
+    /// Impl blocks must target a struct or opaque type declared in the same module
+    InvalidImplType(Span),
+    IncompatibleSignature(Ident, Span),
+    /// A trait method without an implementation in an 'impl Trait for Type' block
+    MissingTraitMethod(Ident, Span),
+    /// A method in an 'impl Trait for Type' block that is not declared by the trait
+    UnknownTraitMethod(Ident, Span),
+    /// A trait method implementation that does not match the trait's signature
+    IncompatibleTraitMethod(Ident, Span),
+    /// A const or static sharing its name with another top-level binding
+    DuplicateGlobals(Ident, Span),
+    /// Const initializers may only contain literals, operators and other constants
+    NonConstantExpr(Span),
+    CyclicConst(Ident, Span),
+    /// A type alias expanding to itself
+    CyclicAlias(Ident, Span),
+    /// Overflow or division by zero while folding a constant
+    ConstArithmetic(Span),
 }
 
 #[derive(Clone, Debug)]
@@ -46,6 +73,12 @@ pub enum ControlFlowError {
     MissingReturn(Span),
     BadBreak(Span),
     BadContinue(Span),
+    /// `break` or `continue` naming a label of no enclosing loop
+    UnknownLabel(Ident, Span),
+    /// `break` with a value leaving a loop other than a `loop` expression
+    BreakValue(Span),
+    /// `loop` expression without a `break` giving its value
+    MissingBreakValue(Span),
 }
 
 impl From<ControlFlowError> for AnalysisError {
@@ -65,6 +98,13 @@ pub enum TypeError {
         span: Span,
     },
 
+    /// `let (a, b) = value;` where the value is not a tuple of two elements
+    TupleDestructureArity {
+        names: usize,
+        found_type: AbstractType,
+        span: Span,
+    },
+
     InEqFnReturn {
         expr: AbstractType,
         fn_return: AbstractType,
@@ -148,6 +188,14 @@ pub enum TypeError {
         span: Span,
     },
 
+    /// Struct field default of a type other than the field's
+    IncompatibleFieldDefault {
+        name: Ident,
+        field_type: AbstractType,
+        found_type: AbstractType,
+        span: Span,
+    },
+
     HeterogenousArray {
         expected: AbstractType,
         found: AbstractType,
@@ -160,6 +208,57 @@ pub enum TypeError {
         span: Span,
     },
 
+    NotAReference {
+        found: AbstractType,
+        span: Span,
+    },
+
+    NotIterable {
+        found: AbstractType,
+        span: Span,
+    },
+
+    NotFormattable {
+        found: AbstractType,
+        span: Span,
+    },
+
+    UnknownMethod {
+        name: Ident,
+        receiver: AbstractType,
+        span: Span,
+    },
+
+    UnsatisfiedTraitBound {
+        trait_name: Ident,
+        type_arg: AbstractType,
+        span: Span,
+    },
+
+    /// Trait bounds are only allowed on function type parameters
+    TraitBoundOnType {
+        ident: Ident,
+        span: Span,
+    },
+
+    /// Anonymous functions do not receive the trait dictionaries of the enclosing function
+    TraitBoundInAnonymousFn {
+        trait_name: Ident,
+        type_arg: AbstractType,
+        span: Span,
+    },
+
+    /// Instantiated functions with trait bounds must be called directly
+    TraitBoundedFnValue {
+        span: Span,
+    },
+
+    /// Partially applied functions need known parameter types
+    InvalidPartialApp {
+        fn_type: AbstractType,
+        span: Span,
+    },
+
     InvalidIndex {
         found: AbstractType,
         span: Span,
@@ -203,6 +302,11 @@ pub enum TypeError {
         span: Span,
     },
 
+    UnresolvedTypeParameter {
+        ident: Ident,
+        span: Span,
+    },
+
     // TODO: fill this in
     FnAnnLocalTypeParameter,
 
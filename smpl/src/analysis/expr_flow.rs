@@ -5,7 +5,8 @@ use super::typed_ast::*;
 use crate::span::Span;
 
 use crate::ast::{
-    ArrayInit as AstArrayInit, AstNode, Expr as AstExpr, TypedPath,
+    ArrayInit as AstArrayInit, AstNode, Expr as AstExpr, FormatPart as AstFormatPart,
+    IterOp as AstIterOp, Literal, TypedPath,
 };
 use super::analysis_context::{LocalData, GlobalData, ReservedAnonymousFn};
 use super::anon_storage::AnonStorage;
@@ -81,10 +82,13 @@ fn flatten_expr(
                     (name.data().clone(), expr)
                 })
                 .collect::<Vec<_>>();
+            let base = init.base.map(|base| {
+                Typed::untyped(flatten_expr(global_data, local_data, anonymous_fns, scope, *base).0)
+            });
             (
                 scope.map_tmp(
                     local_data.new_tmp_id(),
-                    Value::StructInit(StructInit::new(struct_name, field_init)),
+                    Value::StructInit(StructInit::new(struct_name, field_init, base)),
                     span.clone(),
                 ),
                 span,
@@ -135,6 +139,27 @@ fn flatten_expr(
             )
         }
 
+        AstExpr::FnCall(fn_call) if fn_call.data().is_partial() => {
+            let (fn_call, span) = fn_call.to_data();
+            let (fn_val, _fn_val_span) =
+                flatten_expr(global_data, local_data, anonymous_fns, scope, AstExpr::Path(fn_call.path));
+            let args = fn_call
+                .args
+                .unwrap_or_default()
+                .into_iter()
+                .map(|e| match e {
+                    AstExpr::Placeholder(_) => None,
+                    e => Some(Typed::untyped(
+                        flatten_expr(global_data, local_data, anonymous_fns, scope, e).0,
+                    )),
+                })
+                .collect();
+
+            let partial = PartialApp::new(fn_val, args);
+
+            (scope.map_tmp(local_data.new_tmp_id(), Value::PartialApp(partial), span.clone()), span)
+        }
+
         AstExpr::FnCall(fn_call) => {
             let (fn_call, span) = fn_call.to_data();
             let path = fn_call.path;
@@ -184,6 +209,52 @@ fn flatten_expr(
             }
         }
 
+        AstExpr::MethodCall(call) => {
+            let (call, span) = call.to_data();
+            let receiver = Typed::untyped(
+                flatten_expr(global_data, local_data, anonymous_fns, scope, *call.receiver).0,
+            );
+            let args = call
+                .args
+                .unwrap_or_default()
+                .into_iter()
+                .map(|arg| {
+                    Typed::untyped(flatten_expr(global_data, local_data, anonymous_fns, scope, arg).0)
+                })
+                .collect();
+
+            let call = MethodCall {
+                receiver,
+                method: call.method,
+                args,
+            };
+
+            (
+                scope.map_tmp(local_data.new_tmp_id(), Value::MethodCall(call), span.clone()),
+                span,
+            )
+        }
+
+        AstExpr::TupleInit(init) => {
+            let (init, span) = init.to_data();
+            let elements = init
+                .elements
+                .into_iter()
+                .map(|element| {
+                    Typed::untyped(
+                        flatten_expr(global_data, local_data, anonymous_fns, scope, element).0,
+                    )
+                })
+                .collect();
+
+            let init = TupleInit { elements };
+
+            (
+                scope.map_tmp(local_data.new_tmp_id(), Value::TupleInit(init), span.clone()),
+                span,
+            )
+        }
+
         AstExpr::Indexing(indexing) => {
             let (indexing, span) = indexing.to_data();
             let array_expr = indexing.array;
@@ -205,6 +276,86 @@ fn flatten_expr(
             )
         }
 
+        AstExpr::Slice(slice) => {
+            let (slice, span) = slice.to_data();
+
+            let array =
+                Typed::untyped(flatten_expr(global_data, local_data, anonymous_fns, scope, *slice.array).0);
+            let start =
+                Typed::untyped(flatten_expr(global_data, local_data, anonymous_fns, scope, *slice.start).0);
+            let end =
+                Typed::untyped(flatten_expr(global_data, local_data, anonymous_fns, scope, *slice.end).0);
+
+            let slice = Slice {
+                array: array,
+                start: start,
+                end: end,
+            };
+
+            (
+                scope.map_tmp(local_data.new_tmp_id(), Value::Slice(slice), span.clone()),
+                span,
+            )
+        }
+
+        AstExpr::Iter(iter) => {
+            let (iter, span) = iter.to_data();
+            let iter = match iter {
+                AstIterOp::Len(iterable) => {
+                    let iterable = Typed::untyped(
+                        flatten_expr(global_data, local_data, anonymous_fns, scope, *iterable).0,
+                    );
+                    IterOp::Len(iterable)
+                }
+
+                AstIterOp::Get(iterable, index) => {
+                    let iterable = Typed::untyped(
+                        flatten_expr(global_data, local_data, anonymous_fns, scope, *iterable).0,
+                    );
+                    let index = Typed::untyped(
+                        flatten_expr(global_data, local_data, anonymous_fns, scope, *index).0,
+                    );
+                    IterOp::Get { iterable, index }
+                }
+            };
+
+            (
+                scope.map_tmp(local_data.new_tmp_id(), Value::Iter(iter), span.clone()),
+                span,
+            )
+        }
+
+        // Each value is converted by its own tmp before the concatenation
+        AstExpr::Format(format) => {
+            let (format, span) = format.to_data();
+            let parts = format
+                .parts
+                .into_iter()
+                .map(|part| match part {
+                    AstFormatPart::Text(text) => Typed::untyped(scope.map_tmp(
+                        local_data.new_tmp_id(),
+                        Value::Literal(Literal::String(text)),
+                        span.clone(),
+                    )),
+
+                    AstFormatPart::Value(value) => {
+                        let (value, value_span) =
+                            flatten_expr(global_data, local_data, anonymous_fns, scope, value);
+                        Typed::untyped(scope.map_tmp(
+                            local_data.new_tmp_id(),
+                            Value::Stringify(Typed::untyped(value)),
+                            value_span,
+                        ))
+                    }
+                })
+                .collect();
+
+            (
+                scope.map_tmp(local_data.new_tmp_id(), Value::Format(parts), span.clone()),
+                span,
+            )
+        }
+
         AstExpr::Path(path) => {
             let (path, span) = path.to_data();
             let tmp = match path {
@@ -286,6 +437,14 @@ fn flatten_expr(
 
             (previous_result, span)
         }
+
+        AstExpr::If(_) | AstExpr::Block(_) | AstExpr::Loop(_) => {
+            unreachable!("'if', block and 'loop' expressions are desugared by the CFG")
+        }
+
+        AstExpr::Placeholder(_) => {
+            unreachable!("'_' placeholders are only parsed as function call arguments")
+        }
     }
 }
 
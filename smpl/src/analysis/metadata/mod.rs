@@ -22,7 +22,7 @@ pub use self::modules::*;
 use std::collections::{HashMap, HashSet};
 
 use super::error::AnalysisError;
-use crate::analysis::semantic_data::{FnId, ModuleId, Program, TypeId};
+use crate::analysis::semantic_data::{FnId, ModuleId, Program, StaticId, TraitId, TypeId};
 use crate::ast::{Annotation, Ident};
 
 ///
@@ -35,15 +35,22 @@ pub struct Metadata {
     fn_layout: HashMap<FnId, FnLayout>,
     field_ordering: HashMap<TypeId, FieldOrdering>,
     array_types: HashMap<ModuleId, Vec<TypeId>>,
+    opaque_modules: HashMap<TypeId, ModuleId>,
+    methods: HashMap<(TypeId, Ident), FnId>,
+    trait_impls: HashMap<(TraitId, TypeId), HashMap<Ident, FnId>>,
     main: Option<(FnId, ModuleId)>,
+    static_inits: Vec<(StaticId, FnId)>,
 
     fn_map: HashMap<(ModuleId, Ident), FnId>,
+    public_fns: HashSet<FnId>,
     builtin: HashSet<FnId>,
 
     unchecked_builtins_params: HashSet<FnId>,
 
     struct_annotations: HashMap<TypeId, HashMap<String, Option<String>>>,
     fn_annotations: HashMap<FnId, HashMap<String, Option<String>>>,
+
+    id_watermark: u64,
 }
 
 impl Metadata {
@@ -54,15 +61,32 @@ impl Metadata {
             fn_layout: HashMap::new(),
             field_ordering: HashMap::new(),
             array_types: HashMap::new(),
+            opaque_modules: HashMap::new(),
+            methods: HashMap::new(),
+            trait_impls: HashMap::new(),
             main: None,
+            static_inits: Vec::new(),
             fn_map: HashMap::new(),
+            public_fns: HashSet::new(),
             builtin: HashSet::new(),
             unchecked_builtins_params: HashSet::new(),
             struct_annotations: HashMap::new(),
             fn_annotations: HashMap::new(),
+            id_watermark: 0,
         }
     }
 
+    pub(super) fn set_id_watermark(&mut self, watermark: u64) {
+        self.id_watermark = watermark;
+    }
+
+    ///
+    /// All IDs generated during analysis are less than the watermark
+    ///
+    pub(crate) fn id_watermark(&self) -> u64 {
+        self.id_watermark
+    }
+
     pub(super) fn mod_metadata_mut(&mut self) -> &mut ModuleMetadata {
         &mut self.module_meta
     }
@@ -97,6 +121,17 @@ impl Metadata {
         self.fn_map.insert((mod_id, name), fn_id);
     }
 
+    pub(super) fn insert_public_fn(&mut self, id: FnId) {
+        self.public_fns.insert(id);
+    }
+
+    ///
+    /// Whether the module function `id` is reachable from other modules and the host
+    ///
+    pub fn is_public_fn(&self, id: FnId) -> bool {
+        self.public_fns.contains(&id)
+    }
+
     pub fn module_fn<T: Into<Ident>>(
         &self,
         mod_id: ModuleId,
@@ -152,6 +187,64 @@ impl Metadata {
         self.array_types.get(&id).map(|v| v.as_slice())
     }
 
+    pub(super) fn insert_opaque_module(
+        &mut self,
+        type_id: TypeId,
+        mod_id: ModuleId,
+    ) {
+        self.opaque_modules.insert(type_id, mod_id);
+    }
+
+    ///
+    /// Module declaring the opaque type
+    ///
+    pub fn opaque_module(&self, type_id: TypeId) -> Option<ModuleId> {
+        self.opaque_modules.get(&type_id).cloned()
+    }
+
+    pub(super) fn insert_method(
+        &mut self,
+        type_id: TypeId,
+        name: Ident,
+        fn_id: FnId,
+    ) {
+        self.methods.insert((type_id, name), fn_id);
+    }
+
+    ///
+    /// Function implementing the method `name` on a type
+    ///
+    pub fn method(&self, type_id: TypeId, name: &Ident) -> Option<FnId> {
+        self.methods.get(&(type_id, name.clone())).cloned()
+    }
+
+    pub(super) fn insert_trait_impl(
+        &mut self,
+        trait_id: TraitId,
+        type_id: TypeId,
+        methods: HashMap<Ident, FnId>,
+    ) {
+        self.trait_impls.insert((trait_id, type_id), methods);
+    }
+
+    ///
+    /// Functions implementing a trait's methods on a type, by method name
+    ///
+    pub fn trait_impl(&self, trait_id: TraitId, type_id: TypeId) -> Option<&HashMap<Ident, FnId>> {
+        self.trait_impls.get(&(trait_id, type_id))
+    }
+
+    pub(super) fn set_static_inits(&mut self, inits: Vec<(StaticId, FnId)>) {
+        self.static_inits = inits;
+    }
+
+    ///
+    /// Static variables with their initializer functions, in module dependency order
+    ///
+    pub fn static_inits(&self) -> &[(StaticId, FnId)] {
+        &self.static_inits
+    }
+
     pub(super) fn insert_function_param_ids(
         &mut self,
         fn_id: FnId,
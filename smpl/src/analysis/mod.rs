@@ -5,6 +5,7 @@ mod typed_ast;
 #[macro_use]
 mod control_flow;
 mod analysis_helpers;
+mod const_eval;
 mod blocky_linear_cfg_traversal;
 mod control_data;
 mod expr_flow;
@@ -26,14 +27,16 @@ pub mod metadata;
 pub(crate) mod type_cons;
 pub(crate) mod abstract_type;
 
-pub use self::semantic_data::{FnId, TypeId, ModuleId};
+pub use self::semantic_data::{FnId, TypeId, ModuleId, StaticId};
 
 pub(crate) use self::blocky_linear_cfg_traversal::{BlockyPassenger, BlockyTraverser};
 pub(crate) use self::control_data::*;
 pub(crate) use self::control_flow::CFG;
 pub(crate) use self::linear_cfg_traversal::{Passenger, Traverser};
-pub(crate) use self::semantic_ck::check_program;
+pub(crate) use self::semantic_ck::{check_program, reload_program};
 pub(crate) use self::semantic_data::*;
 pub(crate) use self::semantic_data::{Function, AnonymousFn, Module, Program};
-pub(crate) use self::type_checker::TypingContext;
+pub(crate) use self::type_checker::{
+    dictionary_param, FieldFill, IterProtocol, MethodDispatch, TraitDictionary, TypingContext,
+};
 pub(crate) use self::typed_ast::*;
@@ -1,23 +1,29 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::ast::{
-    AstNode, BuiltinFunction as AstBuiltinFunction, Ident, UseDecl,
+    AstNode, BuiltinFunction as AstBuiltinFunction, Ident, Import, ModulePath as AstModulePath,
+    UseDecl,
 };
-use crate::ast::{DeclStmt, Function as AstFunction, Opaque, Struct};
+use crate::ast::{DeclStmt, Function as AstFunction, Method, Opaque, Struct, Trait as AstTrait};
+use crate::ast::{Assignment, Block, ExprStmt, Global, Path, PathSegment, Stmt};
+use crate::ast::{TypeAlias, TypeAnnotation, TypeParams, WhereClause, WidthConstraint};
 use crate::module::{ModuleSource, ParsedModule};
 use crate::span::Span;
 
 use super::analysis_helpers;
+use super::abstract_type::{type_from_ann, AbstractType};
+use super::const_eval;
 use super::control_flow::CFG;
-use super::error::AnalysisError;
+use super::error::{AnalysisError, TypeError};
 use super::metadata::*;
 use super::resolve_scope::ScopedData;
 use super::semantic_data::Module;
 use super::semantic_data::*;
 use super::semantic_data::{ AnonymousFn as ResolvedAnonymousFn, BuiltinFunction, Function};
 use super::type_checker::TypingContext;
-use super::type_cons::TypeCons;
+use super::type_cons::{TraitCons, TypeCons};
 use super::type_cons_gen;
+use super::type_equality;
 use super::analysis_context::*;
 use super::anon_storage::AnonStorage;
 
@@ -36,12 +42,14 @@ struct DependentRawProgram {
     module_map: HashMap<ModuleId, RawModData>,
     scope_map: HashMap<ModuleId, ScopedData>,
     dependency_map: HashMap<ModuleId, HashSet<ModuleId>>,
+    import_map: HashMap<ModuleId, Vec<ResolvedImport>>,
 }
 
 struct TypableRawProgram {
     module_map: HashMap<ModuleId, RawModData>,
     scope_map: HashMap<ModuleId, ScopedData>,
     dependency_map: HashMap<ModuleId, HashSet<ModuleId>>,
+    import_map: HashMap<ModuleId, Vec<ResolvedImport>>,
     type_map: HashMap<TypeId, TypeCons>,
 }
 
@@ -70,13 +78,82 @@ pub(super) struct RawModData {
     pub(super) reserved_structs: HashMap<Ident, ReservedStruct>,
     pub(super) reserved_fns: HashMap<Ident, ReservedFn>,
     pub(super) reserved_builtins: HashMap<Ident, ReservedBuiltinFn>,
+    pub(super) reserved_traits: HashMap<Ident, ReservedTrait>,
+    pub(super) reserved_aliases: HashMap<Ident, ReservedAlias>,
+    pub(super) reserved_consts: HashMap<Ident, AstNode<Global>>,
+    pub(super) reserved_statics: Vec<ReservedStatic>,
+    pub(super) methods: Vec<(TypeId, Ident, FnId)>,
+    pub(super) trait_impls: Vec<RawTraitImpl>,
     pub(super) uses: Vec<AstNode<UseDecl>>,
+    /// Declared `#[explicit_pub] mod name;`. Only 'pub' items can be imported.
+    pub(super) explicit_pub: bool,
 }
 
 pub(super) struct ReservedOpaque(pub(super) TypeId, pub(super) AstNode<Opaque>);
 pub(super) struct ReservedStruct(pub(super) TypeId, pub(super) AstNode<Struct>);
 pub(super) struct ReservedFn(pub(super) FnId, pub(super) AstNode<AstFunction>, pub(super) TypeId,);
 pub(super) struct ReservedBuiltinFn(pub(super) FnId, pub(super) AstNode<AstBuiltinFunction>, pub(super)TypeId);
+pub(super) struct ReservedTrait(pub(super) TraitId, pub(super) AstNode<AstTrait>);
+pub(super) struct ReservedAlias(pub(super) TypeId, pub(super) AstNode<TypeAlias>);
+/// A static and the function initializing it
+pub(super) struct ReservedStatic(pub(super) StaticId, pub(super) AstNode<Global>, pub(super) FnId);
+
+/// A 'use' declaration resolved to the module it imports
+pub(super) struct ResolvedImport {
+    pub(super) module: ModuleId,
+    binding: ImportBinding,
+    /// Items of the imported module not declared 'pub'
+    private: HashSet<Ident>,
+}
+
+enum ImportBinding {
+    /// 'use a::b;' or 'use a::b as c;' binds every item as 'prefix::item'
+    Prefix(Ident),
+    /// 'use a::b::{x, y as z};' binds the listed items by name
+    Items(Vec<(Ident, Ident)>),
+}
+
+impl ResolvedImport {
+    ///
+    /// Paths an item of the imported module is accessed through. Empty if the item
+    ///   is not imported.
+    ///
+    pub(super) fn paths(&self, item: &ModulePath) -> Vec<ModulePath> {
+        match item.0.as_slice() {
+            [item] if !self.private.contains(item) => self.bind(item),
+            _ => Vec::new(),
+        }
+    }
+
+    ///
+    /// Paths the private items of the imported module would be accessed through
+    ///
+    pub(super) fn private_paths(&self) -> Vec<ModulePath> {
+        self.private.iter().flat_map(|item| self.bind(item)).collect()
+    }
+
+    fn bind(&self, item: &Ident) -> Vec<ModulePath> {
+        match self.binding {
+            ImportBinding::Prefix(ref prefix) => {
+                vec![ModulePath(vec![prefix.clone(), item.clone()])]
+            }
+
+            ImportBinding::Items(ref items) => items
+                .iter()
+                .filter(|(name, _)| name == item)
+                .map(|(_, bound)| bound.clone().into())
+                .collect(),
+        }
+    }
+}
+
+/// An 'impl Trait for Type' block before the trait is resolved
+pub(super) struct RawTraitImpl {
+    trait_name: AstNode<AstModulePath>,
+    target: AstNode<TypeAnnotation>,
+    type_id: TypeId,
+    methods: Vec<(AstNode<Ident>, FnId)>,
+}
 
 ///
 /// Perform static analysis on a collection of SMPL modules.
@@ -108,17 +185,25 @@ pub(super) struct ReservedBuiltinFn(pub(super) FnId, pub(super) AstNode<AstBuilt
 ///         3. Mark nested anonymous functions as reserved in the universe.
 ///         4. Add these nested anonymous functions to the worklist.
 ///
+/// If `previous` is the metadata of an earlier analysis of the program,
+///   functions with the same module and name keep their previous `FnId`.
+///   New IDs are generated above the previous program's ID watermark.
+///
 pub fn check_modules(
     modules: Vec<ParsedModule>,
+    previous: Option<&Metadata>,
 ) -> Result<Program, AnalysisError> {
 
-    let mut global_data = GlobalData::new();
+    let mut global_data = match previous {
+        Some(previous) => GlobalData::starting_at(previous.id_watermark()),
+        None => GlobalData::new(),
+    };
     let mut universe = AnalysisUniverse::std(&mut global_data);
     let mut features = PresentFeatures::new();
     let mut metadata = Metadata::new();
 
     // Gather raw mod data
-    let unscoped_raw_program = raw_mod_data(&mut global_data, modules)?;
+    let unscoped_raw_program = raw_mod_data(&mut global_data, modules, previous)?;
 
     // Abstractly map top-level functions and type names
     let internally_scoped_raw_program =
@@ -131,6 +216,10 @@ pub fn check_modules(
     let typable_raw_program =
         map_types(&mut universe, &mut metadata, &mut global_data, dependent_raw_program)?;
 
+    // Fold constants into scope and type statics
+    let typable_raw_program =
+        map_globals(&mut universe, &mut metadata, typable_raw_program)?;
+
     // Convert top-level functions into an analyzable form
     let analyzable_raw_program =
         generate_analyzable_fns(&mut universe,
@@ -139,6 +228,9 @@ pub fn check_modules(
             &mut global_data,
             typable_raw_program)?;
 
+    // Check trait implementations against the traits' method signatures
+    map_trait_impls(&universe, &mut metadata, &analyzable_raw_program)?;
+
     // Perform program-wide static analysis
     let universe = analyze_program(
         universe,
@@ -175,6 +267,8 @@ fn analyze_program(
                 &mut global_data,
                 analyzable_raw_program)?;
 
+        metadata.set_id_watermark(global_data.id_watermark());
+
         let module_map = {
             let mut module_map = module_map;
             for (fn_id, mod_id) in anon_ownership.into_iter() {
@@ -509,6 +603,7 @@ fn generate_analyzable_fns(
     let mut anon_fn_buff = AnonStorage::new();
     let mut fn_map = HashMap::new();
     for (mod_id, raw_mod) in raw_program.module_map.iter() {
+        let private = private_items(raw_mod);
         for (_, reserved_fn) in raw_mod.reserved_fns.iter() {
             let mut local_data = LocalData::new();
             let fn_id = reserved_fn.0;
@@ -580,6 +675,9 @@ fn generate_analyzable_fns(
             );
             metadata
                 .set_fn_annotations(fn_id, &reserved_fn.1.data().annotations);
+            if !private.contains(fn_name) {
+                metadata.insert_public_fn(fn_id);
+            }
         }
 
         for (_, reserved_builtin) in raw_mod.reserved_builtins.iter() {
@@ -621,6 +719,9 @@ fn generate_analyzable_fns(
                 fn_id,
                 &reserved_builtin.1.data().annotations,
             );
+            if !private.contains(fn_decl.name.data()) {
+                metadata.insert_public_fn(fn_id);
+            }
         }
     }
 
@@ -658,48 +759,113 @@ fn map_usings(
     .collect();
 
     let mut dependency_map: HashMap<ModuleId, HashSet<ModuleId>> = HashMap::new();
+    let mut import_map: HashMap<ModuleId, Vec<ResolvedImport>> = HashMap::new();
     for (id, raw_mod) in module_map.iter() {
 
         let mut dependencies = HashSet::new();
+        let mut imports = Vec::new();
+
+        // Names bound by earlier imports. Binding a name twice is only allowed for the same item.
+        let mut bound_prefixes: HashMap<Ident, ModuleId> = HashMap::new();
+        let mut bound_items: HashMap<Ident, (ModuleId, Ident)> = HashMap::new();
 
         for use_decl in raw_mod.uses.iter() {
-            let import_name = use_decl.data().0.data();
-            let import_id = module_name_map
-                .get(import_name)
-                .ok_or({
-                    let (ident, span) = use_decl.data().0.clone().to_data();
-                    AnalysisError::UnresolvedUses(vec![(ident, span)])
+            let use_decl = use_decl.data();
+            let module_name = Ident(use_decl.module.data().to_string());
+            let import_id = *module_name_map
+                .get(&module_name)
+                .ok_or_else(|| {
+                    AnalysisError::UnresolvedUses(vec![(module_name.clone(), use_decl.module.span())])
                 })?;
+            let imported_raw = &module_map[&import_id];
+            let private = private_items(imported_raw);
+
+            let binding = match use_decl.import {
+                Import::Module(..) => {
+                    let prefix = use_decl.prefix().expect("Module imports have a prefix");
+                    match bound_prefixes.insert(prefix.data().clone(), import_id) {
+                        Some(previous) if previous != import_id => {
+                            return Err(AnalysisError::AmbiguousImport(
+                                prefix.data().clone(),
+                                prefix.span(),
+                            ));
+                        }
+
+                        _ => (),
+                    }
+
+                    ImportBinding::Prefix(prefix.data().clone())
+                }
 
-            dependencies.insert(import_id.clone());
-            // Get imported module's types and functions
-            let (all_types, all_fns) = {
+                Import::Items(ref items) => {
+                    let mut bindings = Vec::new();
+                    for item in items.iter() {
+                        let (name, bound) = (item.name.data(), item.bound_name());
+                        if !declares(imported_raw, name) {
+                            return Err(AnalysisError::UnknownImport(
+                                name.clone(),
+                                item.name.span(),
+                            ));
+                        }
+
+                        if private.contains(name) {
+                            let mut path = use_decl.module.data().clone();
+                            path.0.push(item.name.clone());
+                            return Err(AnalysisError::PrivateItem(path, item.name.span()));
+                        }
+
+                        let imported = (import_id, name.clone());
+                        let conflict = match bound_items.insert(bound.data().clone(), imported.clone()) {
+                            Some(previous) => previous != imported,
+                            None => declares(raw_mod, bound.data()),
+                        };
+                        if conflict {
+                            return Err(AnalysisError::AmbiguousImport(
+                                bound.data().clone(),
+                                bound.span(),
+                            ));
+                        }
+
+                        bindings.push((name.clone(), bound.data().clone()));
+                    }
+
+                    ImportBinding::Items(bindings)
+                }
+            };
+
+            let import = ResolvedImport {
+                module: import_id,
+                binding,
+                private,
+            };
+
+            dependencies.insert(import_id);
+            // Get imported module's types, functions and traits
+            let (all_types, all_fns, all_traits) = {
                 let imported_scope = internally_scoped_map
-                    .get(import_id)
+                    .get(&import_id)
                     .unwrap();
 
                 let all_types = imported_scope
                     .all_types()
-                    .into_iter()
-                    .map(|(path, id)| {
-                        let mut path = path.clone();
-                        path.0.insert(0, import_name.clone());
-
-                        (path, id.clone())
+                    .flat_map(|(path, id)| {
+                        import.paths(path).into_iter().map(move |path| (path, id))
                     })
                     .collect::<HashMap<_, _>>();
                 let all_fns = imported_scope
                     .all_fns()
-                    .into_iter()
-                    .map(|(path, id)| {
-                        let mut path = path.clone();
-                        path.0.insert(0, import_name.clone());
-
-                        (path, id.clone())
+                    .flat_map(|(path, id)| {
+                        import.paths(path).into_iter().map(move |path| (path, id))
+                    })
+                    .collect::<HashMap<_, _>>();
+                let all_traits = imported_scope
+                    .all_traits()
+                    .flat_map(|(path, id)| {
+                        import.paths(path).into_iter().map(move |path| (path, id))
                     })
                     .collect::<HashMap<_, _>>();
 
-                (all_types, all_fns)
+                (all_types, all_fns, all_traits)
             };
 
             let current_module_scope = fully_scoped_map
@@ -708,11 +874,12 @@ fn map_usings(
 
             // Bring imported types into scope
             for (path, imported) in all_types.into_iter() {
-                if current_module_scope
-                    .insert_type_cons(path.clone().into(), imported)
-                    .is_some()
-                {
-                    panic!("Should not have overrwritten {}. Paths should be unique by prefixing with the originating module.", path);
+                match current_module_scope.insert_type_cons(path.clone(), imported) {
+                    Some(previous) if previous != imported => {
+                        panic!("Should not have overrwritten {}. Imported names should be unique.", path);
+                    }
+
+                    _ => (),
                 }
             }
 
@@ -720,18 +887,85 @@ fn map_usings(
             for (path, imported) in all_fns.into_iter() {
                 current_module_scope.insert_fn(path, imported);
             }
+
+            // Bring imported traits into scope
+            for (path, imported) in all_traits.into_iter() {
+                current_module_scope.insert_trait(path, imported);
+            }
+
+            // Remember private items to report accesses to them
+            for path in import.private_paths().into_iter() {
+                current_module_scope.insert_private(path);
+            }
+
+            imports.push(import);
         }
 
         dependency_map.insert(id.clone(), dependencies);
+        import_map.insert(id.clone(), imports);
     }
 
     Ok(DependentRawProgram {
         module_map: module_map,
         scope_map: fully_scoped_map,
         dependency_map: dependency_map,
+        import_map,
     })
 }
 
+///
+/// Whether a module declares a struct, opaque type, function, trait, type alias or constant
+///   named `name`
+///
+fn declares(raw: &RawModData, name: &Ident) -> bool {
+    raw.reserved_structs.contains_key(name)
+        || raw.reserved_opaque.contains_key(name)
+        || raw.reserved_fns.contains_key(name)
+        || raw.reserved_builtins.contains_key(name)
+        || raw.reserved_traits.contains_key(name)
+        || raw.reserved_aliases.contains_key(name)
+        || raw.reserved_consts.contains_key(name)
+}
+
+///
+/// Items a module declares without 'pub'
+///
+/// A module opts into privacy with `#[explicit_pub]` on its `mod` declaration. Other modules
+///   keep every item public, including those next to 'pub' items.
+///
+fn private_items(raw: &RawModData) -> HashSet<Ident> {
+    if !raw.explicit_pub {
+        return HashSet::new();
+    }
+
+    let structs = raw.reserved_structs.iter().map(|(name, s)| (name, s.1.data().public));
+    let opaque = raw.reserved_opaque.iter().map(|(name, o)| (name, o.1.data().public));
+    // Methods are reached through their type, not the module
+    let methods: HashSet<FnId> = raw.methods.iter().map(|(_, _, fn_id)| *fn_id).collect();
+    let fns = raw.reserved_fns
+        .iter()
+        .filter(|(_, f)| !methods.contains(&f.0))
+        .map(|(name, f)| (name, f.1.data().public));
+    let builtins = raw.reserved_builtins
+        .iter()
+        .filter(|(_, f)| !methods.contains(&f.0))
+        .map(|(name, f)| (name, f.1.data().public));
+    let traits = raw.reserved_traits.iter().map(|(name, t)| (name, t.1.data().public));
+    let aliases = raw.reserved_aliases.iter().map(|(name, a)| (name, a.1.data().public));
+    let consts = raw.reserved_consts.iter().map(|(name, c)| (name, c.data().public));
+
+    structs
+        .chain(opaque)
+        .chain(fns)
+        .chain(builtins)
+        .chain(traits)
+        .chain(aliases)
+        .chain(consts)
+        .filter(|(_, public)| !public)
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
 /// Splits ParsedModule's into a UnscopedRawProgram
 /// Per module:
 ///   1) Checks for top-level name conflicts
@@ -741,19 +975,42 @@ fn map_usings(
 fn raw_mod_data(
     global_data: &mut GlobalData,
     modules: Vec<ParsedModule>,
+    previous: Option<&Metadata>,
 ) -> Result<UnscopedRawProgram, AnalysisError> {
 
     use super::error::TopLevelError;
 
     let mut mod_map = HashMap::new();
     for module in modules {
+        // Reuse the FnId of a function with the same name in the previous program
+        let previous_mod_id = previous.and_then(|previous| {
+            module.module.name()
+                .and_then(|name| previous.mod_metadata().get_module(name.clone()))
+        });
+        let fn_id = |name: &Ident| {
+            previous_mod_id
+                .and_then(|mod_id| {
+                    previous.and_then(|previous| previous.module_fn(mod_id, name.clone()))
+                })
+                .unwrap_or_else(|| global_data.new_fn_id())
+        };
+
         let mut opaque_reserve: HashMap<Ident, ReservedOpaque> = HashMap::new();
         let mut struct_reserve: HashMap<Ident, ReservedStruct> = HashMap::new();
         let mut fn_reserve: HashMap<Ident, ReservedFn>= HashMap::new();
         let mut builtin_fn_reserve: HashMap<Ident, ReservedBuiltinFn> = HashMap::new();
+        let mut trait_reserve: HashMap<Ident, ReservedTrait> = HashMap::new();
+        let mut alias_reserve: HashMap<Ident, ReservedAlias> = HashMap::new();
         let mut uses = Vec::new();
+        let mut impls = Vec::new();
+        let mut consts = Vec::new();
+        let mut statics = Vec::new();
 
         let ast_module = module.module;
+        let explicit_pub = ast_module.2
+            .iter()
+            .flat_map(|anno| anno.keys.iter())
+            .any(|(key, _)| key.as_str() == "explicit_pub");
         for decl_stmt in ast_module.1.into_iter() {
             match decl_stmt {
                 DeclStmt::Struct(d) => {
@@ -769,6 +1026,8 @@ fn raw_mod_data(
                         )
                         .is_some()
                         || opaque_reserve.contains_key(&name)
+                        || trait_reserve.contains_key(&name)
+                        || alias_reserve.contains_key(&name)
                     {
                         return Err(TopLevelError::DuplicateTypes(name, span).into());
                     }
@@ -780,7 +1039,7 @@ fn raw_mod_data(
                     if fn_reserve
                         .insert(
                             name.clone(),
-                            ReservedFn(global_data.new_fn_id(), d, global_data.new_type_id()),
+                            ReservedFn(fn_id(&name), d, global_data.new_type_id()),
                         )
                         .is_some()
                         || builtin_fn_reserve.contains_key(&name)
@@ -796,7 +1055,7 @@ fn raw_mod_data(
                         .insert(
                             name.clone(),
                             ReservedBuiltinFn(
-                                global_data.new_fn_id(),
+                                fn_id(&name),
                                 d,
                                 global_data.new_type_id(),
                             ),
@@ -812,6 +1071,18 @@ fn raw_mod_data(
                     uses.push(u);
                 }
 
+                DeclStmt::Impl(i) => {
+                    impls.push(i);
+                }
+
+                DeclStmt::Const(c) => {
+                    consts.push(c);
+                }
+
+                DeclStmt::Static(s) => {
+                    statics.push(s);
+                }
+
                 DeclStmt::Opaque(o) => {
                     let span = o.data().name.span();
                     let name = o.data().name.data().clone();
@@ -825,13 +1096,161 @@ fn raw_mod_data(
                         )
                         .is_some()
                         || struct_reserve.contains_key(&name)
+                        || trait_reserve.contains_key(&name)
+                        || alias_reserve.contains_key(&name)
                     {
                         return Err(TopLevelError::DuplicateTypes(name, span).into());
                     }
                 }
+
+                DeclStmt::Trait(t) => {
+                    let span = t.data().name.span();
+                    let name = t.data().name.data().clone();
+                    if trait_reserve
+                        .insert(name.clone(), ReservedTrait(global_data.new_trait_id(), t))
+                        .is_some()
+                        || struct_reserve.contains_key(&name)
+                        || opaque_reserve.contains_key(&name)
+                        || alias_reserve.contains_key(&name)
+                    {
+                        return Err(TopLevelError::DuplicateTypes(name, span).into());
+                    }
+                }
+
+                DeclStmt::TypeAlias(a) => {
+                    let span = a.data().name.span();
+                    let name = a.data().name.data().clone();
+                    if alias_reserve
+                        .insert(name.clone(), ReservedAlias(global_data.new_type_id(), a))
+                        .is_some()
+                        || struct_reserve.contains_key(&name)
+                        || opaque_reserve.contains_key(&name)
+                        || trait_reserve.contains_key(&name)
+                    {
+                        return Err(TopLevelError::DuplicateTypes(name, span).into());
+                    }
+                }
+            }
+        }
+
+        // Methods are reserved as functions named after their type (i.e. 'Point::len')
+        let mut methods = Vec::new();
+        let mut trait_impls = Vec::new();
+        for impl_decl in impls {
+            let impl_decl = impl_decl.to_data().0;
+            let (type_name, type_id) =
+                impl_type(&impl_decl.target, &struct_reserve, &opaque_reserve)
+                    .ok_or(TopLevelError::InvalidImplType(impl_decl.target.span()))?;
+
+            let mut impl_methods = Vec::new();
+            for method in impl_decl.methods {
+                match method {
+                    Method::Function(f) => {
+                        let (mut f, fn_span) = f.to_data();
+                        let (method_name, name_span) = f.name.to_data();
+                        let name = Ident(format!("{}::{}", type_name, method_name));
+
+                        f.name = AstNode::new(name.clone(), name_span.clone());
+                        f.type_params =
+                            merge_type_params(&impl_decl.type_params, f.type_params);
+                        f.where_clause =
+                            merge_where_clauses(&impl_decl.where_clause, f.where_clause);
+
+                        let fn_id = fn_id(&name);
+                        if fn_reserve
+                            .insert(
+                                name.clone(),
+                                ReservedFn(
+                                    fn_id,
+                                    AstNode::new(f, fn_span),
+                                    global_data.new_type_id(),
+                                ),
+                            )
+                            .is_some()
+                            || builtin_fn_reserve.contains_key(&name)
+                        {
+                            return Err(TopLevelError::DuplicateFns(name, name_span).into());
+                        }
+
+                        impl_methods.push((AstNode::new(method_name, name_span), fn_id));
+                    }
+
+                    Method::Builtin(f) => {
+                        let (mut f, fn_span) = f.to_data();
+                        let (method_name, name_span) = f.name.to_data();
+                        let name = Ident(format!("{}::{}", type_name, method_name));
+
+                        f.name = AstNode::new(name.clone(), name_span.clone());
+                        f.type_params =
+                            merge_type_params(&impl_decl.type_params, f.type_params);
+                        f.where_clause =
+                            merge_where_clauses(&impl_decl.where_clause, f.where_clause);
+
+                        let fn_id = fn_id(&name);
+                        if builtin_fn_reserve
+                            .insert(
+                                name.clone(),
+                                ReservedBuiltinFn(
+                                    fn_id,
+                                    AstNode::new(f, fn_span),
+                                    global_data.new_type_id(),
+                                ),
+                            )
+                            .is_some()
+                            || fn_reserve.contains_key(&name)
+                        {
+                            return Err(TopLevelError::DuplicateFns(name, name_span).into());
+                        }
+
+                        impl_methods.push((AstNode::new(method_name, name_span), fn_id));
+                    }
+                }
+            }
+
+            methods.extend(
+                impl_methods
+                    .iter()
+                    .map(|(name, fn_id)| (type_id, name.data().clone(), *fn_id)),
+            );
+
+            if let Some(trait_name) = impl_decl.trait_name {
+                trait_impls.push(RawTraitImpl {
+                    trait_name,
+                    target: impl_decl.target,
+                    type_id,
+                    methods: impl_methods,
+                });
             }
         }
 
+        // Constants and statics share the namespace of functions
+        let mut global_names = HashSet::new();
+        for global in consts.iter().chain(statics.iter()) {
+            let (name, span) = global.data().name.clone().to_data();
+            if !global_names.insert(name.clone())
+                || fn_reserve.contains_key(&name)
+                || builtin_fn_reserve.contains_key(&name)
+            {
+                return Err(TopLevelError::DuplicateGlobals(name, span).into());
+            }
+        }
+
+        // Statics are initialized by functions named after them (i.e. 'static COUNTER')
+        let mut static_reserve = Vec::new();
+        for global in statics {
+            let name = Ident(format!("static {}", global.data().name.data()));
+            let init_fn = static_init_fn(name.clone(), &global);
+            let fn_id = fn_id(&name);
+
+            fn_reserve.insert(name, ReservedFn(fn_id, init_fn, global_data.new_type_id()));
+            static_reserve.push(ReservedStatic(global_data.new_static_id(), global, fn_id));
+        }
+
+        let const_reserve = consts
+            .into_iter()
+            .map(|global| (global.data().name.data().clone(), global))
+            .collect();
+
         let raw = RawModData {
             source: module.source.clone(),
             name: ast_module.0.ok_or(AnalysisError::MissingModName)?,
@@ -840,7 +1259,14 @@ fn raw_mod_data(
             reserved_structs: struct_reserve,
             reserved_fns: fn_reserve,
             reserved_builtins: builtin_fn_reserve,
+            reserved_traits: trait_reserve,
+            reserved_aliases: alias_reserve,
+            reserved_consts: const_reserve,
+            reserved_statics: static_reserve,
+            methods,
+            trait_impls,
             uses: uses,
+            explicit_pub,
         };
 
         // TODO Map module name to id
@@ -859,6 +1285,212 @@ fn raw_mod_data(
     })
 }
 
+///
+/// Checks each 'impl Trait for Type' block against its trait and records the implementing
+///   functions in the metadata.
+///
+fn map_trait_impls(
+    universe: &AnalysisUniverse,
+    metadata: &mut Metadata,
+    raw_program: &AnalyzableRawProgram,
+) -> Result<(), AnalysisError> {
+    use super::error::TopLevelError;
+
+    for (mod_id, raw_mod) in raw_program.module_map.iter() {
+        let scope = raw_program.scope_map.get(mod_id).unwrap();
+
+        for trait_impl in raw_mod.trait_impls.iter() {
+            let trait_path = trait_impl.trait_name.data();
+            let trait_id = scope
+                .trait_id(&trait_path.clone().into())
+                .ok_or_else(|| {
+                    let span = trait_impl.trait_name.span();
+                    scope.private_or(
+                        trait_path,
+                        span.clone(),
+                        AnalysisError::UnknownTrait(trait_path.clone(), span),
+                    )
+                })?;
+            let trait_cons = universe.get_trait_cons(trait_id);
+
+            let mut methods = HashMap::new();
+            for (name, fn_id) in trait_impl.methods.iter() {
+                let trait_method = trait_cons.methods.get(name.data()).ok_or_else(|| {
+                    TopLevelError::UnknownTraitMethod(name.data().clone(), name.span())
+                })?;
+
+                let method = universe
+                    .get_fn_type_cons(*fn_id)
+                    .expect("Missing method type constructor");
+
+                if !compatible_trait_method(
+                    universe,
+                    scope,
+                    trait_method,
+                    method,
+                    &trait_impl.target,
+                )? {
+                    return Err(
+                        TopLevelError::IncompatibleTraitMethod(name.data().clone(), name.span())
+                            .into(),
+                    );
+                }
+
+                methods.insert(name.data().clone(), *fn_id);
+            }
+
+            let missing = trait_cons
+                .methods
+                .keys()
+                .filter(|name| !methods.contains_key(*name))
+                .min_by_key(|name| name.as_str());
+            if let Some(missing) = missing {
+                return Err(TopLevelError::MissingTraitMethod(
+                    missing.clone(),
+                    trait_impl.target.span(),
+                )
+                .into());
+            }
+
+            metadata.insert_trait_impl(trait_id, trait_impl.type_id, methods);
+        }
+    }
+
+    Ok(())
+}
+
+///
+/// A method implements a trait method if it has the trait method's signature with `Self` as
+///   the impl's target type.
+///
+/// Implementations cannot have trait bounds of their own; they are called through a
+///   dictionary which has no way to pass them.
+///
+fn compatible_trait_method(
+    universe: &AnalysisUniverse,
+    scope: &ScopedData,
+    trait_method: &TypeCons,
+    method: &TypeCons,
+    target: &AstNode<TypeAnnotation>,
+) -> Result<bool, AnalysisError> {
+    use super::abstract_type::{type_from_ann, AbstractType};
+
+    let type_params = match method {
+        TypeCons::Function {
+            ref type_params, ..
+        } => type_params,
+
+        _ => return Ok(false),
+    };
+
+    if !type_params.bounds().is_empty() {
+        return Ok(false);
+    }
+
+    // Type the target with the method's (i.e. the impl's) type parameters in scope
+    let mut scope = scope.clone();
+    let mut typing_context = TypingContext::empty();
+    let type_args = type_params
+        .iter()
+        .map(|(param_id, constraint)| {
+            let type_var = type_params.placeholder_type_var(param_id);
+            scope.insert_type_var(type_params.name(param_id).clone(), type_var);
+            typing_context.type_vars.insert(type_var, constraint.clone());
+
+            AbstractType::TypeVar(target.span(), type_var)
+        })
+        .collect::<Vec<_>>();
+
+    let self_type = type_from_ann(&scope, &typing_context, target)?;
+
+    let expected = AbstractType::App2 {
+        data: target.span(),
+        type_cons: Box::new(trait_method.clone()),
+        args: vec![self_type],
+    }
+    .substitute(universe, &scope, &typing_context)?;
+
+    let found = AbstractType::App2 {
+        data: target.span(),
+        type_cons: Box::new(method.clone()),
+        args: type_args,
+    }
+    .substitute(universe, &scope, &typing_context)?;
+
+    Ok(super::type_equality::equal_types_static(
+        universe,
+        &scope,
+        &typing_context,
+        &found,
+        &expected,
+        target.span(),
+    )
+    .is_ok())
+}
+
+///
+/// Finds the struct or opaque type targeted by an impl block.
+///
+/// Only types declared in the same module may be targeted.
+///
+fn impl_type(
+    target: &AstNode<TypeAnnotation>,
+    structs: &HashMap<Ident, ReservedStruct>,
+    opaques: &HashMap<Ident, ReservedOpaque>,
+) -> Option<(Ident, TypeId)> {
+    let path = match target.data() {
+        TypeAnnotation::Path(path) => path.module_path(),
+        _ => return None,
+    };
+
+    if path.0.len() != 1 {
+        return None;
+    }
+
+    let name = path.0[0].data();
+    structs
+        .get(name)
+        .map(|r| r.0)
+        .or_else(|| opaques.get(name).map(|r| r.0))
+        .map(|type_id| (name.clone(), type_id))
+}
+
+/// Impl type parameters come before the method's own type parameters
+fn merge_type_params(
+    impl_params: &Option<TypeParams>,
+    method_params: Option<TypeParams>,
+) -> Option<TypeParams> {
+    match impl_params {
+        None => method_params,
+
+        Some(impl_params) => {
+            let mut params = impl_params.params.clone();
+            params.extend(method_params.into_iter().flat_map(|p| p.params));
+            Some(TypeParams { params })
+        }
+    }
+}
+
+fn merge_where_clauses(
+    impl_clause: &Option<WhereClause>,
+    method_clause: Option<WhereClause>,
+) -> Option<WhereClause> {
+    match (impl_clause, method_clause) {
+        (None, method_clause) => method_clause,
+
+        (Some(impl_clause), None) => Some(impl_clause.clone()),
+
+        (Some(impl_clause), Some(method_clause)) => {
+            let mut merged = impl_clause.0.clone();
+            for (param, constraints) in method_clause.0 {
+                merged.entry(param).or_insert_with(Vec::new).extend(constraints);
+            }
+
+            Some(WhereClause(merged))
+        }
+    }
+}
+
 ///
 /// Creates a module-level scope per each module based off of the standard scope
 ///   of the Universe and the module's top-level declarations.
@@ -910,6 +1542,19 @@ fn map_internal_data(scope: &mut ScopedData, raw: &RawModData) {
     for (_ident, r) in raw.reserved_builtins.iter() {
         scope.insert_fn(r.1.data().name.data().clone().into(), r.0.clone());
     }
+
+    for (ident, r) in raw.reserved_traits.iter() {
+        scope.insert_trait(ident.clone().into(), r.0);
+    }
+
+    for (ident, r) in raw.reserved_aliases.iter() {
+        scope.insert_type_cons(ident.clone().into(), r.0);
+    }
+
+    // Statics are private to their module
+    for r in raw.reserved_statics.iter() {
+        scope.insert_static(r.1.data().name.data().clone(), r.0);
+    }
 }
 
 /// Insert type constructors into the Universe and a separate type map
@@ -921,6 +1566,8 @@ fn map_types(
 
     -> Result<TypableRawProgram, AnalysisError> {
 
+    check_alias_cycles(&raw_program)?;
+
     let mut type_map = HashMap::new();
     // Map ALL structs into the universe before generating functions
     for (mod_id, raw_mod) in raw_program.module_map.iter() {
@@ -964,6 +1611,54 @@ fn map_types(
             assert!(type_map.insert(type_id, opaque_type_cons.clone()).is_none());
             universe
                 .manual_insert_type_cons(type_id, opaque_type_cons);
+
+            metadata.insert_opaque_module(type_id, *mod_id);
+        }
+
+        for (_, reserved_alias) in raw_mod.reserved_aliases.iter() {
+            let type_id = reserved_alias.0;
+            let alias_type_cons = type_cons_gen::generate_alias_type_cons(
+                universe,
+                global_data,
+                raw_program.scope_map.get(mod_id).unwrap(),
+                &TypingContext::empty(),
+                reserved_alias.1.data(),
+            )?;
+
+            assert!(type_map.insert(type_id, alias_type_cons.clone()).is_none());
+            universe.manual_insert_type_cons(type_id, alias_type_cons);
+        }
+
+        for (type_id, name, fn_id) in raw_mod.methods.iter() {
+            metadata.insert_method(*type_id, name.clone(), *fn_id);
+        }
+
+        for (name, reserved_trait) in raw_mod.reserved_traits.iter() {
+            let methods = reserved_trait
+                .1
+                .data()
+                .methods
+                .iter()
+                .map(|method| {
+                    let type_cons = type_cons_gen::generate_trait_method_type_cons(
+                        universe,
+                        global_data,
+                        raw_program.scope_map.get(mod_id).unwrap(),
+                        &TypingContext::empty(),
+                        method.data(),
+                    )?;
+
+                    Ok((method.data().name.data().clone(), type_cons))
+                })
+                .collect::<Result<HashMap<_, _>, AnalysisError>>()?;
+
+            universe.insert_trait_cons(
+                reserved_trait.0,
+                TraitCons {
+                    name: name.clone(),
+                    methods,
+                },
+            );
         }
     }
 
@@ -972,7 +1667,310 @@ fn map_types(
         module_map: raw_program.module_map,
         scope_map: raw_program.scope_map,
         dependency_map: raw_program.dependency_map,
+        import_map: raw_program.import_map,
         type_map: type_map,
     })
 
 }
+
+///
+/// Aliases are expanded wherever they are used, so an alias may not reach itself
+///   through its aliased type or the aliases it uses.
+///
+fn check_alias_cycles(raw_program: &DependentRawProgram) -> Result<(), AnalysisError> {
+    use super::error::TopLevelError;
+
+    let aliases: HashMap<TypeId, (ModuleId, &AstNode<TypeAlias>)> = raw_program
+        .module_map
+        .iter()
+        .flat_map(|(mod_id, raw_mod)| {
+            raw_mod
+                .reserved_aliases
+                .values()
+                .map(move |reserved| (reserved.0, (*mod_id, &reserved.1)))
+        })
+        .collect();
+
+    for (alias_id, (_, alias)) in aliases.iter() {
+        let mut visited = HashSet::new();
+        let mut stack = vec![*alias_id];
+        while let Some(current) = stack.pop() {
+            let (mod_id, current_alias) = aliases[&current];
+            let scope = raw_program.scope_map.get(&mod_id).unwrap();
+
+            let mut referenced = Vec::new();
+            annotation_types(scope, &current_alias.data().aliased, &mut referenced);
+            for type_id in referenced {
+                if type_id == *alias_id {
+                    let name = alias.data().name.clone();
+                    return Err(TopLevelError::CyclicAlias(name.data().clone(), name.span()).into());
+                }
+
+                if aliases.contains_key(&type_id) && visited.insert(type_id) {
+                    stack.push(type_id);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+///
+/// Collects the types named in an annotation
+///
+fn annotation_types(scope: &ScopedData, ann: &AstNode<TypeAnnotation>, types: &mut Vec<TypeId>) {
+    match ann.data() {
+        TypeAnnotation::Path(path) => {
+            if let Some(type_id) = scope.type_cons(&path.module_path().clone().into()) {
+                types.push(type_id);
+            }
+
+            for arg in path.annotations().unwrap_or(&[]) {
+                annotation_types(scope, arg, types);
+            }
+        }
+
+        TypeAnnotation::Array(element, _) => annotation_types(scope, element, types),
+        TypeAnnotation::Ref(inner) => annotation_types(scope, inner, types),
+        TypeAnnotation::Tuple(elements) => {
+            for element in elements {
+                annotation_types(scope, element, types);
+            }
+        }
+
+        TypeAnnotation::FnType(_, params, return_type) => {
+            for param in params.iter().flatten() {
+                annotation_types(scope, param, types);
+            }
+
+            if let Some(return_type) = return_type {
+                annotation_types(scope, return_type, types);
+            }
+        }
+
+        TypeAnnotation::WidthConstraint(constraints) => {
+            for constraint in constraints {
+                match constraint.data() {
+                    WidthConstraint::BaseStruct(base) => annotation_types(scope, base, types),
+                    WidthConstraint::Anonymous(fields) => {
+                        for (_, field_type) in fields {
+                            annotation_types(scope, field_type, types);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+///
+/// Folds module constants and brings them into scope, folds struct field defaults, types
+///   statics and orders their initializers.
+///
+/// Constants are visible in their own module as 'NAME' and in modules using it as
+///   'module::NAME'. Statics are initialized module by module, dependencies first.
+///
+fn map_globals(
+    universe: &mut AnalysisUniverse,
+    metadata: &mut Metadata,
+    raw_program: TypableRawProgram,
+) -> Result<TypableRawProgram, AnalysisError> {
+    let TypableRawProgram {
+        module_map,
+        mut scope_map,
+        dependency_map,
+        import_map,
+        type_map,
+    } = raw_program;
+
+    let consts = const_eval::evaluate_consts(&module_map, &import_map)?;
+
+    for ((mod_id, name), value) in consts.iter() {
+        let global = &module_map[mod_id].reserved_consts[name];
+        let scope = &scope_map[mod_id];
+        let typing_context = TypingContext::empty();
+
+        let const_type = type_from_ann(scope, &typing_context, &global.data().global_type)?
+            .substitute(universe, scope, &typing_context)?;
+        let value_type = const_eval::literal_type(value, global.data().value.span());
+
+        type_equality::equal_types_static(
+            universe,
+            scope,
+            &typing_context,
+            &value_type,
+            &const_type,
+            global.span(),
+        )
+        .map_err(|_| TypeError::IncompatibleLocal {
+            name: name.clone(),
+            local_type: const_type.clone(),
+            found_type: value_type.clone(),
+            span: global.span(),
+        })?;
+    }
+
+    for ((mod_id, name), value) in consts.into_iter() {
+        let const_path = ModulePath::from(name.clone());
+        for (dependent, imports) in import_map.iter() {
+            for import in imports.iter().filter(|import| import.module == mod_id) {
+                for path in import.paths(&const_path) {
+                    scope_map
+                        .get_mut(dependent)
+                        .unwrap()
+                        .insert_const(path, value.clone());
+                }
+            }
+        }
+
+        scope_map
+            .get_mut(&mod_id)
+            .unwrap()
+            .insert_const(name.into(), value);
+    }
+
+    for (mod_id, raw_mod) in module_map.iter() {
+        let scope = &scope_map[mod_id];
+        for ReservedStruct(type_id, struct_def) in raw_mod.reserved_structs.values() {
+            let (fields, field_map) = match *universe.get_type_cons(*type_id) {
+                TypeCons::Record {
+                    ref fields,
+                    ref field_map,
+                    ..
+                } => (fields.clone(), field_map.clone()),
+
+                _ => unreachable!(),
+            };
+
+            let struct_fields = struct_def.data().body.0.iter().flatten();
+            for field in struct_fields {
+                let default = match field.default {
+                    Some(ref default) => default,
+                    None => continue,
+                };
+
+                let value = const_eval::evaluate_scoped(scope, default)?;
+                let value_type = const_eval::literal_type(&value, default.span());
+                let field_id = field_map[field.name.data()];
+                let field_type = fields[&field_id].substitute(universe, scope, &TypingContext::empty())?;
+
+                // Defaults are literals. Fields typed by a type parameter cannot have one.
+                let compatible = match *field_type.expanded() {
+                    AbstractType::Int(_)
+                    | AbstractType::Float(_)
+                    | AbstractType::Bool(_)
+                    | AbstractType::String(_) => type_equality::equal_types_static(
+                        universe,
+                        scope,
+                        &TypingContext::empty(),
+                        &value_type,
+                        &field_type,
+                        default.span(),
+                    )
+                    .is_ok(),
+
+                    _ => false,
+                };
+
+                if !compatible {
+                    return Err(TypeError::IncompatibleFieldDefault {
+                        name: field.name.data().clone(),
+                        field_type: field_type,
+                        found_type: value_type,
+                        span: default.span(),
+                    }
+                    .into());
+                }
+
+                universe.insert_field_default(field_id, value);
+            }
+        }
+    }
+
+    let mut static_inits = Vec::new();
+    for mod_id in module_init_order(&dependency_map) {
+        let scope = &scope_map[&mod_id];
+        for ReservedStatic(static_id, global, init_fn) in module_map[&mod_id].reserved_statics.iter() {
+            let static_type = type_from_ann(scope, &TypingContext::empty(), &global.data().global_type)?
+                .substitute(universe, scope, &TypingContext::empty())?;
+
+            universe.insert_static_type(*static_id, static_type);
+            static_inits.push((*static_id, *init_fn));
+        }
+    }
+    metadata.set_static_inits(static_inits);
+
+    Ok(TypableRawProgram {
+        module_map,
+        scope_map,
+        dependency_map,
+        import_map,
+        type_map,
+    })
+}
+
+///
+/// Orders modules so that every module comes after the modules it uses.
+///
+fn module_init_order(dependency_map: &HashMap<ModuleId, HashSet<ModuleId>>) -> Vec<ModuleId> {
+    fn visit(
+        mod_id: ModuleId,
+        dependency_map: &HashMap<ModuleId, HashSet<ModuleId>>,
+        visited: &mut HashSet<ModuleId>,
+        order: &mut Vec<ModuleId>,
+    ) {
+        if !visited.insert(mod_id) {
+            return;
+        }
+
+        let mut dependencies = dependency_map[&mod_id].iter().cloned().collect::<Vec<_>>();
+        dependencies.sort();
+        for dependency in dependencies {
+            visit(dependency, dependency_map, visited, order);
+        }
+
+        order.push(mod_id);
+    }
+
+    let mut modules = dependency_map.keys().cloned().collect::<Vec<_>>();
+    modules.sort();
+
+    let mut visited = HashSet::new();
+    let mut order = Vec::new();
+    for mod_id in modules {
+        visit(mod_id, dependency_map, &mut visited, &mut order);
+    }
+
+    order
+}
+
+///
+/// Builds the function assigning a static its initial value.
+///
+fn static_init_fn(name: Ident, global: &AstNode<Global>) -> AstNode<AstFunction> {
+    let span = global.span();
+    let assignment = Assignment {
+        name: AstNode::new(Path(vec![PathSegment::Ident(global.data().name.clone())]), span.clone()),
+        value: global.data().value.data().clone(),
+        deref: false,
+    };
+
+    let body = Block(vec![Stmt::ExprStmt(AstNode::new(
+        ExprStmt::Assignment(assignment),
+        span.clone(),
+    ))]);
+
+    let function = AstFunction {
+        name: AstNode::new(name, global.data().name.span()),
+        params: None,
+        return_type: None,
+        body: AstNode::new(body, span.clone()),
+        annotations: Vec::new(),
+        type_params: None,
+        where_clause: None,
+        public: false,
+    };
+
+    AstNode::new(function, span)
+}
@@ -1,15 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use petgraph::graph::NodeIndex;
 
-use crate::ast::{AstNode, Ident, ModulePath as AstModulePath};
+use crate::ast::{AstNode, Ident, Literal, ModulePath as AstModulePath};
+use crate::span::Span;
 
 use super::control_data::*;
 use super::unique_linear_cfg_traversal::*;
 
 use super::error::AnalysisError;
 use super::semantic_data::{
-    FnId, ModulePath, TypeId, TypeVarId, VarId, AnonymousFn as ResolvedAnonymousFn,
+    FnId, ModulePath, StaticId, TraitId, TypeId, TypeVarId, VarId,
+    AnonymousFn as ResolvedAnonymousFn,
 };
 use super::analysis_context::{
     AnalyzableFn, AnalyzableAnonymousFn as AnonymousFn,
@@ -109,6 +111,9 @@ impl ScopeResolver {
 
         for tmp_id in expr.execution_order() {
             let tmp = expr.get_tmp_mut(tmp_id);
+
+            // Value of a constant replacing its binding
+            let mut folded = None;
             match tmp.value_mut().data_mut() {
                 Value::Literal(ref mut _literal) => (),
 
@@ -127,6 +132,14 @@ impl ScopeResolver {
                         BindingInfo::Fn(fn_id) => {
                             var.set_id(fn_id);
                         }
+
+                        BindingInfo::Static(static_id) => {
+                            var.set_id(static_id);
+                        }
+
+                        BindingInfo::Const(literal) => {
+                            folded = Some(literal);
+                        }
                     }
                 }
 
@@ -144,8 +157,8 @@ impl ScopeResolver {
                     }
 
                     let current_scope = self.current();
-                    let var_id = current_scope.var_id(path.root_name())?;
-                    path.set_root_var(var_id);
+                    let root = current_scope.path_root(path.root_name())?;
+                    path.set_root(root);
                 }
 
                 Value::BinExpr(..) => (),
@@ -154,14 +167,34 @@ impl ScopeResolver {
 
                 Value::FnCall(..) => (),
 
+                Value::PartialApp(..) => (),
+
                 Value::ArrayInit(..) => (),
 
+                Value::TupleInit(..) => (),
+
+                Value::MethodCall(..) => (),
+
                 Value::Indexing(..) => (),
 
+                Value::Slice(..) => (),
+
+                Value::Iter(..) => (),
+
+                Value::Format(..) => (),
+
+                Value::Stringify(..) => (),
+
                 Value::ModAccess(ref mut access) => {
                     let current_scope = self.current();
-                    let fn_id = current_scope.get_fn(&access.path())?;
-                    access.set_fn_id(fn_id);
+                    match current_scope.get_const(access.path()) {
+                        Some(literal) => folded = Some(literal.clone()),
+
+                        None => {
+                            let fn_id = current_scope.get_fn(&access.path())?;
+                            access.set_fn_id(fn_id);
+                        }
+                    }
                 }
 
                 // TODO: Generate anonymous functions in a separate phase
@@ -181,6 +214,10 @@ impl ScopeResolver {
                     type_inst.set_id(fn_id);
                 }
             }
+
+            if let Some(literal) = folded {
+                *tmp.value_mut().data_mut() = Value::Literal(literal);
+            }
         }
 
         Ok(())
@@ -241,7 +278,9 @@ impl UniquePassenger<E> for ScopeResolver {
     ) -> Result<(), E> {
         let var_decl = &mut decl.decl;
 
-        self.resolve_expr_scope(var_decl.init_expr_mut())?;
+        if let Some(init) = var_decl.init_expr_mut() {
+            self.resolve_expr_scope(init)?;
+        }
 
         let name = var_decl.var_name().clone();
         let var_id = var_decl.var_id();
@@ -259,8 +298,22 @@ impl UniquePassenger<E> for ScopeResolver {
 
         let path = assignment.assignee_mut().path_mut();
 
-        let var_id = self.current().var_id(path.root_name())?;
-        path.set_root_var(var_id);
+        let root = match self.current().path_root(path.root_name()) {
+            Ok(root) => root,
+
+            // Constants are folded into their uses and have no storage to assign to
+            Err(err) => match self.current().get_const(&AstModulePath(vec![path.root_name().clone()])) {
+                Some(_) => {
+                    return Err(AnalysisError::ConstAssignment(
+                        path.root_name().data().clone(),
+                        path.root_name().span(),
+                    ));
+                }
+
+                None => return Err(err),
+            },
+        };
+        path.set_root(root);
 
         // Resolve expression scopes for root indexing expressions
         if let Some(root_index) = path.root_indexing_expr_mut() {
@@ -347,6 +400,11 @@ pub struct ScopedData {
     var_map: HashMap<Ident, VarId>,
     fn_map: HashMap<ModulePath, FnId>,
     type_param_map: HashMap<Ident, TypeVarId>,
+    trait_map: HashMap<ModulePath, TraitId>,
+    const_map: HashMap<ModulePath, Literal>,
+    static_map: HashMap<Ident, StaticId>,
+    /// Paths of imported items not declared 'pub'
+    private_set: HashSet<ModulePath>,
 }
 
 impl ScopedData {
@@ -356,6 +414,10 @@ impl ScopedData {
             var_map: HashMap::new(),
             fn_map: HashMap::new(),
             type_param_map: HashMap::new(),
+            trait_map: HashMap::new(),
+            const_map: HashMap::new(),
+            static_map: HashMap::new(),
+            private_set: HashSet::new(),
         }
     }
 
@@ -387,24 +449,58 @@ impl ScopedData {
         self.type_cons_map.insert(path, id)
     }
 
+    pub fn trait_id(&self, path: &ModulePath) -> Option<TraitId> {
+        self.trait_map.get(path).cloned()
+    }
+
+    pub fn insert_trait(&mut self, path: ModulePath, id: TraitId) -> Option<TraitId> {
+        self.trait_map.insert(path, id)
+    }
+
     pub fn binding_info(
         &self,
         name: &AstNode<Ident>,
     ) -> Result<BindingInfo, AnalysisError> {
-        match self.var_map.get(name.data()) {
-            Some(v_id) => Ok(BindingInfo::Var(v_id.clone())),
-
-            None => {
-                let p = ModulePath(vec![name.data().clone()]);
-                self.fn_map
-                    .get(&p)
-                    .map(|f| BindingInfo::Fn(f.clone()))
-                    .ok_or(AnalysisError::UnknownBinding(
-                        name.data().clone(),
-                        name.span(),
-                    ))
-            }
+        if let Some(v_id) = self.var_map.get(name.data()) {
+            return Ok(BindingInfo::Var(v_id.clone()));
+        }
+
+        if let Some(static_id) = self.static_map.get(name.data()) {
+            return Ok(BindingInfo::Static(*static_id));
+        }
+
+        let p = ModulePath(vec![name.data().clone()]);
+        if let Some(literal) = self.const_map.get(&p) {
+            return Ok(BindingInfo::Const(literal.clone()));
         }
+
+        self.fn_map
+            .get(&p)
+            .map(|f| BindingInfo::Fn(f.clone()))
+            .ok_or(AnalysisError::UnknownBinding(
+                name.data().clone(),
+                name.span(),
+            ))
+    }
+
+    ///
+    /// Variables shadow module statics
+    ///
+    pub fn path_root(
+        &self,
+        name: &AstNode<Ident>,
+    ) -> Result<PathRoot, AnalysisError> {
+        if let Some(var_id) = self.var_map.get(name.data()) {
+            return Ok(PathRoot::Var(*var_id));
+        }
+
+        self.static_map
+            .get(name.data())
+            .map(|static_id| PathRoot::Static(*static_id))
+            .ok_or(AnalysisError::UnknownBinding(
+                name.data().clone(),
+                name.span(),
+            ))
     }
 
     pub fn var_id(
@@ -423,6 +519,22 @@ impl ScopedData {
         Ok(var_id)
     }
 
+    pub fn insert_const(&mut self, path: ModulePath, value: Literal) -> Option<Literal> {
+        self.const_map.insert(path, value)
+    }
+
+    pub fn get_const(&self, path: &AstModulePath) -> Option<&Literal> {
+        self.const_map.get(&path.clone().into())
+    }
+
+    pub fn all_consts(&self) -> impl Iterator<Item = (&ModulePath, &Literal)> {
+        self.const_map.iter()
+    }
+
+    pub fn insert_static(&mut self, name: Ident, id: StaticId) -> Option<StaticId> {
+        self.static_map.insert(name, id)
+    }
+
     pub fn insert_var(&mut self, name: Ident, id: VarId) -> Option<VarId> {
         self.var_map.insert(name, id)
     }
@@ -431,7 +543,25 @@ impl ScopedData {
         self.fn_map
             .get(&path.clone().into())
             .map(|id| id.clone())
-            .ok_or(AnalysisError::UnknownFn(path.clone(), path.span()))
+            .ok_or_else(|| {
+                self.private_or(path, path.span(), AnalysisError::UnknownFn(path.clone(), path.span()))
+            })
+    }
+
+    pub fn insert_private(&mut self, path: ModulePath) -> bool {
+        self.private_set.insert(path)
+    }
+
+    ///
+    /// Replaces `error` for a failed lookup of `path` if `path` names a private item
+    ///   of an imported module
+    ///
+    pub fn private_or(&self, path: &AstModulePath, span: Span, error: AnalysisError) -> AnalysisError {
+        if self.private_set.contains(&path.clone().into()) {
+            AnalysisError::PrivateItem(path.clone(), span)
+        } else {
+            error
+        }
     }
 
     pub fn insert_type_var(&mut self, ident: Ident, id: TypeVarId) -> bool {
@@ -455,9 +585,15 @@ impl ScopedData {
     pub fn all_fns(&self) -> impl Iterator<Item = (&ModulePath, FnId)> {
         self.fn_map.iter().map(|(path, id)| (path, id.clone()))
     }
+
+    pub fn all_traits(&self) -> impl Iterator<Item = (&ModulePath, TraitId)> {
+        self.trait_map.iter().map(|(path, id)| (path, *id))
+    }
 }
 
 pub enum BindingInfo {
     Var(VarId),
     Fn(FnId),
+    Static(StaticId),
+    Const(Literal),
 }
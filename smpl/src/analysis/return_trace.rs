@@ -92,6 +92,9 @@ fn return_check_id(
 
         Node::ExitScope => Ok(Some(vec![cfg.previous(id)])),
 
+        // Only left by returning
+        Node::LoopFoot(ref data) if data.infinite => Ok(None),
+
         _ => return Err(ControlFlowError::MissingReturn(fn_span.clone()).into()),
     }
 }
@@ -1,7 +1,14 @@
+use std::collections::HashMap;
+
 use crate::feature::*;
 use crate::module::ParsedModule;
+use crate::ast::{
+    AstNode, BuiltinFnParams, DeclStmt, FnParameter, Ident, Module as AstModule,
+    TypeAnnotation, TypeParams, WhereClause,
+};
+use crate::span::Span;
 
-use super::error::AnalysisError;
+use super::error::{AnalysisError, TopLevelError};
 use super::metadata::*;
 use super::mod_resolver;
 use super::semantic_data::*;
@@ -10,20 +17,135 @@ pub fn check_program(
     modules: Vec<ParsedModule>,
 ) -> Result<Program, AnalysisError> {
 
-    let mut program = mod_resolver::check_modules(modules)?;
+    let mut program = mod_resolver::check_modules(modules, None)?;
 
     Metadata::find_main(&mut program)?;
 
     Ok(program)
 }
 
+///
+/// Re-analyze a program after one of its modules was replaced.
+///
+/// Functions keep the `FnId` they had in the `previous` program if their
+///   module and name match.
+///
+/// Fails with `TopLevelError::IncompatibleSignature` if a function in the
+///   reloaded module changed its parameter types, return type, or type parameters.
+///
+pub fn reload_program(
+    modules: Vec<ParsedModule>,
+    previous_module: &ParsedModule,
+    previous: &Metadata,
+) -> Result<Program, AnalysisError> {
+
+    let reloaded = modules
+        .iter()
+        .find(|m| m.module.name() == previous_module.module.name());
+
+    if let Some(reloaded) = reloaded {
+        check_reload_signatures(&previous_module.module, &reloaded.module)?;
+    }
+
+    let mut program = mod_resolver::check_modules(modules, Some(previous))?;
+
+    Metadata::find_main(&mut program)?;
+
+    Ok(program)
+}
+
+#[derive(PartialEq)]
+struct FnSignature<'a> {
+    params: Option<Vec<&'a AstNode<TypeAnnotation>>>,
+    return_type: Option<&'a AstNode<TypeAnnotation>>,
+    type_params: Option<&'a TypeParams>,
+    where_clause: Option<&'a WhereClause>,
+}
+
+impl<'a> FnSignature<'a> {
+    fn param_types(params: &'a Option<Vec<AstNode<FnParameter>>>)
+        -> Option<Vec<&'a AstNode<TypeAnnotation>>> {
+
+        // Parameter names do not matter to callers
+        Some(params
+            .iter()
+            .flat_map(|params| params.iter())
+            .map(|param| &param.data().param_type)
+            .collect())
+    }
+
+    fn signatures(module: &'a AstModule) -> HashMap<&'a Ident, (Span, FnSignature<'a>)> {
+        module.1
+            .iter()
+            .filter_map(|decl| match decl {
+                DeclStmt::Function(ref f) => {
+                    let f = f.data();
+                    Some((f.name.data(), (f.name.span(), FnSignature {
+                        params: FnSignature::param_types(&f.params),
+                        return_type: f.return_type.as_ref(),
+                        type_params: f.type_params.as_ref(),
+                        where_clause: f.where_clause.as_ref(),
+                    })))
+                }
+
+                DeclStmt::BuiltinFunction(ref f) => {
+                    let f = f.data();
+                    let params = match f.params {
+                        BuiltinFnParams::Unchecked => None,
+                        BuiltinFnParams::Checked(ref params) => {
+                            FnSignature::param_types(params)
+                        }
+                    };
+
+                    Some((f.name.data(), (f.name.span(), FnSignature {
+                        params,
+                        return_type: f.return_type.as_ref(),
+                        type_params: f.type_params.as_ref(),
+                        where_clause: f.where_clause.as_ref(),
+                    })))
+                }
+
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn check_reload_signatures(
+    previous: &AstModule,
+    reloaded: &AstModule,
+) -> Result<(), AnalysisError> {
+
+    let previous = FnSignature::signatures(previous);
+
+    let errors: Vec<AnalysisError> = FnSignature::signatures(reloaded)
+        .into_iter()
+        .filter_map(|(name, (span, signature))| {
+            match previous.get(name) {
+                Some((_, previous_signature)) if *previous_signature != signature => {
+                    Some(TopLevelError::IncompatibleSignature(name.clone(), span).into())
+                }
+
+                _ => None,
+            }
+        })
+        .collect();
+
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.into_iter().next().unwrap()),
+        _ => Err(AnalysisError::Errors(errors)),
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(rustfmt, rustfmt_skip)]
 mod tests {
+    use super::super::abstract_type::AbstractType;
     use super::super::error::*;
     use super::*;
     use crate::parser::*;
-    use crate::ast::Ident;
+    use crate::ast::{BinOp, Ident};
     use crate::module::UnparsedModule;
 
     macro_rules! error_variant {
@@ -635,6 +757,51 @@ fn main() {
         }
     }
 
+    #[test]
+    fn generic_uninferable_type_param() {
+        let mod1 = include_test!("generic_uninferable_type_param.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::UnresolvedTypeParameter { ident, .. })) => {
+                assert_eq!(ident.as_str(), "T");
+            }
+            r => panic!("Expected TypeError::UnresolvedTypeParameter. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn generic_conflicting_type_arg() {
+        let mod1 = include_test!("generic_conflicting_type_arg.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::UnexpectedType { found, expected, .. })) => {
+                assert!(matches!(found, AbstractType::Bool(_)));
+                assert!(matches!(expected, AbstractType::Int(_)));
+            }
+            r => panic!("Expected TypeError::UnexpectedType. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn generic_inferred_invalid_type_arg() {
+        let mod1 = include_test!("generic_inferred_invalid_type_arg.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Ok(_) => panic!("Expected a type error. Found OK"),
+
+            Err(AnalysisError::TypeError(_)) => (),
+            Err(AnalysisError::Errors(e)) => {
+                if !e.iter().any(|err| matches!(err, AnalysisError::TypeError(_))) {
+                    panic!("Expected a type error. Found {:?}", e);
+                }
+            }
+            Err(e) => panic!("Expected a type error. Found {:?}", e),
+        }
+    }
+
     #[test]
     fn top_level_name_collision_struct() {
         let mod1 = include_test!("top_level_name_collision_struct.smpl");
@@ -742,4 +909,857 @@ fn main() {
             }
         }
     }
+
+    #[test]
+    fn deref_non_reference() {
+        let mod1 = include_test!("deref_non_reference.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::NotAReference { .. })) => (),
+            r => panic!("Expected TypeError::NotAReference. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn deref_assign_non_reference() {
+        let mod1 = include_test!("deref_assign_non_reference.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::NotAReference { .. })) => (),
+            r => panic!("Expected TypeError::NotAReference. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn for_non_iterable() {
+        let mod1 = include_test!("for_non_iterable.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::NotIterable { .. })) => (),
+            r => panic!("Expected TypeError::NotIterable. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn tuple_unknown_field() {
+        let mod1 = include_test!("tuple_unknown_field.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::UnknownField { name, .. })) => {
+                assert_eq!(name.as_str(), "2");
+            }
+            r => panic!("Expected TypeError::UnknownField. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn tuple_mismatch() {
+        let mod1 = include_test!("tuple_mismatch.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(_)) => (),
+            r => panic!("Expected a type error. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn tuple_destructure_arity() {
+        let check = |input: &str| {
+            let module = parse_module(wrap_input!(input)).unwrap();
+            check_program(vec![module])
+        };
+
+        let mod1 = "mod mod1;
+fn test() {
+    let (a, b) = (1, 2, 3);
+}";
+        match check(mod1) {
+            Err(AnalysisError::TypeError(TypeError::TupleDestructureArity { names, .. })) => {
+                assert_eq!(names, 2)
+            }
+            r => panic!("Expected TypeError::TupleDestructureArity. Found {:?}", r.err()),
+        }
+
+        let mod1 = "mod mod1;
+fn test() {
+    let (a, b, c) = (1, 2);
+}";
+        match check(mod1) {
+            Err(AnalysisError::TypeError(TypeError::TupleDestructureArity { names, .. })) => {
+                assert_eq!(names, 3)
+            }
+            r => panic!("Expected TypeError::TupleDestructureArity. Found {:?}", r.err()),
+        }
+
+        let mod1 = "mod mod1;
+fn test() -> int {
+    let (a, b) = (1, 2);
+    return a + b;
+}";
+        check(mod1).unwrap();
+    }
+
+    #[test]
+    fn method_unknown() {
+        let mod1 = include_test!("method_unknown.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::UnknownMethod { name, .. })) => {
+                assert_eq!(name.as_str(), "product");
+            }
+            r => panic!("Expected TypeError::UnknownMethod. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn impl_invalid_type() {
+        let mod1 = include_test!("impl_invalid_type.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TopLevelError(TopLevelError::InvalidImplType(..))) => (),
+            r => panic!("Expected TopLevelError::InvalidImplType. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn trait_bound_unsatisfied() {
+        let mod1 = include_test!("trait_bound_unsatisfied.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::UnsatisfiedTraitBound { trait_name, .. })) => {
+                assert_eq!(trait_name.as_str(), "Score");
+            }
+            r => panic!("Expected TypeError::UnsatisfiedTraitBound. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn trait_bound_anonymous_fn() {
+        let mod1 = include_test!("trait_bound_anonymous_fn.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::TraitBoundInAnonymousFn { trait_name, .. })) => {
+                assert_eq!(trait_name.as_str(), "Score");
+            }
+            r => panic!("Expected TypeError::TraitBoundInAnonymousFn. Found {:?}", r.err()),
+        }
+
+        // Forwarding the bound to another function
+        let mod1 = include_test!("trait_bound_anonymous_fn.smpl")
+            .replace("return v.score();", "return total(v);");
+
+        let mod1 = parse_module(wrap_input!(&mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::TraitBoundInAnonymousFn { trait_name, .. })) => {
+                assert_eq!(trait_name.as_str(), "Score");
+            }
+            r => panic!("Expected TypeError::TraitBoundInAnonymousFn. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn trait_missing_method() {
+        let mod1 = include_test!("trait_missing_method.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TopLevelError(TopLevelError::MissingTraitMethod(name, _))) => {
+                assert_eq!(name.as_str(), "sides");
+            }
+            r => panic!("Expected TopLevelError::MissingTraitMethod. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn trait_incompatible_method() {
+        let mod1 = include_test!("trait_incompatible_method.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TopLevelError(TopLevelError::IncompatibleTraitMethod(name, _))) => {
+                assert_eq!(name.as_str(), "same");
+            }
+            r => panic!("Expected TopLevelError::IncompatibleTraitMethod. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn trait_unknown() {
+        let mod1 = include_test!("trait_unknown.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::UnknownTrait(..)) => (),
+            r => panic!("Expected AnalysisError::UnknownTrait. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn reference_mismatch() {
+        let mod1 = include_test!("reference_mismatch.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(_)) => (),
+            r => panic!("Expected a type error. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn reload_signatures() {
+        let mod1 =
+"mod mod1;
+
+fn foo(a: int) -> int {
+    return a;
+}";
+
+        let mod1_compatible =
+"mod mod1;
+
+fn foo(b: int) -> int {
+    return b + 1;
+}
+
+fn bar() {
+}";
+
+        let mod1_incompatible =
+"mod mod1;
+
+fn foo(a: float) -> int {
+    return 0;
+}";
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        let program = check_program(vec![mod1.clone()]).unwrap();
+
+        let foo_id = |program: &Program| {
+            let mod_id = program.metadata().mod_metadata().get_module(ident!("mod1")).unwrap();
+            program.metadata().module_fn(mod_id, ident!("foo")).unwrap()
+        };
+
+        let compatible = parse_module(wrap_input!(mod1_compatible)).unwrap();
+        let reloaded = reload_program(vec![compatible], &mod1, program.metadata()).unwrap();
+        assert_eq!(foo_id(&program), foo_id(&reloaded));
+
+        let incompatible = parse_module(wrap_input!(mod1_incompatible)).unwrap();
+        match reload_program(vec![incompatible], &mod1, program.metadata()) {
+            Err(AnalysisError::TopLevelError(TopLevelError::IncompatibleSignature(name, _))) => {
+                assert_eq!(name, ident!("foo"));
+            }
+
+            r => panic!("Expected an incompatible signature error. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn const_mod_access() {
+        let mod1 = include_test!("const_mod_access_1.smpl");
+        let mod2 = include_test!("const_mod_access_2.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        let mod2 = parse_module(wrap_input!(mod2)).unwrap();
+        check_program(vec![mod1, mod2]).unwrap();
+    }
+
+    #[test]
+    fn const_cyclic() {
+        let mod1 = include_test!("const_cyclic.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TopLevelError(TopLevelError::CyclicConst(..))) => (),
+            r => panic!("Expected TopLevelError::CyclicConst. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn const_non_constant() {
+        let mod1 = include_test!("const_non_constant.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TopLevelError(TopLevelError::NonConstantExpr(..))) => (),
+            r => panic!("Expected TopLevelError::NonConstantExpr. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn const_type_mismatch() {
+        let mod1 = include_test!("const_type_mismatch.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::IncompatibleLocal { name, .. })) => {
+                assert_eq!(name.as_str(), "A");
+            }
+            r => panic!("Expected TypeError::IncompatibleLocal. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn const_assignment() {
+        let mod1 = include_test!("const_assignment.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::ConstAssignment(name, _)) => {
+                assert_eq!(name.as_str(), "A");
+            }
+            r => panic!("Expected AnalysisError::ConstAssignment. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn global_duplicate() {
+        let mod1 = include_test!("global_duplicate.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TopLevelError(TopLevelError::DuplicateGlobals(name, _))) => {
+                assert_eq!(name.as_str(), "COUNT");
+            }
+            r => panic!("Expected TopLevelError::DuplicateGlobals. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn bitwise_non_int() {
+        let mod1 = include_test!("bitwise_non_int.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::BinOp { op: BinOp::BitAnd, .. })) => (),
+            r => panic!("Expected TypeError::BinOp. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn if_expr_branch_mismatch() {
+        let mod1 = include_test!("if_expr_branch_mismatch.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::UnexpectedType {
+                found: AbstractType::Bool(_),
+                expected: AbstractType::Int(_),
+                ..
+            })) => (),
+            r => panic!("Expected TypeError::UnexpectedType. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn value_expr_local_type() {
+        let check = |input: &str| {
+            let module = parse_module(wrap_input!(input)).unwrap();
+            check_program(vec![module])
+        };
+
+        // The hidden result local is declared without a value and typed by its first assignment
+        let mod1 = "mod mod1;
+fn test(flag: bool) -> bool {
+    let value = if flag { 1 } else { 2 };
+    let result: bool = value;
+    return result;
+}";
+        match check(mod1) {
+            Err(AnalysisError::TypeError(TypeError::IncompatibleLocal { .. })) => (),
+            r => panic!("Expected TypeError::IncompatibleLocal. Found {:?}", r.err()),
+        }
+
+        let mod1 = "mod mod1;
+fn test(flag: bool) -> int {
+    let values = { [1, 2] };
+    let first = if flag { values[0] } else { if flag { 0 } else { values[1] } };
+    return first;
+}";
+        check(mod1).unwrap();
+    }
+
+    #[test]
+    fn format_not_formattable() {
+        let mod1 = include_test!("format_not_formattable.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::NotFormattable {
+                found: AbstractType::Record { .. },
+                ..
+            })) => (),
+            r => panic!("Expected TypeError::NotFormattable. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn format_to_string_not_string() {
+        let mod1 = include_test!("format_to_string_not_string.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::NotFormattable { .. })) => (),
+            r => panic!("Expected TypeError::NotFormattable. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn nested_mod_imports() {
+        let mod1 = include_test!("nested_mod_imports_1.smpl");
+        let mod2 = include_test!("nested_mod_imports_2.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        let mod2 = parse_module(wrap_input!(mod2)).unwrap();
+        check_program(vec![mod1, mod2]).unwrap();
+    }
+
+    #[test]
+    fn import_unknown_item() {
+        let mod1 = include_test!("import_unknown_item_1.smpl");
+        let mod2 = include_test!("import_unknown_item_2.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        let mod2 = parse_module(wrap_input!(mod2)).unwrap();
+        match check_program(vec![mod1, mod2]) {
+            Err(AnalysisError::UnknownImport(ident, _)) => assert_eq!(ident, ident!("missing")),
+            r => panic!("Expected AnalysisError::UnknownImport. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn import_ambiguous() {
+        let mod1 = include_test!("import_ambiguous_1.smpl");
+        let mod2 = include_test!("import_ambiguous_2.smpl");
+        let mod3 = include_test!("import_ambiguous_3.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        let mod2 = parse_module(wrap_input!(mod2)).unwrap();
+        let mod3 = parse_module(wrap_input!(mod3)).unwrap();
+        match check_program(vec![mod1, mod2, mod3]) {
+            Err(AnalysisError::AmbiguousImport(ident, _)) => assert_eq!(ident, ident!("total")),
+            r => panic!("Expected AnalysisError::AmbiguousImport. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn import_unknown_module() {
+        let mod1 = "mod mod1; use game::missing; fn test() { }";
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::UnresolvedUses(uses)) => {
+                assert_eq!(uses[0].0, ident!("game::missing"))
+            }
+            r => panic!("Expected AnalysisError::UnresolvedUses. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn private_items() {
+        let private_mod = include_test!("private_items.smpl");
+        let check = |mod1: &str| {
+            let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+            let mod2 = parse_module(wrap_input!(private_mod)).unwrap();
+            check_program(vec![mod1, mod2])
+        };
+
+        check("mod mod1; use mod2; fn test() -> int { return mod2::total(); }").unwrap();
+
+        let private_accesses = vec![
+            ("mod mod1; use mod2; fn test() -> int { return mod2::helper(); }", "mod2::helper"),
+            ("mod mod1; use mod2; fn test() -> int { return mod2::LIMIT; }", "mod2::LIMIT"),
+            ("mod mod1; use mod2; fn test(s: mod2::Secret) { }", "mod2::Secret"),
+            ("mod mod1; use mod2::{total, helper}; fn test() { }", "mod2::helper"),
+        ];
+
+        for (mod1, expected) in private_accesses {
+            match check(mod1) {
+                Err(AnalysisError::PrivateItem(path, _)) => assert_eq!(path.to_string(), expected),
+                r => panic!("Expected AnalysisError::PrivateItem. Found {:?}", r.err()),
+            }
+        }
+    }
+
+    #[test]
+    fn private_items_opt_in() {
+        // 'pub' items alone do not hide the other items of a module
+        let mod2 = "mod mod2;
+pub fn total() -> int { return helper() + 1; }
+fn helper() -> int { return 2; }
+struct Secret { value: int }";
+
+        let check = |mod1: &str| {
+            let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+            let mod2 = parse_module(wrap_input!(mod2)).unwrap();
+            check_program(vec![mod1, mod2])
+        };
+
+        check("mod mod1; use mod2; fn test() -> int { return mod2::total() + mod2::helper(); }")
+            .unwrap();
+        check("mod mod1; use mod2::{helper, Secret}; fn test(s: Secret) -> int { return helper(); }")
+            .unwrap();
+
+        // Annotations before the 'mod' declaration belong to the module, not its first item
+        let module = parse_module(wrap_input!("#[explicit_pub]
+mod mod2;
+fn helper() { }")).unwrap();
+        assert_eq!(module.module.2.len(), 1);
+        assert_eq!(module.module.2[0].keys[0].0, ident!("explicit_pub"));
+    }
+
+    #[test]
+    fn type_alias_mismatch() {
+        let mod1 = include_test!("type_alias_mismatch.smpl");
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            // The declared type is reported as written, not expanded
+            Err(AnalysisError::TypeError(TypeError::IncompatibleLocal {
+                local_type: AbstractType::App { .. },
+                ..
+            })) => (),
+            r => panic!("Expected TypeError::IncompatibleLocal. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn type_alias_diagnostics() {
+        let mod1 = "mod mod1; type Hp = int; fn f() -> Hp { return true; }";
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TypeError(TypeError::UnexpectedType {
+                expected: AbstractType::Alias { ref name, .. },
+                found: AbstractType::Bool(_),
+                ..
+            })) => assert_eq!(name.as_str(), "Hp"),
+            r => panic!("Expected TypeError::UnexpectedType. Found {:?}", r.err()),
+        }
+
+        let mod2 = "mod mod2; type Hp = int; fn f() { let h: Hp = 1; let b: bool = h; }";
+
+        let mod2 = parse_module(wrap_input!(mod2)).unwrap();
+        match check_program(vec![mod2]) {
+            Err(AnalysisError::TypeError(TypeError::IncompatibleLocal {
+                local_type: AbstractType::App { .. },
+                found_type: AbstractType::Alias { ref name, .. },
+                ..
+            })) => assert_eq!(name.as_str(), "Hp"),
+            r => panic!("Expected TypeError::IncompatibleLocal. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn type_alias_cyclic() {
+        let mod1 = "mod mod1; type A = (int, B); type B = [A; 2]; fn test() { }";
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        match check_program(vec![mod1]) {
+            Err(AnalysisError::TopLevelError(TopLevelError::CyclicAlias(..))) => (),
+            r => panic!("Expected TopLevelError::CyclicAlias. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn partial_app_type() {
+        let mod1 =
+"mod mod1;
+
+fn add(a: int, b: float, c: bool) -> int {
+    return a;
+}
+
+fn id(type T)(t: T) -> T {
+    return t;
+}
+
+fn test() {
+    let f: fn(float) -> int = add(1, _, true);
+    let g: fn(int, bool) -> int = add(_, 1.0, _);
+    let h: fn(bool) -> int = g(2, _);
+    let i: fn(int) -> int = id(type int)(_);
+}";
+
+        let mod1 = parse_module(wrap_input!(mod1)).unwrap();
+        check_program(vec![mod1]).unwrap();
+
+        let mismatch = "mod mod1; fn add(a: int, b: float) -> int { return a; }
+fn test() { let f: fn(int) -> int = add(1, _); }";
+        let mismatch = parse_module(wrap_input!(mismatch)).unwrap();
+        match check_program(vec![mismatch]) {
+            Err(AnalysisError::TypeError(TypeError::IncompatibleLocal { .. })) => (),
+            r => panic!("Expected TypeError::IncompatibleLocal. Found {:?}", r.err()),
+        }
+
+        let arity = "mod mod1; fn add(a: int, b: float) -> int { return a; }
+fn test() { let f = add(_); }";
+        let arity = parse_module(wrap_input!(arity)).unwrap();
+        match check_program(vec![arity]) {
+            Err(AnalysisError::TypeError(TypeError::Arity { .. })) => (),
+            r => panic!("Expected TypeError::Arity. Found {:?}", r.err()),
+        }
+
+        let generic = "mod mod1; fn id(type T)(t: T) -> T { return t; }
+fn test() { let f = id(_); }";
+        let generic = parse_module(wrap_input!(generic)).unwrap();
+        match check_program(vec![generic]) {
+            Err(AnalysisError::TypeError(TypeError::InvalidPartialApp { .. })) => (),
+            r => panic!("Expected TypeError::InvalidPartialApp. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn loop_labels() {
+        let check = |input: &str| {
+            let input = format!("mod mod1; fn test() -> int {{ {} }}", input);
+            let module = parse_module(wrap_input!(&input)).unwrap();
+            check_program(vec![module])
+        };
+
+        // Only left by returning
+        check("let i = 0; loop { i = i + 1; if i > 3 { return i; } }").unwrap();
+
+        match check("'outer: while true { break 'inner; } return 0;") {
+            Err(AnalysisError::ControlFlowError(ControlFlowError::UnknownLabel(label, _))) => {
+                assert_eq!(label, ident!("inner"))
+            }
+            r => panic!("Expected ControlFlowError::UnknownLabel. Found {:?}", r.err()),
+        }
+
+        match check("let x = loop { while true { break 1; } }; return x;") {
+            Err(AnalysisError::ControlFlowError(ControlFlowError::BreakValue(..))) => (),
+            r => panic!("Expected ControlFlowError::BreakValue. Found {:?}", r.err()),
+        }
+
+        match check("let x = loop { break; }; return x;") {
+            Err(AnalysisError::ControlFlowError(ControlFlowError::MissingBreakValue(..))) => (),
+            r => panic!("Expected ControlFlowError::MissingBreakValue. Found {:?}", r.err()),
+        }
+
+        match check("while true { } continue; return 0;") {
+            Err(AnalysisError::ControlFlowError(ControlFlowError::BadContinue(..))) => (),
+            r => panic!("Expected ControlFlowError::BadContinue. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn struct_field_defaults() {
+        let check = |input: &str| {
+            let module = parse_module(wrap_input!(input)).unwrap();
+            check_program(vec![module])
+        };
+
+        let mod1 = "mod mod1;
+struct Point {
+    x: int,
+    y: int = 0,
+}
+
+fn test() -> Point {
+    let q = init Point { x: 1 };
+    return init Point { y: 2, ..q };
+}";
+        check(mod1).unwrap();
+
+        let mod1 = "mod mod1;
+struct Point {
+    x: int,
+    y: int = 0,
+}
+
+fn test() -> Point {
+    return init Point { y: 1 };
+}";
+        match check(mod1) {
+            Err(AnalysisError::TypeError(TypeError::StructNotFullyInitialized {
+                missing_fields, ..
+            })) => assert_eq!(missing_fields, vec![ident!("x")]),
+            r => panic!("Expected TypeError::StructNotFullyInitialized. Found {:?}", r.err()),
+        }
+
+        let mod1 = "mod mod1;
+struct Point {
+    x: int,
+    y: int = true,
+}";
+        match check(mod1) {
+            Err(AnalysisError::TypeError(TypeError::IncompatibleFieldDefault { name, .. })) => {
+                assert_eq!(name, ident!("y"))
+            }
+            r => panic!("Expected TypeError::IncompatibleFieldDefault. Found {:?}", r.err()),
+        }
+
+        let mod1 = "mod mod1;
+fn zero() -> int {
+    return 0;
+}
+
+struct Point {
+    x: int = zero(),
+}";
+        match check(mod1) {
+            Err(AnalysisError::TopLevelError(TopLevelError::NonConstantExpr(..))) => (),
+            r => panic!("Expected TopLevelError::NonConstantExpr. Found {:?}", r.err()),
+        }
+
+        let mod1 = "mod mod1;
+struct Point {
+    x: int,
+}
+
+struct Other {
+    x: int,
+}
+
+fn test(o: Other) -> Point {
+    return init Point { ..o };
+}";
+        assert!(check(mod1).is_err());
+    }
+
+    #[test]
+    fn growable_arrays() {
+        let check = |input: &str| {
+            let module = parse_module(wrap_input!(input)).unwrap();
+            check_program(vec![module])
+        };
+
+        let mod1 = "mod mod1;
+fn sum(values: [int]) -> int {
+    let total = 0;
+    for v in values {
+        total += v;
+    }
+    return total;
+}
+
+fn test() -> int {
+    let fixed: [int; 3] = [1, 2, 3];
+    let values: [int] = [];
+    values = fixed;
+    let tail: [int] = values[1..2];
+    return sum(fixed) + sum(tail) + sum([]) + values[0];
+}";
+        check(mod1).unwrap();
+
+        // Growable arrays have no static size
+        let mod1 = "mod mod1;
+fn test() {
+    let values: [int] = [1, 2, 3];
+    let fixed: [int; 3] = values;
+}";
+        match check(mod1) {
+            Err(AnalysisError::TypeError(TypeError::IncompatibleLocal { .. })) => (),
+            r => panic!("Expected TypeError::IncompatibleLocal. Found {:?}", r.err()),
+        }
+
+        // Growing through the reference would change the size of the fixed array
+        let mod1 = "mod mod1;
+fn test() {
+    let fixed = [1, 2, 3];
+    let values: &[int] = &fixed;
+}";
+        match check(mod1) {
+            Err(AnalysisError::TypeError(TypeError::IncompatibleLocal { .. })) => (),
+            r => panic!("Expected TypeError::IncompatibleLocal. Found {:?}", r.err()),
+        }
+
+        let mod1 = "mod mod1;
+fn test() {
+    let values: [int] = [\"a\"];
+}";
+        assert!(check(mod1).is_err());
+
+        let mod1 = "mod mod1;
+fn test() {
+    let a = 5;
+    let b = a[0..1];
+}";
+        match check(mod1) {
+            Err(AnalysisError::TypeError(TypeError::NotAnArray { .. })) => (),
+            r => panic!("Expected TypeError::NotAnArray. Found {:?}", r.err()),
+        }
+
+        let mod1 = "mod mod1;
+fn test() {
+    let a: [int] = [1, 2];
+    let b = a[0..true];
+}";
+        match check(mod1) {
+            Err(AnalysisError::TypeError(TypeError::InvalidIndex { .. })) => (),
+            r => panic!("Expected TypeError::InvalidIndex. Found {:?}", r.err()),
+        }
+    }
+
+    #[test]
+    fn reference_args() {
+        let check = |input: &str| {
+            let module = parse_module(wrap_input!(input)).unwrap();
+            check_program(vec![module])
+        };
+
+        let mod1 = "mod mod1;
+builtin fn push(a: &[int], v: int);
+
+fn inc(a: &int) {
+    *a += 1;
+}
+
+fn test() {
+    let x = 1;
+    inc(&x);
+    let values: [int] = [];
+    push(&values, 4);
+}";
+        check(mod1).unwrap();
+
+        let mod1 = "mod mod1;
+fn inc(a: &int) {
+    *a += 1;
+}
+
+fn test() {
+    let x = 1;
+    inc(x);
+}";
+        match check(mod1) {
+            Err(AnalysisError::TypeError(TypeError::ArgMismatch { .. })) => (),
+            r => panic!("Expected TypeError::ArgMismatch. Found {:?}", r.err()),
+        }
+
+        let mod1 = "mod mod1;
+builtin fn push(a: &[int], v: int);
+
+fn test() {
+    let values: [int] = [];
+    push(values, 4);
+}";
+        match check(mod1) {
+            Err(AnalysisError::TypeError(TypeError::ArgMismatch { .. })) => (),
+            r => panic!("Expected TypeError::ArgMismatch. Found {:?}", r.err()),
+        }
+
+        let mod1 = "mod mod1;
+fn add(a: &int, b: int) -> int {
+    return *a + b;
+}
+
+fn test() {
+    let x = 1;
+    let f = add(x, _);
+}";
+        match check(mod1) {
+            Err(AnalysisError::TypeError(TypeError::ArgMismatch { .. })) => (),
+            r => panic!("Expected TypeError::ArgMismatch. Found {:?}", r.err()),
+        }
+    }
 }
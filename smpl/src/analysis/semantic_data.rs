@@ -324,6 +324,7 @@ impl SMPLFunction {
 pub enum BindingId {
     Var(VarId),
     Fn(FnId),
+    Static(StaticId),
 }
 
 impl From<VarId> for BindingId {
@@ -338,6 +339,12 @@ impl From<FnId> for BindingId {
     }
 }
 
+impl From<StaticId> for BindingId {
+    fn from(id: StaticId) -> BindingId {
+        BindingId::Static(id)
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TypeId(pub(super) u64);
 
@@ -368,6 +375,36 @@ impl TypeParamId {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TraitId(pub(super) u64);
+
+impl ::std::fmt::Display for TraitId {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TraitId[{}]", self.0)
+    }
+}
+
+impl TraitId {
+    pub fn raw(&self) -> u64 {
+        self.0
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StaticId(pub(super) u64);
+
+impl ::std::fmt::Display for StaticId {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "StaticId[{}]", self.0)
+    }
+}
+
+impl StaticId {
+    pub fn raw(&self) -> u64 {
+        self.0
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TypeVarId(pub(super) u64);
 
@@ -521,12 +558,8 @@ impl ModulePath {
 
 impl fmt::Display for ModulePath {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let buffer =
-            self.0.iter().fold(String::new(), |mut buffer, ref item| {
-                buffer.push_str(&item.0);
-                buffer
-            });
-        write!(f, "{}", buffer)
+        let segments: Vec<_> = self.0.iter().map(|item| item.as_str()).collect();
+        write!(f, "{}", segments.join("::"))
     }
 }
 
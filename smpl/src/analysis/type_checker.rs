@@ -17,7 +17,7 @@ use super::semantic_data::*;
 use super::semantic_data::{ AnonymousFn as ResolvedAnonymousFn };
 use super::abstract_type::*;
 use super::type_cons_gen;
-use super::type_cons::TypeCons;
+use super::type_cons::{TypeCons, TypeParams};
 use super::type_resolver;
 use super::typed_ast::*;
 use super::analysis_context::{
@@ -91,6 +91,8 @@ struct TypeChecker<'a> {
     return_type: AbstractType,
     anon_typing_context_storage: AnonStorage<TypingContext>,
     anon_type_cons_storage: AnonStorage<TypeCons>,
+    trait_bounds: Vec<(TypeVarId, TraitId)>,
+    pending_dictionaries: HashMap<TmpId, Vec<TraitDictionary>>,
 }
 
 impl<'a> TypeChecker<'a> {
@@ -160,6 +162,8 @@ impl<'a> TypeChecker<'a> {
                             return_type: return_type,
                             anon_typing_context_storage: AnonStorage::new(),
                             anon_type_cons_storage: AnonStorage::new(),
+                            trait_bounds: Vec::new(),
+                            pending_dictionaries: HashMap::new(),
                         })
                     }
                 }
@@ -203,6 +207,28 @@ impl<'a> TypeChecker<'a> {
                     }
                 };
 
+                // Bounded type parameters receive their trait dictionaries as hidden parameters
+                let trait_bounds = match universe.get_type_cons(smpl_function.type_id()) {
+                    TypeCons::Function { ref type_params, .. } => {
+                        let type_vars = smpl_function
+                            .analysis_context()
+                            .existential_type_vars();
+
+                        type_params
+                            .bounds()
+                            .iter()
+                            .map(|(param_id, trait_id)| {
+                                let position = type_params
+                                    .position(*param_id)
+                                    .expect("Bound on unknown type parameter");
+                                (type_vars[position], *trait_id)
+                            })
+                            .collect()
+                    }
+
+                    _ => panic!("Non-function type constructor for function"),
+                };
+
                 Ok(TypeChecker {
                     scopes: vec![fn_scope],
                     typing_context: typing_context,
@@ -213,6 +239,8 @@ impl<'a> TypeChecker<'a> {
                     return_type: return_type,
                     anon_typing_context_storage: AnonStorage::new(),
                     anon_type_cons_storage: AnonStorage::new(),
+                    trait_bounds,
+                    pending_dictionaries: HashMap::new(),
                 })
             }
         }
@@ -252,6 +280,15 @@ impl<'a> TypeChecker<'a> {
             }
         }
 
+        // Instantiations of bounded functions must be called in the same expression
+        let pending = self.pending_dictionaries.keys().next().cloned();
+        if let Some(tmp_id) = pending {
+            return Err(TypeError::TraitBoundedFnValue {
+                span: expr.get_tmp(tmp_id).span(),
+            }
+            .into());
+        }
+
         Ok(expr_type.unwrap())
     }
 
@@ -300,7 +337,7 @@ impl<'a> TypeChecker<'a> {
             }
 
             Value::StructInit(ref init) => {
-                self.resolve_struct_init(init, tmp.span())?
+                self.resolve_struct_init(tmp.id(), init, tmp.span())?
             }
 
             Value::AnonStructInit(ref init) => self.resolve_anon_struct_init(
@@ -317,19 +354,63 @@ impl<'a> TypeChecker<'a> {
             }
 
             Value::FnCall(ref fn_call) => {
-                self.resolve_fn_call(fn_call, tmp.span())?
+                self.resolve_fn_call(fn_call, tmp.id(), tmp.span())?
+            }
+
+            Value::PartialApp(ref partial) => {
+                self.resolve_partial_app(partial, tmp.id(), tmp.span())?
+            }
+
+            Value::MethodCall(ref call) => {
+                self.resolve_method_call(call, tmp.id(), tmp.span())?
             }
 
             Value::ArrayInit(ref init) => {
                 self.resolve_array_init(init, tmp.span())?
             }
 
+            Value::TupleInit(ref init) => {
+                let elements = init
+                    .elements
+                    .iter()
+                    .map(|element| {
+                        self.typing_context
+                            .tmp_type_map
+                            .get(element.data())
+                            .expect("Missing TMP")
+                            .substitute(self.universe, self.current(), &self.typing_context)
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                AbstractType::Tuple {
+                    data: tmp.span(),
+                    elements,
+                }
+            }
+
             Value::Indexing(ref indexing) => {
                 self.resolve_indexing(indexing, tmp.span())?
             }
 
+            Value::Slice(ref slice) => {
+                self.resolve_slice(slice, tmp.span())?
+            }
+
+            // Every part is a String tmp
+            Value::Format(..) => AbstractType::String(tmp_span.clone()),
+
+            Value::Stringify(ref value) => {
+                self.resolve_stringify(value, tmp.id(), tmp.span())?
+            }
+
+            Value::Iter(ref iter) => {
+                let tmp_type = self.resolve_iter(iter, tmp.span())?;
+                self.typing_context.iter_protocol.insert(tmp.id(), tmp_type.1);
+                tmp_type.0
+            }
+
             Value::TypeInst(ref type_inst) => {
-                self.resolve_type_inst(type_inst, tmp.span())?
+                self.resolve_type_inst(type_inst, tmp.id(), tmp.span())?
             }
 
             Value::AnonymousFn(ref a_fn) => {
@@ -376,8 +457,9 @@ impl<'a> TypeChecker<'a> {
                 }) => {
 
                     // Store the snapshot
-                    self.anon_typing_context_storage
-                        .insert(fn_id, self.typing_context.clone());
+                    let mut snapshot = self.typing_context.clone();
+                    snapshot.enclosing_trait_bounds.extend(self.trait_bounds.iter().cloned());
+                    self.anon_typing_context_storage.insert(fn_id, snapshot);
 
                     let current_scope =
                         self.scopes.last().expect("Expect a scope");
@@ -453,7 +535,7 @@ impl<'a> TypeChecker<'a> {
         let expected_bool = AbstractType::Bool(span.clone());
 
         let resolve_type = match *op {
-            Add | Sub | Mul | Div | Mod => match (&lhs, &rhs) {
+            Add | Sub | Mul | Div | Mod => match (lhs.expanded(), rhs.expanded()) {
                 (&AbstractType::Int(_), &AbstractType::Int(_)) => AbstractType::Int(span.clone()),
                 (&AbstractType::Float(_), &AbstractType::Float(_)) => AbstractType::Float(span.clone()),
 
@@ -469,7 +551,7 @@ impl<'a> TypeChecker<'a> {
                 }
             },
 
-            LogicalAnd | LogicalOr => match (&lhs, &rhs) {
+            LogicalAnd | LogicalOr => match (lhs.expanded(), rhs.expanded()) {
                 (&AbstractType::Bool(_), &AbstractType::Bool(_)) => AbstractType::Bool(span.clone()),
                 _ => {
                     return Err(TypeError::BinOp {
@@ -483,7 +565,7 @@ impl<'a> TypeChecker<'a> {
                 }
             },
 
-            GreaterEq | LesserEq | Greater | Lesser => match (&lhs, &rhs) {
+            GreaterEq | LesserEq | Greater | Lesser => match (lhs.expanded(), rhs.expanded()) {
                 (&AbstractType::Int(_), &AbstractType::Int(_)) => AbstractType::Bool(span.clone()),
                 (&AbstractType::Float(_), &AbstractType::Float(_)) => AbstractType::Bool(span.clone()),
 
@@ -499,6 +581,21 @@ impl<'a> TypeChecker<'a> {
                 }
             },
 
+            BitAnd | BitOr | BitXor | Shl | Shr => match (lhs.expanded(), rhs.expanded()) {
+                (&AbstractType::Int(_), &AbstractType::Int(_)) => AbstractType::Int(span.clone()),
+
+                _ => {
+                    return Err(TypeError::BinOp {
+                        op: op.clone(),
+                        expected: vec![expected_int],
+                        lhs: lhs.clone(),
+                        rhs: rhs.clone(),
+                        span,
+                    }
+                    .into());
+                }
+            },
+
             Eq | InEq => {
                 // TODO: Stricter equality check?
                 let current_scope =
@@ -536,7 +633,7 @@ impl<'a> TypeChecker<'a> {
         let expected_bool = AbstractType::Bool(span.clone());
 
         match *op {
-            Negate => match tmp_type {
+            Negate => match tmp_type.expanded() {
                 AbstractType::Int(_) | AbstractType::Float(_) => Ok(tmp_type.clone()),
                 _ => Err(TypeError::UniOp {
                     op: op.clone(),
@@ -547,7 +644,18 @@ impl<'a> TypeChecker<'a> {
                 .into()),
             },
 
-            LogicalInvert => match tmp_type {
+            BitNot => match tmp_type.expanded() {
+                AbstractType::Int(_) => Ok(tmp_type.clone()),
+                _ => Err(TypeError::UniOp {
+                    op: op.clone(),
+                    expected: vec![expected_int],
+                    expr: tmp_type.clone(),
+                    span,
+                }
+                .into()),
+            },
+
+            LogicalInvert => match tmp_type.expanded() {
                 AbstractType::Bool(_) => Ok(tmp_type.clone()),
                 _ => Err(TypeError::UniOp {
                     op: op.clone(),
@@ -558,12 +666,30 @@ impl<'a> TypeChecker<'a> {
                 .into()),
             },
 
-            _ => unimplemented!(),
+            Ref => Ok(AbstractType::Ref {
+                data: span,
+                inner: Box::new(tmp_type.clone()),
+            }),
+
+            Deref => match tmp_type.expanded() {
+                AbstractType::Ref { ref inner, .. } => {
+                    inner.substitute(self.universe, self.current(), &self.typing_context)
+                }
+                _ => Err(TypeError::NotAReference {
+                    found: tmp_type.clone(),
+                    span,
+                }
+                .into()),
+            },
         }
     }
 
+    ///
+    /// Fields left out of the init are copied from the `..base` struct or take their default.
+    ///
     fn resolve_struct_init(
         &mut self,
+        init_tmp: TmpId,
         init: &StructInit,
         init_span: Span,
     ) -> Result<AbstractType, AnalysisError> {
@@ -572,7 +698,13 @@ impl<'a> TypeChecker<'a> {
         let tmp_type_name = type_name.clone().into();
         let struct_type_id = self.current()
             .type_cons(&tmp_type_name)
-            .ok_or(AnalysisError::UnknownType(type_name.clone(), init_span.clone()))?;
+            .ok_or_else(|| {
+                self.current().private_or(
+                    type_name,
+                    init_span.clone(),
+                    AnalysisError::UnknownType(type_name.clone(), init_span.clone()),
+                )
+            })?;
 
         let type_args = init
             .type_args()
@@ -593,7 +725,7 @@ impl<'a> TypeChecker<'a> {
 
         // Check if type is a struct.
         // Opaque types are represented using AbstractType::Opaque
-        let (_struct_type_id, fields, field_map) = match struct_type {
+        let (_struct_type_id, fields, field_map) = match *struct_type.expanded() {
             AbstractType::Record {
                 type_id: struct_type_id,
                 ref abstract_field_map,
@@ -606,7 +738,7 @@ impl<'a> TypeChecker<'a> {
 
             AbstractType::Opaque { .. } => {
                 return Err(TypeError::InitOpaqueType {
-                    struct_type: struct_type,
+                    struct_type: struct_type.clone(),
                     span: init_span,
                 }
                 .into());
@@ -615,7 +747,7 @@ impl<'a> TypeChecker<'a> {
             _ => {
                 return Err(TypeError::NotAStruct {
                     type_name: type_name.clone(),
-                    found: struct_type,
+                    found: struct_type.clone(),
                     span: init_span,
                 }
                 .into());
@@ -647,14 +779,43 @@ impl<'a> TypeChecker<'a> {
             }
         }
 
-        // Not a full struct init
-        if init_expr_type_map.len() != fields.len() {
-            let missing_fields = field_map
-                .iter()
-                .filter(|(_, field_id)| init_expr_type_map.contains_key(field_id))
-                .map(|(ident, _)| ident.clone())
-                .collect();
+        if let Some(base) = init.base() {
+            let base_type = self.typing_context
+                .tmp_type_map
+                .get(base.data())
+                .expect("Missing tmp")
+                .substitute(self.universe, self.current(), &self.typing_context)?;
+
+            let current_scope =
+                self.scopes.last().expect("Should always have a scope");
+            type_resolver::resolve_types(
+                self.universe,
+                current_scope,
+                &mut self.typing_context,
+                &base_type,
+                &struct_type,
+                init_span.clone(),
+            )?;
+        }
+
+        let mut fills = Vec::new();
+        let mut missing_fields = Vec::new();
+        for (field_name, field_id) in field_map.iter() {
+            if init_expr_type_map.contains_key(field_id) {
+                continue;
+            }
+
+            match (init.base(), self.universe.field_default(*field_id)) {
+                (Some(base), _) => fills.push((field_name.clone(), FieldFill::Base(*base.data()))),
+                (None, Some(default)) => {
+                    fills.push((field_name.clone(), FieldFill::Default(default.clone())))
+                }
+                (None, None) => missing_fields.push(field_name.clone()),
+            }
+        }
 
+        // Not a full struct init
+        if !missing_fields.is_empty() {
             return Err(TypeError::StructNotFullyInitialized {
                 type_name: type_name.clone(),
                 struct_type: struct_type.clone(),
@@ -664,13 +825,21 @@ impl<'a> TypeChecker<'a> {
             .into());
         }
 
+        if !fills.is_empty() {
+            self.typing_context.struct_fills.insert(init_tmp, fills);
+        }
+
         // SATISFIED CONDITIONS:
         //   Field init expressions should be fully typed (tmps)
         //   Field names are all present and all valid
+        //   Fields left out have a base struct or default (checked when folded)
 
         // Check if field init expressions are of the correct type
         for (field_id, field_type) in fields.iter() {
-            let init_expr_type = init_expr_type_map.get(field_id).unwrap();
+            let init_expr_type = match init_expr_type_map.get(field_id) {
+                Some(init_expr_type) => init_expr_type,
+                None => continue,
+            };
 
             let current_scope =
                 self.scopes.last().expect("Should always have a scope");
@@ -744,6 +913,8 @@ impl<'a> TypeChecker<'a> {
                 .expect("Missing VarId")
                 .clone()),
 
+            BindingId::Static(static_id) => Ok(self.universe.get_static_type(static_id).clone()),
+
             BindingId::Fn(fn_id) => {
                 // self.program.features_mut().add_feature(FUNCTION_VALUE);
 
@@ -780,8 +951,15 @@ impl<'a> TypeChecker<'a> {
                     data: bind_span,
                     type_cons: Box::new(fn_type_cons),
                     args: Vec::new(),
+                };
+
+                // Type arguments are inferred when the function is called
+                if requires_type_args(&fn_type) {
+                    return Ok(fn_type);
                 }
-                .substitute(self.universe, self.current(), &self.typing_context)?;
+
+                let fn_type = fn_type
+                    .substitute(self.universe, self.current(), &self.typing_context)?;
 
                 Ok(fn_type)
             }
@@ -808,111 +986,755 @@ impl<'a> TypeChecker<'a> {
         };
         */
 
-        let fn_type_cons = self.universe
-            .get_fn_type_cons(fn_id)
-            .expect("Expect anonymous functions to already be resolved")
-            .clone();
+        self.fn_value_type(fn_id, access_span)
+    }
+
+    ///
+    /// The type of a function referenced by its FnId, left uninstantiated if generic.
+    ///
+    fn fn_value_type(
+        &self,
+        fn_id: FnId,
+        span: Span,
+    ) -> Result<AbstractType, AnalysisError> {
+        let fn_type_cons = self.universe
+            .get_fn_type_cons(fn_id)
+            .expect("Expect anonymous functions to already be resolved")
+            .clone();
+
+        let fn_type = AbstractType::App2 {
+            data: span,
+            type_cons: Box::new(fn_type_cons),
+            args: Vec::new(),
+        };
+
+        // Type arguments are inferred when the function is called
+        if requires_type_args(&fn_type) {
+            return Ok(fn_type);
+        }
+
+        let fn_type = fn_type.substitute(
+            self.universe,
+            self.current(),
+            &self.typing_context)?;
+
+        Ok(fn_type)
+    }
+
+    ///
+    /// Checks the arguments of a call against the parameters of the called function type.
+    ///
+    fn check_call(
+        &self,
+        fn_type: &AbstractType,
+        arg_types: &[AbstractType],
+        span: Span,
+    ) -> Result<AbstractType, AnalysisError> {
+        match fn_type.expanded() {
+            AbstractType::Function {
+                data: ref _fn_type_span,
+                parameters: ref params,
+                ref return_type,
+            } => {
+                if params.len() != arg_types.len() {
+                    return Err(TypeError::Arity {
+                        fn_type: fn_type.clone(),
+                        found_args: arg_types.len(),
+                        expected_param: params.len(),
+                        span,
+                    }
+                    .into());
+                }
+
+                for (index, (arg_type, param_type)) in
+                    arg_types.iter().zip(params.iter()).enumerate()
+                {
+                    let resolved = type_resolver::resolve_types_static(
+                        self.universe,
+                        self.current(),
+                        &self.typing_context,
+                        arg_type,
+                        param_type,
+                        span.clone(),
+                    );
+
+                    if resolved.is_err() {
+                        return Err(TypeError::ArgMismatch {
+                            fn_type: fn_type.clone(),
+                            index,
+                            arg: arg_type.clone(),
+                            param: param_type.clone(),
+                            span,
+                        }
+                        .into());
+                    }
+                }
+
+                Ok(*(return_type.clone()))
+            }
+
+            AbstractType::UncheckedFunction { return_type, .. } => {
+                Ok(*(return_type.clone()))
+            }
+
+            t => panic!("AnalyzableFn call on a non-function type: {:?}", t),
+        }
+    }
+
+    ///
+    /// A partial application produces a function over the parameters left as placeholders (`None`).
+    ///
+    fn check_partial_app(
+        &self,
+        fn_type: &AbstractType,
+        arg_types: &[Option<AbstractType>],
+        span: Span,
+    ) -> Result<AbstractType, AnalysisError> {
+        match fn_type.expanded() {
+            AbstractType::Function {
+                parameters: ref params,
+                ref return_type,
+                ..
+            } => {
+                if params.len() != arg_types.len() {
+                    return Err(TypeError::Arity {
+                        fn_type: fn_type.clone(),
+                        found_args: arg_types.len(),
+                        expected_param: params.len(),
+                        span,
+                    }
+                    .into());
+                }
+
+                for (index, (arg_type, param_type)) in
+                    arg_types.iter().zip(params.iter()).enumerate()
+                {
+                    let arg_type = match *arg_type {
+                        Some(ref arg_type) => arg_type,
+                        None => continue,
+                    };
+
+                    let resolved = type_resolver::resolve_types_static(
+                        self.universe,
+                        self.current(),
+                        &self.typing_context,
+                        arg_type,
+                        param_type,
+                        span.clone(),
+                    );
+
+                    if resolved.is_err() {
+                        return Err(TypeError::ArgMismatch {
+                            fn_type: fn_type.clone(),
+                            index,
+                            arg: arg_type.clone(),
+                            param: param_type.clone(),
+                            span,
+                        }
+                        .into());
+                    }
+                }
+
+                let parameters = arg_types
+                    .iter()
+                    .zip(params.iter())
+                    .filter(|(arg_type, _)| arg_type.is_none())
+                    .map(|(_, param_type)| param_type.clone())
+                    .collect();
+
+                Ok(AbstractType::Function {
+                    data: span,
+                    parameters,
+                    return_type: return_type.clone(),
+                })
+            }
+
+            AbstractType::UncheckedFunction { .. } => Err(TypeError::InvalidPartialApp {
+                fn_type: fn_type.clone(),
+                span,
+            }
+            .into()),
+
+            t => panic!("AnalyzableFn partial application on a non-function type: {:?}", t),
+        }
+    }
+
+    fn resolve_fn_call(
+        &mut self,
+        fn_call: &FnCall,
+        call_tmp: TmpId,
+        span: Span,
+    ) -> Result<AbstractType, AnalysisError> {
+        let fn_value = fn_call.fn_value();
+        let arg_types = fn_call
+            .args()
+            .map(|args| self.tmp_types(args))
+            .unwrap_or_default();
+
+        let fn_value_type =
+            self.typing_context.tmp_type_map.get(&fn_value).expect("Missing TMP");
+
+        // Generic function called without explicit type arguments
+        if requires_type_args(fn_value_type) {
+            let fn_value_type = fn_value_type.clone();
+            let (inst_type, dictionaries) =
+                self.infer_fn_type(&fn_value_type, &arg_types, span.clone())?;
+            self.typing_context.tmp_type_map.insert(fn_value, inst_type);
+            self.insert_dictionaries(call_tmp, dictionaries);
+        } else if let Some(dictionaries) = self.pending_dictionaries.remove(&fn_value) {
+            self.insert_dictionaries(call_tmp, dictionaries);
+        }
+
+        let fn_value_type =
+            self.typing_context.tmp_type_map.get(&fn_value).expect("Missing TMP");
+
+        self.check_call(fn_value_type, &arg_types, span)
+    }
+
+    ///
+    /// Generic functions must be instantiated explicitly (`f(type int)(_)`) before they are
+    ///   partially applied. Their trait dictionaries are bound with the arguments.
+    ///
+    fn resolve_partial_app(
+        &mut self,
+        partial: &PartialApp,
+        partial_tmp: TmpId,
+        span: Span,
+    ) -> Result<AbstractType, AnalysisError> {
+        let fn_value = partial.fn_value();
+        let arg_types = partial
+            .args()
+            .iter()
+            .map(|arg| {
+                arg.as_ref().map(|tmp_id| {
+                    self.typing_context
+                        .tmp_type_map
+                        .get(tmp_id.data())
+                        .expect("Missing TMP")
+                        .clone()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let fn_value_type =
+            self.typing_context.tmp_type_map.get(&fn_value).expect("Missing TMP");
+
+        if requires_type_args(fn_value_type) {
+            return Err(TypeError::InvalidPartialApp {
+                fn_type: fn_value_type.clone(),
+                span,
+            }
+            .into());
+        }
+
+        let fn_value_type = fn_value_type.clone();
+        if let Some(dictionaries) = self.pending_dictionaries.remove(&fn_value) {
+            self.insert_dictionaries(partial_tmp, dictionaries);
+        }
+
+        self.check_partial_app(&fn_value_type, &arg_types, span)
+    }
+
+    ///
+    /// Methods are found by the receiver's type.
+    ///   The receiver is passed as the first argument of the method.
+    ///
+    /// Receivers typed by a bounded type parameter dispatch through the trait dictionary of
+    ///   the first bound providing the method.
+    ///
+    fn resolve_method_call(
+        &mut self,
+        call: &MethodCall,
+        call_tmp: TmpId,
+        span: Span,
+    ) -> Result<AbstractType, AnalysisError> {
+        let mut receiver_type = self.typing_context
+            .tmp_type_map
+            .get(call.receiver.data())
+            .expect("Missing TMP")
+            .clone();
+
+        // Methods take the receiver by value. References to it are followed like field access.
+        let mut derefs = 0;
+        loop {
+            receiver_type = match *receiver_type.expanded() {
+                AbstractType::Ref { ref inner, .. } => (**inner).clone(),
+                _ => break,
+            };
+            derefs += 1;
+        }
+
+        if derefs > 0 {
+            self.typing_context.receiver_derefs.insert(call_tmp, derefs);
+        }
+
+        let mut arg_types = vec![receiver_type.clone()];
+        arg_types.extend(self.tmp_types(&call.args));
+
+        if let AbstractType::TypeVar(_, type_var) = *receiver_type.expanded() {
+            if let Some((index, method_type_cons)) = self.bound_method(type_var, call.method.data()) {
+                let fn_type = AbstractType::App2 {
+                    data: span.clone(),
+                    type_cons: Box::new(method_type_cons.clone()),
+                    args: vec![receiver_type.clone()],
+                }
+                .substitute(self.universe, self.current(), &self.typing_context)?;
+
+                let return_type = self.check_call(&fn_type, &arg_types, span)?;
+
+                self.typing_context.method_calls.insert(
+                    call_tmp,
+                    MethodDispatch::Dictionary {
+                        index,
+                        method: call.method.data().clone(),
+                    },
+                );
+
+                return Ok(return_type);
+            }
+
+            if let Some(trait_id) = self.enclosing_bound(type_var, |trait_id| {
+                self.universe.get_trait_cons(trait_id).methods.contains_key(call.method.data())
+            }) {
+                return Err(TypeError::TraitBoundInAnonymousFn {
+                    trait_name: self.universe.get_trait_cons(trait_id).name.clone(),
+                    type_arg: receiver_type,
+                    span,
+                }
+                .into());
+            }
+        }
+
+        let type_id = match *receiver_type
+            .substitute(self.universe, self.current(), &self.typing_context)?
+            .expanded()
+        {
+            AbstractType::Record { type_id, .. } => Some(type_id),
+            AbstractType::Opaque { type_id, .. } => Some(type_id),
+            _ => None,
+        };
+
+        let fn_id = match type_id
+            .and_then(|type_id| self.metadata.method(type_id, call.method.data()))
+        {
+            Some(fn_id) => fn_id,
+            None => {
+                return Err(TypeError::UnknownMethod {
+                    name: call.method.data().clone(),
+                    receiver: receiver_type,
+                    span,
+                }
+                .into());
+            }
+        };
+
+        let fn_type = self.fn_value_type(fn_id, span.clone())?;
+        let fn_type = if requires_type_args(&fn_type) {
+            let (fn_type, dictionaries) =
+                self.infer_fn_type(&fn_type, &arg_types, span.clone())?;
+            self.insert_dictionaries(call_tmp, dictionaries);
+            fn_type
+        } else {
+            fn_type
+        };
+
+        let return_type = self.check_call(&fn_type, &arg_types, span)?;
+
+        self.typing_context
+            .method_calls
+            .insert(call_tmp, MethodDispatch::Static(fn_id));
+
+        Ok(return_type)
+    }
+
+    ///
+    /// Finds the first trait bound on `type_var` declaring the method `name`.
+    ///
+    fn bound_method(&self, type_var: TypeVarId, name: &ast::Ident) -> Option<(usize, &TypeCons)> {
+        self.trait_bounds
+            .iter()
+            .enumerate()
+            .filter(|(_, (bound_var, _))| *bound_var == type_var)
+            .filter_map(|(index, (_, trait_id))| {
+                self.universe
+                    .get_trait_cons(*trait_id)
+                    .methods
+                    .get(name)
+                    .map(|method| (index, method))
+            })
+            .next()
+    }
+
+    ///
+    /// Finds a trait bound of an enclosing function on `type_var`.
+    ///   Those bounds cannot be used inside an anonymous function.
+    ///
+    fn enclosing_bound<F>(&self, type_var: TypeVarId, pred: F) -> Option<TraitId>
+    where
+        F: Fn(TraitId) -> bool,
+    {
+        self.typing_context
+            .enclosing_trait_bounds
+            .iter()
+            .filter(|(bound_var, _)| *bound_var == type_var)
+            .map(|(_, trait_id)| *trait_id)
+            .find(|trait_id| pred(*trait_id))
+    }
+
+    fn insert_dictionaries(&mut self, call_tmp: TmpId, dictionaries: Vec<TraitDictionary>) {
+        if !dictionaries.is_empty() {
+            self.typing_context
+                .call_dictionaries
+                .insert(call_tmp, dictionaries);
+        }
+    }
+
+    ///
+    /// Builds a dictionary for each trait bound of a function instantiated with `type_args`.
+    ///
+    fn trait_dictionaries(
+        &self,
+        type_params: &TypeParams,
+        type_args: &[AbstractType],
+        span: Span,
+    ) -> Result<Vec<TraitDictionary>, AnalysisError> {
+        type_params
+            .bounds()
+            .iter()
+            .map(|(param_id, trait_id)| {
+                let position = type_params
+                    .position(*param_id)
+                    .expect("Bound on unknown type parameter");
+                self.trait_dictionary(*trait_id, &type_args[position], span.clone())
+            })
+            .collect()
+    }
+
+    ///
+    /// A bounded type parameter of the current function forwards its own dictionary.
+    ///   Any other type argument must have an implementation of the trait.
+    ///
+    fn trait_dictionary(
+        &self,
+        trait_id: TraitId,
+        type_arg: &AbstractType,
+        span: Span,
+    ) -> Result<TraitDictionary, AnalysisError> {
+        if let AbstractType::TypeVar(_, type_var) = *type_arg.expanded() {
+            let forwarded = self.trait_bounds
+                .iter()
+                .position(|bound| *bound == (type_var, trait_id));
+
+            if let Some(index) = forwarded {
+                return Ok(TraitDictionary::Param(index));
+            }
+
+            if self.enclosing_bound(type_var, |bound| bound == trait_id).is_some() {
+                return Err(TypeError::TraitBoundInAnonymousFn {
+                    trait_name: self.universe.get_trait_cons(trait_id).name.clone(),
+                    type_arg: type_arg.clone(),
+                    span,
+                }
+                .into());
+            }
+        }
+
+        let type_id = match *type_arg
+            .substitute(self.universe, self.current(), &self.typing_context)?
+            .expanded()
+        {
+            AbstractType::Record { type_id, .. } => Some(type_id),
+            AbstractType::Opaque { type_id, .. } => Some(type_id),
+            _ => None,
+        };
+
+        match type_id.and_then(|type_id| self.metadata.trait_impl(trait_id, type_id)) {
+            Some(methods) => Ok(TraitDictionary::Impl(methods.clone())),
+
+            None => Err(TypeError::UnsatisfiedTraitBound {
+                trait_name: self.universe.get_trait_cons(trait_id).name.clone(),
+                type_arg: type_arg.clone(),
+                span,
+            }
+            .into()),
+        }
+    }
+
+    fn tmp_types(&self, tmps: &[Typed<TmpId>]) -> Vec<AbstractType> {
+        tmps.iter()
+            .map(|tmp_id| {
+                self.typing_context
+                    .tmp_type_map
+                    .get(tmp_id.data())
+                    .expect("Missing TMP")
+                    .clone()
+            })
+            .collect()
+    }
+
+    ///
+    /// Instantiates a generic function type by unifying its parameter types with the argument
+    ///   types of the call. Width constraints are checked on instantiation and trait bounds
+    ///   are resolved to dictionaries.
+    ///
+    fn infer_fn_type(
+        &self,
+        fn_type: &AbstractType,
+        arg_types: &[AbstractType],
+        span: Span,
+    ) -> Result<(AbstractType, Vec<TraitDictionary>), AnalysisError> {
+        let type_cons = match fn_type {
+            AbstractType::App2 { ref type_cons, .. } => type_cons,
+            _ => unreachable!(),
+        };
+
+        let (type_params, parameters) = match **type_cons {
+            TypeCons::Function {
+                ref type_params,
+                ref parameters,
+                ..
+            } => (type_params, parameters),
+
+            _ => unreachable!(),
+        };
+
+        if arg_types.len() != parameters.len() {
+            return Err(TypeError::Arity {
+                fn_type: fn_type.clone(),
+                found_args: arg_types.len(),
+                expected_param: parameters.len(),
+                span,
+            }
+            .into());
+        }
+
+        let mut inferred = HashMap::new();
+        for (param_type, arg_type) in parameters.iter().zip(arg_types.iter()) {
+            self.infer_type_args(param_type, arg_type, &mut inferred, span.clone())?;
+        }
+
+        let type_args = type_params
+            .iter()
+            .map(|(param_id, _)| {
+                inferred
+                    .get(&type_params.placeholder_type_var(param_id))
+                    .cloned()
+                    .ok_or_else(|| {
+                        TypeError::UnresolvedTypeParameter {
+                            ident: type_params.name(param_id).clone(),
+                            span: span.clone(),
+                        }
+                        .into()
+                    })
+            })
+            .collect::<Result<Vec<_>, AnalysisError>>()?;
+
+        let dictionaries = self.trait_dictionaries(type_params, &type_args, span.clone())?;
+
+        let inst_type = AbstractType::App2 {
+            data: span.clone(),
+            type_cons: type_cons.clone(),
+            args: type_args,
+        }
+        .substitute(self.universe, self.current(), &self.typing_context)?;
+
+        Ok((inst_type, dictionaries))
+    }
+
+    ///
+    /// Binds the type variables in `pattern` to the matching parts of `actual`.
+    ///
+    /// The first binding of a type variable is kept. Every later occurrence must resolve to
+    ///   that binding, otherwise the conflict is a type error. Mismatched structure is ignored.
+    ///
+    fn infer_type_args(
+        &self,
+        pattern: &AbstractType,
+        actual: &AbstractType,
+        inferred: &mut HashMap<TypeVarId, AbstractType>,
+        span: Span,
+    ) -> Result<(), AnalysisError> {
+        let actual = actual.substitute(self.universe, self.current(), &self.typing_context)?;
+
+        match (pattern.expanded(), actual.expanded()) {
+            (AbstractType::TypeVar(_, type_var), _) => match inferred.get(type_var) {
+                Some(bound) => type_resolver::resolve_types_static(
+                    self.universe,
+                    self.current(),
+                    &self.typing_context,
+                    &actual,
+                    bound,
+                    span,
+                )?,
+
+                None => {
+                    inferred.insert(*type_var, actual);
+                }
+            },
+
+            (
+                AbstractType::Array {
+                    element_type: ref pattern_element,
+                    ..
+                },
+                AbstractType::Array {
+                    element_type: ref actual_element,
+                    ..
+                },
+            ) => self.infer_type_args(pattern_element, actual_element, inferred, span.clone())?,
+
+            (
+                AbstractType::Ref {
+                    inner: ref pattern_inner,
+                    ..
+                },
+                AbstractType::Ref {
+                    inner: ref actual_inner,
+                    ..
+                },
+            ) => self.infer_type_args(pattern_inner, actual_inner, inferred, span.clone())?,
+
+            (
+                AbstractType::Tuple {
+                    elements: ref pattern_elements,
+                    ..
+                },
+                AbstractType::Tuple {
+                    elements: ref actual_elements,
+                    ..
+                },
+            ) => {
+                for (p, a) in pattern_elements.iter().zip(actual_elements.iter()) {
+                    self.infer_type_args(p, a, inferred, span.clone())?;
+                }
+            }
+
+            (
+                AbstractType::Function {
+                    parameters: ref pattern_params,
+                    return_type: ref pattern_return,
+                    ..
+                },
+                AbstractType::Function {
+                    parameters: ref actual_params,
+                    return_type: ref actual_return,
+                    ..
+                },
+            ) => {
+                for (p, a) in pattern_params.iter().zip(actual_params.iter()) {
+                    self.infer_type_args(p, a, inferred, span.clone())?;
+                }
+                self.infer_type_args(pattern_return, actual_return, inferred, span.clone())?;
+            }
 
-        let fn_type = AbstractType::App2 {
-            data: access_span,
-            type_cons: Box::new(fn_type_cons),
-            args: Vec::new(),
+            (
+                AbstractType::App {
+                    ref type_cons,
+                    ref args,
+                    ..
+                },
+                _,
+            ) => {
+                let type_cons = self.universe.get_type_cons(*type_cons);
+                self.infer_app_args(type_cons, args, &actual, inferred, span.clone())?;
+            }
+
+            (
+                AbstractType::App2 {
+                    ref type_cons,
+                    ref args,
+                    ..
+                },
+                _,
+            ) => self.infer_app_args(type_cons, args, &actual, inferred, span.clone())?,
+
+            _ => (),
         }
-        .substitute(
-            self.universe,
-            self.current(),
-            &self.typing_context)?;
 
-        Ok(fn_type)
+        Ok(())
     }
 
-    fn resolve_fn_call(
+    ///
+    /// Infers type variables in the arguments of an application of `type_cons` from `actual`
+    ///
+    fn infer_app_args(
         &self,
-        fn_call: &FnCall,
+        type_cons: &TypeCons,
+        pattern_args: &[AbstractType],
+        actual: &AbstractType,
+        inferred: &mut HashMap<TypeVarId, AbstractType>,
         span: Span,
-    ) -> Result<AbstractType, AnalysisError> {
-        let fn_value = fn_call.fn_value();
-        let fn_value_type =
-            self.typing_context.tmp_type_map.get(&fn_value).expect("Missing TMP");
-
-        // Check args and parameters align
-        match fn_value_type {
-            AbstractType::Function {
-                data: ref _fn_type_span,
-                parameters: ref params,
-                ref return_type,
-            } => {
-                let arg_types = fn_call.args().map(|ref vec| {
-                    vec.iter()
-                        .map(|ref tmp_id| {
-                            self.typing_context
-                                .tmp_type_map
-                                .get(tmp_id.data())
-                                .expect("Missing TMP")
-                                .clone()
-                        })
-                        .collect::<Vec<_>>()
-                });
-
-                match arg_types {
-                    Some(arg_types) => {
-                        if params.len() != arg_types.len() {
-                            return Err(TypeError::Arity {
-                                fn_type: fn_value_type.clone(),
-                                found_args: arg_types.len(),
-                                expected_param: params.len(),
-                                span: span,
-                            }
-                            .into());
-                        }
-
-                        let fn_param_types = params.iter();
-
-                        for (_index, (arg_type, param_type)) in
-                            arg_types.iter().zip(fn_param_types).enumerate()
-                        {
-                            let _arg_type: &AbstractType = arg_type;
-                            let _param_type: &AbstractType = param_type;
-                            // TODO: Check if types can resolve
-                            /*
-                            if !resolve_types(&arg_type, &param_type) {
-                                return Err(TypeError::ArgMismatch {
-                                    fn_type: fn_value_type.clone(),
-                                    index: index,
-                                    arg: arg_type.clone(),
-                                    param: param_type.clone(),
-                                    span: span,
-                                }
-                                .into());
-                            }
-                            */
-                        }
+    ) -> Result<(), AnalysisError> {
+        match (type_cons, actual.expanded()) {
+            (
+                TypeCons::Opaque { type_id, .. },
+                AbstractType::Opaque {
+                    type_id: actual_id,
+                    ref args,
+                    ..
+                },
+            ) if type_id == actual_id => {
+                for (p, a) in pattern_args.iter().zip(args.iter()) {
+                    self.infer_type_args(p, a, inferred, span.clone())?;
+                }
+            }
 
-                        Ok(*(return_type.clone()))
+            (
+                TypeCons::Record {
+                    type_id,
+                    ref type_params,
+                    ref fields,
+                    ..
+                },
+                AbstractType::Record {
+                    type_id: actual_id,
+                    ref abstract_field_map,
+                    ..
+                },
+            ) if type_id == actual_id => {
+                // Records do not keep their type arguments. Recover them from the fields.
+                let mut record_inferred = HashMap::new();
+                for (field_id, field_type) in fields.iter() {
+                    if let Some(actual_field) = abstract_field_map.fields.get(field_id) {
+                        self.infer_type_args(field_type, actual_field, &mut record_inferred, span.clone())?;
                     }
+                }
 
-                    None => {
-                        if params.len() != 0 {
-                            Err(TypeError::Arity {
-                                fn_type: fn_value_type.clone(),
-                                found_args: 0,
-                                expected_param: params.len(),
-                                span: span,
-                            }
-                            .into())
-                        } else {
-                            Ok(*(return_type.clone()))
-                        }
+                for ((param_id, _), p) in type_params.iter().zip(pattern_args.iter()) {
+                    let placeholder = type_params.placeholder_type_var(param_id);
+                    if let Some(a) = record_inferred.get(&placeholder) {
+                        self.infer_type_args(p, a, inferred, span.clone())?;
                     }
                 }
             }
 
-            AbstractType::UncheckedFunction { return_type, .. } => {
-                Ok(*(return_type.clone()))
+            (
+                TypeCons::Alias {
+                    ref type_params,
+                    ref aliased,
+                    ..
+                },
+                _,
+            ) => {
+                let alias_args = type_params
+                    .iter()
+                    .zip(pattern_args.iter())
+                    .map(|((param_id, _), p)| (type_params.placeholder_type_var(param_id), p.clone()))
+                    .collect();
+                let expanded = aliased.substitute_with(
+                    self.universe,
+                    self.current(),
+                    &self.typing_context,
+                    &alias_args,
+                )?;
+
+                self.infer_type_args(&expanded, actual, inferred, span.clone())?;
             }
 
-            t @ _ => panic!("AnalyzableFn call on a non-function type: {:?}", t),
+            _ => (),
         }
+
+        Ok(())
     }
 
     fn resolve_array_init(
@@ -973,10 +1795,14 @@ impl<'a> TypeChecker<'a> {
                     })?;
                 }
 
+                // The element type of an empty literal comes from where it is used
+                let element_type = expected_element_type
+                    .unwrap_or_else(|| AbstractType::Any(span.clone()));
+
                 let array_type = AbstractType::Array {
                     data: span,
-                    element_type: Box::new(expected_element_type.unwrap().clone()),
-                    size: size,
+                    element_type: Box::new(element_type),
+                    size: Some(size),
                 };
 
                 Ok(array_type)
@@ -989,7 +1815,7 @@ impl<'a> TypeChecker<'a> {
                 let array_type = AbstractType::Array {
                     data: span,
                     element_type: Box::new(element_type.clone()),
-                    size: size,
+                    size: Some(size),
                 };
 
                 // TODO: Insert array type into metadata?
@@ -1016,7 +1842,7 @@ impl<'a> TypeChecker<'a> {
                 .expect("Missing TMP");
 
             // TODO: Already applied?
-            match &tmp_type {
+            match tmp_type.expanded() {
                 AbstractType::Array {
                     ref element_type, ..
                 } => {
@@ -1045,7 +1871,7 @@ impl<'a> TypeChecker<'a> {
                 .expect("Missing TMP");
 
             // TODO: Already applied?
-            match &tmp_type {
+            match tmp_type.expanded() {
                 AbstractType::Int(_) => (),
 
                 _ => {
@@ -1061,9 +1887,222 @@ impl<'a> TypeChecker<'a> {
         Ok(expected_element_type)
     }
 
-    fn resolve_type_inst(
+    /// Slicing any array produces a growable array of the same element type
+    fn resolve_slice(
+        &self,
+        slice: &Slice,
+        span: Span,
+    ) -> Result<AbstractType, AnalysisError> {
+        let array_type = self.typing_context
+            .tmp_type_map
+            .get(slice.array.data())
+            .expect("Missing TMP");
+
+        let element_type = match array_type.expanded() {
+            AbstractType::Array {
+                ref element_type, ..
+            } => {
+                element_type
+                    .substitute(
+                        self.universe,
+                        self.current(),
+                        &self.typing_context)?
+            }
+
+            _ => {
+                return Err(TypeError::NotAnArray {
+                    found: array_type.clone(),
+                    span: span,
+                }
+                .into());
+            }
+        };
+
+        for bound in [&slice.start, &slice.end].iter() {
+            let bound_type = self.typing_context
+                .tmp_type_map
+                .get(bound.data())
+                .expect("Missing TMP");
+
+            match bound_type.expanded() {
+                AbstractType::Int(_) => (),
+
+                _ => {
+                    return Err(TypeError::InvalidIndex {
+                        found: bound_type.clone(),
+                        span: span,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        Ok(AbstractType::Array {
+            data: span,
+            element_type: Box::new(element_type),
+            size: None,
+        })
+    }
+
+    ///
+    /// Interpolated ints, floats, bools and Strings are converted directly. Any other value is
+    ///   converted by calling its `to_string` method, which must return a String.
+    ///
+    fn resolve_stringify(
+        &mut self,
+        value: &Typed<TmpId>,
+        stringify_tmp: TmpId,
+        span: Span,
+    ) -> Result<AbstractType, AnalysisError> {
+        let value_type = self.typing_context
+            .tmp_type_map
+            .get(value.data())
+            .expect("Missing TMP")
+            .substitute(self.universe, self.current(), &self.typing_context)?;
+
+        match *value_type.expanded() {
+            AbstractType::Int(_)
+            | AbstractType::Float(_)
+            | AbstractType::Bool(_)
+            | AbstractType::String(_) => return Ok(AbstractType::String(span)),
+
+            _ => (),
+        }
+
+        let not_formattable = TypeError::NotFormattable {
+            found: value_type,
+            span: span.clone(),
+        };
+
+        let call = MethodCall {
+            receiver: value.clone(),
+            method: ast::AstNode::new(ident!["to_string"], span.clone()),
+            args: Vec::new(),
+        };
+
+        let return_type = self
+            .resolve_method_call(&call, stringify_tmp, span.clone())
+            .map_err(|e| match e {
+                AnalysisError::TypeError(TypeError::UnknownMethod { .. }) => {
+                    not_formattable.clone().into()
+                }
+
+                e => e,
+            })?
+            .substitute(self.universe, self.current(), &self.typing_context)?;
+
+        match *return_type.expanded() {
+            AbstractType::String(_) => Ok(AbstractType::String(span)),
+            _ => Err(not_formattable.into()),
+        }
+    }
+
+    ///
+    /// Arrays iterate over their elements. Opaque types iterate through the 'len' and
+    ///   'get_value' functions of the module declaring the type.
+    ///
+    fn resolve_iter(
         &self,
+        iter: &IterOp,
+        span: Span,
+    ) -> Result<(AbstractType, IterProtocol), AnalysisError> {
+        let iterable = match iter {
+            IterOp::Len(ref iterable) => iterable,
+            IterOp::Get { ref iterable, .. } => iterable,
+        };
+
+        let iterable_type = self.typing_context
+            .tmp_type_map
+            .get(iterable.data())
+            .expect("Missing TMP")
+            .substitute(self.universe, self.current(), &self.typing_context)?;
+
+        match *iterable_type.expanded() {
+            AbstractType::Array {
+                ref element_type,
+                size,
+                ..
+            } => {
+                let protocol = IterProtocol::Array(size);
+                match iter {
+                    IterOp::Len(..) => Ok((AbstractType::Int(span), protocol)),
+                    IterOp::Get { .. } => {
+                        let element_type = element_type.substitute(
+                            self.universe,
+                            self.current(),
+                            &self.typing_context,
+                        )?;
+                        Ok((element_type, protocol))
+                    }
+                }
+            }
+
+            AbstractType::Opaque {
+                type_id,
+                ref args,
+                ..
+            } => {
+                let protocol = self.metadata
+                    .opaque_module(type_id)
+                    .and_then(|mod_id| {
+                        let len = self.metadata.module_fn(mod_id, ident!["len"])?;
+                        let get_value =
+                            self.metadata.module_fn(mod_id, ident!["get_value"])?;
+                        Some(IterProtocol::Opaque { len, get_value })
+                    })
+                    .ok_or(TypeError::NotIterable {
+                        found: iterable_type.clone(),
+                        span: span.clone(),
+                    })?;
+
+                let protocol_fn = match (iter, &protocol) {
+                    (IterOp::Len(..), IterProtocol::Opaque { len, .. }) => *len,
+                    (IterOp::Get { .. }, IterProtocol::Opaque { get_value, .. }) => *get_value,
+                    _ => unreachable!(),
+                };
+
+                let fn_type_cons = self.universe
+                    .get_fn_type_cons(protocol_fn)
+                    .expect("Missing function type constructor")
+                    .clone();
+
+                let fn_type = AbstractType::App2 {
+                    data: span.clone(),
+                    type_cons: Box::new(fn_type_cons),
+                    args: args.clone(),
+                }
+                .substitute(self.universe, self.current(), &self.typing_context)?;
+
+                match fn_type.expanded() {
+                    AbstractType::Function { return_type, .. } => {
+                        let return_type = return_type.substitute(
+                            self.universe,
+                            self.current(),
+                            &self.typing_context,
+                        )?;
+                        Ok((return_type, protocol))
+                    }
+
+                    _ => Err(TypeError::NotIterable {
+                        found: iterable_type.clone(),
+                        span,
+                    }
+                    .into()),
+                }
+            }
+
+            _ => Err(TypeError::NotIterable {
+                found: iterable_type.clone(),
+                span,
+            }
+            .into()),
+        }
+    }
+
+    fn resolve_type_inst(
+        &mut self,
         type_inst: &TypeInst,
+        inst_tmp: TmpId,
         inst_span: Span,
     ) -> Result<AbstractType, AnalysisError> {
         let fn_id = type_inst
@@ -1081,6 +2120,16 @@ impl<'a> TypeChecker<'a> {
             .map(|ann| type_from_ann(self.current(), &self.typing_context, ann))
             .collect::<Result<Vec<_>, _>>()?;
 
+        if let TypeCons::Function { ref type_params, .. } = fn_type_cons {
+            let dictionaries =
+                self.trait_dictionaries(type_params, &type_args, inst_span.clone())?;
+
+            // Passed on to the call of the instantiated function
+            if !dictionaries.is_empty() {
+                self.pending_dictionaries.insert(inst_tmp, dictionaries);
+            }
+        }
+
         let inst_type = AbstractType::App2 {
             data: inst_span,
             type_cons: Box::new(fn_type_cons),
@@ -1111,7 +2160,7 @@ impl<'a> TypeChecker<'a> {
             Box<dyn Fn(&crate::ast::Ident) -> Result<AbstractType, AnalysisError>>,
             AnalysisError,
         > {
-            match current_type.substitute(universe, scope, context)? {
+            match current_type.substitute(universe, scope, context)?.expanded().clone() {
                 AbstractType::WidthConstraint {
                     data: width_span,
                     width: awc,
@@ -1164,6 +2213,42 @@ impl<'a> TypeChecker<'a> {
                     }))
                 }
 
+                // Tuple elements are fields named by their position
+                AbstractType::Tuple { data, elements } => {
+                    Ok(Box::new(move |name| {
+                        name.as_str()
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|index| elements.get(index))
+                            .cloned()
+                            .ok_or(
+                                TypeError::UnknownField {
+                                    name: name.clone(),
+                                    struct_type: AbstractType::Tuple {
+                                        data: data.clone(),
+                                        elements: elements.clone(),
+                                    },
+                                    span: span.clone(),
+                                }
+                                .into(),
+                            )
+                    }))
+                }
+
+                // Field access automatically dereferences
+                AbstractType::Ref { inner, .. } => {
+                    generate_field_retriever(
+                        universe,
+                        scope,
+                        context,
+                        *inner,
+                        index,
+                        field_access,
+                        root_var_type,
+                        span,
+                    )
+                }
+
                 AbstractType::TypeVar(ref span, ref type_var) => {
                     let type_var_value = context
                         .get_type_var(*type_var)
@@ -1197,15 +2282,26 @@ impl<'a> TypeChecker<'a> {
             }
         }
 
+        /// Indexing automatically dereferences
+        fn deref(t: AbstractType) -> AbstractType {
+            match t.expanded().clone() {
+                AbstractType::Ref { inner, .. } => deref(*inner),
+                t => t,
+            }
+        }
+
         let path = field_access.path();
         let path_iter = path.path().iter();
 
-        let root_var_id = path.root_var_id();
-        let root_var_type = self.typing_context
-            .var_type_map
-            .get(&root_var_id)
-            .expect("Missing VAR")
-            .clone();
+        let root_var_type = match path.root() {
+            PathRoot::Var(root_var_id) => self.typing_context
+                .var_type_map
+                .get(&root_var_id)
+                .expect("Missing VAR")
+                .clone(),
+
+            PathRoot::Static(static_id) => self.universe.get_static_type(static_id).clone(),
+        };
 
         let mut current_type: AbstractType = root_var_type.clone();
 
@@ -1213,8 +2309,9 @@ impl<'a> TypeChecker<'a> {
             let indexing_type =
                 self.resolve_expr(expr)?;
 
-            match indexing_type
-                .substitute(self.universe, self.current(), &self.typing_context)? {
+            match *indexing_type
+                .substitute(self.universe, self.current(), &self.typing_context)?
+                .expanded() {
                 AbstractType::Int(_) => (),
                 _ => {
                     return Err(TypeError::InvalidIndex {
@@ -1225,8 +2322,8 @@ impl<'a> TypeChecker<'a> {
                 }
             }
 
-            match current_type
-                .substitute(self.universe, self.current(), &self.typing_context)? {
+            match deref(current_type
+                .substitute(self.universe, self.current(), &self.typing_context)?) {
                 AbstractType::Array { element_type, .. } => {
                     current_type = *(element_type.clone());
                 }
@@ -1267,7 +2364,7 @@ impl<'a> TypeChecker<'a> {
                         self.resolve_expr(indexing)?;
 
                     // TODO: Application?
-                    match indexing_type {
+                    match *indexing_type.expanded() {
                         AbstractType::Int(_) => (),
 
                         _ => {
@@ -1280,7 +2377,7 @@ impl<'a> TypeChecker<'a> {
                     };
 
                     // TODO: Application?
-                    match field_type {
+                    match deref(field_type.clone()) {
                         AbstractType::Array {
                             data,
                             element_type,
@@ -1398,7 +2495,12 @@ impl<'a> Passenger<E> for TypeChecker<'a> {
     ) -> Result<(), E> {
         let var_decl = &decl.decl;
 
-        let expr_type = self.resolve_expr(var_decl.init_expr())?;
+        let expr_type = match var_decl.init_expr() {
+            Some(init) => self.resolve_expr(init)?,
+
+            // Typed by its first assignment
+            None => return Ok(()),
+        };
 
         let var_type = match var_decl.type_annotation() {
             Some(ann) => {
@@ -1430,6 +2532,22 @@ impl<'a> Passenger<E> for TypeChecker<'a> {
             &self.typing_context,
         )?;
 
+        if let Some(names) = var_decl.tuple_arity() {
+            let arity = match *var_type.expanded() {
+                AbstractType::Tuple { ref elements, .. } => Some(elements.len()),
+                _ => None,
+            };
+
+            if arity != Some(names) {
+                return Err(TypeError::TupleDestructureArity {
+                    names,
+                    found_type: var_type,
+                    span: decl.span.clone(),
+                }
+                .into());
+            }
+        }
+
         self.typing_context
             .var_type_map
             .insert(var_decl.var_id(), var_type);
@@ -1446,11 +2564,44 @@ impl<'a> Passenger<E> for TypeChecker<'a> {
 
         let value_type = self.resolve_expr(assignment.value())?;
 
+        // Locals declared without a value (the result of an 'if' or block expression) take the
+        //   type of the first branch assigning them. Later branches must be compatible with it.
+        if let PathRoot::Var(var_id) = assignment.assignee().path().root() {
+            if !self.typing_context.var_type_map.contains_key(&var_id) {
+                let value_type = value_type.substitute(
+                    self.universe,
+                    self.current(),
+                    &self.typing_context,
+                )?;
+                self.typing_context.var_type_map.insert(var_id, value_type);
+
+                return Ok(());
+            }
+        }
+
         let assignee_type = self.resolve_field_access(
             assignment.assignee(),
             assignment.access_span(),
         )?;
 
+        let assignee_type = if assignment.is_deref() {
+            match assignee_type
+                .substitute(self.universe, self.current(), &self.typing_context)?
+                .expanded()
+                .clone() {
+                AbstractType::Ref { inner, .. } => *inner,
+                found => {
+                    return Err(TypeError::NotAReference {
+                        found,
+                        span: assignment.access_span(),
+                    }
+                    .into());
+                }
+            }
+        } else {
+            assignee_type
+        };
+
         resolve!(self, &value_type, &assignee_type, assign.span.clone())?;
 
         Ok(())
@@ -1536,12 +2687,49 @@ impl<'a> Passenger<E> for TypeChecker<'a> {
     }
 }
 
+///
+/// A generic function referenced without type arguments
+///
+fn requires_type_args(fn_type: &AbstractType) -> bool {
+    match fn_type {
+        AbstractType::App2 {
+            ref type_cons,
+            ref args,
+            ..
+        } => match **type_cons {
+            TypeCons::Function {
+                ref type_params, ..
+            } => args.is_empty() && type_params.len() > 0,
+            _ => false,
+        },
+
+        _ => false,
+    }
+}
+
+///
+/// How an iteration protocol operation (from a 'for' loop) is carried out
+///
+#[derive(Clone, Debug)]
+pub enum IterProtocol {
+    /// Array of the given size (None for growable arrays)
+    Array(Option<u64>),
+    /// Opaque type with 'len' and 'get_value' functions in its module
+    Opaque { len: FnId, get_value: FnId },
+}
+
 #[derive(Clone, Debug)]
 pub struct TypingContext {
     pub type_vars: HashMap<TypeVarId, AbstractType>,
     pub var_type_map: HashMap<VarId, AbstractType>,
     pub fn_type_map: HashMap<FnId, AbstractType>,
     pub tmp_type_map: HashMap<TmpId, AbstractType>,
+    pub iter_protocol: HashMap<TmpId, IterProtocol>,
+    pub method_calls: HashMap<TmpId, MethodDispatch>,
+    pub call_dictionaries: HashMap<TmpId, Vec<TraitDictionary>>,
+    pub struct_fills: HashMap<TmpId, Vec<(ast::Ident, FieldFill)>>,
+    pub receiver_derefs: HashMap<TmpId, usize>,
+    pub enclosing_trait_bounds: Vec<(TypeVarId, TraitId)>,
 }
 
 impl TypingContext {
@@ -1551,6 +2739,12 @@ impl TypingContext {
             var_type_map: HashMap::new(),
             fn_type_map: HashMap::new(),
             tmp_type_map: HashMap::new(),
+            iter_protocol: HashMap::new(),
+            method_calls: HashMap::new(),
+            call_dictionaries: HashMap::new(),
+            struct_fills: HashMap::new(),
+            receiver_derefs: HashMap::new(),
+            enclosing_trait_bounds: Vec::new(),
         }
     }
 
@@ -1563,4 +2757,83 @@ impl TypingContext {
             .get(&tmp_id)
             .expect("Missing type for tmp")
     }
+
+    pub fn iter_protocol(&self, tmp_id: TmpId) -> &IterProtocol {
+        self.iter_protocol
+            .get(&tmp_id)
+            .expect("Missing iteration protocol for tmp")
+    }
+
+    pub fn method_call(&self, tmp_id: TmpId) -> &MethodDispatch {
+        self.method_calls
+            .get(&tmp_id)
+            .expect("Missing method for tmp")
+    }
+
+    ///
+    /// Number of references followed to reach the receiver of a method call
+    ///
+    pub fn receiver_derefs(&self, tmp_id: TmpId) -> usize {
+        self.receiver_derefs.get(&tmp_id).cloned().unwrap_or(0)
+    }
+
+    ///
+    /// `to_string` method converting an interpolated value. `None` for primitives.
+    ///
+    pub fn stringify_method(&self, tmp_id: TmpId) -> Option<&MethodDispatch> {
+        self.method_calls.get(&tmp_id)
+    }
+
+    ///
+    /// Trait dictionaries passed after the arguments of a call
+    ///
+    pub fn call_dictionaries(&self, tmp_id: TmpId) -> &[TraitDictionary] {
+        self.call_dictionaries
+            .get(&tmp_id)
+            .map(|dictionaries| dictionaries.as_slice())
+            .unwrap_or(&[])
+    }
+
+    ///
+    /// Fields a struct init left out
+    ///
+    pub fn struct_fills(&self, tmp_id: TmpId) -> &[(ast::Ident, FieldFill)] {
+        self.struct_fills
+            .get(&tmp_id)
+            .map(|fills| fills.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum FieldFill {
+    /// Folded default of the field
+    Default(ast::Literal),
+
+    /// Copied from the `..base` struct in the given tmp
+    Base(TmpId),
+}
+
+#[derive(Clone, Debug)]
+pub enum MethodDispatch {
+    Static(FnId),
+
+    /// Method of the trait dictionary passed for the enclosing function's `index`-th bound
+    Dictionary { index: usize, method: ast::Ident },
+}
+
+#[derive(Clone, Debug)]
+pub enum TraitDictionary {
+    /// Methods of a trait implementation
+    Impl(HashMap<ast::Ident, FnId>),
+
+    /// Dictionary of the enclosing function's `index`-th bound
+    Param(usize),
+}
+
+///
+/// Name of the hidden parameter carrying the dictionary for the `index`-th trait bound
+///
+pub fn dictionary_param(index: usize) -> String {
+    format!("__trait_dict{}", index)
 }
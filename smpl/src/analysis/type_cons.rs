@@ -5,7 +5,7 @@ use crate::span::Span;
 
 use super::abstract_type::*;
 use super::error::{AnalysisError, ApplicationError, TypeError as ATypeError};
-use super::semantic_data::{FieldId, TypeId, TypeParamId, TypeVarId};
+use super::semantic_data::{FieldId, TraitId, TypeId, TypeParamId, TypeVarId};
 
 macro_rules! nill_check {
     ($type_args: expr) => {{
@@ -42,6 +42,13 @@ pub enum TypeCons {
         type_params: TypeParams,
     },
 
+    /// Applications expand to the aliased type
+    Alias {
+        name: Ident,
+        type_params: TypeParams,
+        aliased: AbstractType,
+    },
+
     Int,
     Float,
     String,
@@ -76,6 +83,10 @@ impl TypeCons {
                 ref type_params, ..
             } => Some(type_params),
 
+            TypeCons::Alias {
+                ref type_params, ..
+            } => Some(type_params),
+
             TypeCons::Int
             | TypeCons::Float
             | TypeCons::String
@@ -89,6 +100,8 @@ impl TypeCons {
 pub struct TypeParams {
     params: Vec<(TypeParamId, AbstractType)>,
     placeholder_variables: HashMap<TypeParamId, TypeVarId>,
+    names: HashMap<TypeParamId, Ident>,
+    bounds: Vec<(TypeParamId, TraitId)>,
 }
 
 impl TypeParams {
@@ -96,6 +109,8 @@ impl TypeParams {
         TypeParams {
             params: Vec::new(),
             placeholder_variables: HashMap::new(),
+            names: HashMap::new(),
+            bounds: Vec::new(),
         }
     }
 
@@ -106,6 +121,7 @@ impl TypeParams {
     pub fn add_param(
         &mut self,
         param: TypeParamId,
+        name: Ident,
         constraint: Option<AbstractWidthConstraint>,
         placeholder_var: TypeVarId,
         var_span: Span,
@@ -119,6 +135,7 @@ impl TypeParams {
 
         self.params.push((param, constraint));
         self.placeholder_variables.insert(param, placeholder_var);
+        self.names.insert(param, name);
     }
 
     pub fn len(&self) -> usize {
@@ -135,4 +152,34 @@ impl TypeParams {
     pub fn placeholder_type_var(&self, id: TypeParamId) -> TypeVarId {
         self.placeholder_variables.get(&id).unwrap().clone()
     }
+
+    pub fn name(&self, id: TypeParamId) -> &Ident {
+        self.names.get(&id).unwrap()
+    }
+
+    pub fn add_bound(&mut self, param: TypeParamId, trait_id: TraitId) {
+        self.bounds.push((param, trait_id));
+    }
+
+    ///
+    /// Trait bounds in the order their dictionaries are passed
+    ///
+    pub fn bounds(&self) -> &[(TypeParamId, TraitId)] {
+        &self.bounds
+    }
+
+    pub fn position(&self, id: TypeParamId) -> Option<usize> {
+        self.params.iter().position(|(param_id, _)| *param_id == id)
+    }
+}
+
+///
+/// Method signatures of a trait.
+///
+/// Each method is a function type constructor with a single type parameter, `Self`.
+///
+#[derive(Debug, Clone)]
+pub struct TraitCons {
+    pub name: Ident,
+    pub methods: HashMap<Ident, TypeCons>,
 }
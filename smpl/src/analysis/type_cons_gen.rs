@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 
 use crate::ast::{
-    AnonymousFn, BuiltinFnParams, BuiltinFunction, Function, Opaque, Struct,
-    TypeParams as AstTypeParams, WhereClause,
+    AnonymousFn, AstNode, BuiltinFnParams, BuiltinFunction, Function, Ident, Opaque, Struct,
+    TraitMethod, TypeAlias, TypeAnnotation, TypedPath, TypeParams as AstTypeParams, WhereClause,
+    TRAIT_SELF,
 };
 use crate::feature::*;
 use crate::span::Span;
@@ -11,7 +12,7 @@ use super::error::{AnalysisError, TypeError};
 use super::metadata::*;
 use super::resolve_scope::ScopedData;
 use super::semantic_data::{
-    FieldId, FnId, Program, TypeId, TypeParamId, TypeVarId,
+    FieldId, FnId, Program, TraitId, TypeId, TypeParamId, TypeVarId,
 };
 use super::type_checker::TypingContext;
 use super::type_cons::*;
@@ -38,6 +39,7 @@ pub fn generate_struct_type_cons(
             &scope,
             &typing_context,
         )?;
+    no_trait_bounds(&type_params, struct_def.name.span())?;
 
     // Generate the constructor
     let mut fields = HashMap::new();
@@ -346,13 +348,26 @@ fn type_param_map(
         }
     }
 
-    let mut finished: HashMap<TypeParamId, (Option<_>, _, Span)> = HashMap::new();
+    let mut finished: HashMap<TypeParamId, (Ident, Option<_>, _, Span)> = HashMap::new();
+    let mut trait_bounds = Vec::new();
     if let Some(where_clause) = where_clause {
         for (ident, vec_ast_type_ann) in where_clause.0.iter() {
             // Remove from type_parameter_map
             match internal_type_map.remove(ident.data()) {
                 Some((param_span, type_param_id, type_var_id)) => {
-                    if vec_ast_type_ann.len() > 1 {
+                    // Trait bounds are kept apart from the width constraint
+                    let (bounds, constraints): (Vec<_>, Vec<_>) = vec_ast_type_ann
+                        .iter()
+                        .map(|ann| (trait_bound(&current_scope, ann), ann))
+                        .partition(|(trait_id, _)| trait_id.is_some());
+
+                    trait_bounds.extend(
+                        bounds
+                            .into_iter()
+                            .map(|(trait_id, _)| (type_param_id, trait_id.unwrap())),
+                    );
+
+                    if constraints.len() > 1 {
                         // TODO: Allow multiple constraint declarations on one type param?
                         // where A: { ... }
                         //       A: { ... }
@@ -364,7 +379,22 @@ fn type_param_map(
                         );
                     }
 
-                    let ast_constraint = vec_ast_type_ann.get(0).unwrap();
+                    let ast_constraint = match constraints.get(0) {
+                        Some((_, ann)) => *ann,
+
+                        None => {
+                            current_scope
+                                .insert_type_var(ident.data().clone(), type_var_id);
+                            typing_context
+                                .type_vars
+                                .insert(type_var_id, AbstractType::Any(param_span.clone()));
+                            finished.insert(
+                                type_param_id,
+                                (ident.data().clone(), None, type_var_id, param_span),
+                            );
+                            continue;
+                        }
+                    };
                     let abstract_type = type_from_ann(
                         &current_scope,
                         &typing_context,
@@ -392,7 +422,7 @@ fn type_param_map(
 
                         finished.insert(
                             type_param_id.clone(),
-                            (Some(constraint), type_var_id.clone(), param_span),
+                            (ident.data().clone(), Some(constraint), type_var_id.clone(), param_span),
                         );
                     } else {
                         // TODO: found non-constraint in constraint position
@@ -422,19 +452,135 @@ fn type_param_map(
             .type_vars
             .insert(type_var_id.clone(), AbstractType::Any(param_span.clone()));
 
-        finished.insert(type_param_id.clone(), (None, type_var_id.clone(), param_span));
+        finished.insert(type_param_id.clone(), (ident, None, type_var_id.clone(), param_span));
     }
 
     let mut type_params = TypeParams::new();
     // NEED TO PRESERVE ORDER
-    for param_id in type_param_order {
-        let (opt, ty, param_span) = finished.remove(&param_id).unwrap();
-        type_params.add_param(param_id.clone(), opt, ty, param_span);
+    for param_id in type_param_order.iter() {
+        let (name, opt, ty, param_span) = finished.remove(param_id).unwrap();
+        type_params.add_param(param_id.clone(), name, opt, ty, param_span);
+    }
+
+    // Bounds are ordered by type parameter (where clauses are unordered)
+    trait_bounds.sort_by_key(|(param_id, _)| {
+        type_param_order.iter().position(|p| p == param_id)
+    });
+    for (param_id, trait_id) in trait_bounds {
+        type_params.add_bound(param_id, trait_id);
     }
 
     Ok((type_params, current_scope, typing_context))
 }
 
+///
+/// A where clause annotation naming a trait in scope
+///
+fn trait_bound(scope: &ScopedData, ann: &AstNode<TypeAnnotation>) -> Option<TraitId> {
+    match ann.data() {
+        TypeAnnotation::Path(TypedPath::NillArity(path)) => {
+            scope.trait_id(&path.clone().into())
+        }
+
+        _ => None,
+    }
+}
+
+fn no_trait_bounds(type_params: &TypeParams, span: Span) -> Result<(), AnalysisError> {
+    match type_params.bounds().first() {
+        Some((param_id, _)) => Err(TypeError::TraitBoundOnType {
+            ident: type_params.name(*param_id).clone(),
+            span,
+        }
+        .into()),
+
+        None => Ok(()),
+    }
+}
+
+///
+/// Trait method signatures are functions generic over `Self`, the implementing type.
+///
+pub fn generate_trait_method_type_cons(
+    universe: &AnalysisUniverse,
+    global_data: &mut GlobalData,
+    outer_scope: &ScopedData,
+    outer_context: &TypingContext,
+    method: &TraitMethod,
+) -> Result<TypeCons, AnalysisError> {
+    let self_param = AstTypeParams {
+        params: vec![AstNode::new(Ident(TRAIT_SELF.to_string()), method.name.span())],
+    };
+
+    let (type_params, type_param_scope, type_param_typing_context) =
+        type_param_map(
+            universe,
+            global_data,
+            Some(&self_param),
+            None,
+            outer_scope,
+            outer_context,
+        )?;
+
+    let return_type = match method.return_type {
+        Some(ref ann) => type_from_ann(
+            &type_param_scope,
+            &type_param_typing_context,
+            ann,
+        )?,
+
+        None => AbstractType::Unit(method.name.span()),
+    };
+
+    let parameters = method
+        .params
+        .iter()
+        .map(|param| {
+            type_from_ann(
+                &type_param_scope,
+                &type_param_typing_context,
+                &param.data().param_type,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(TypeCons::Function {
+        type_params,
+        parameters,
+        return_type,
+    })
+}
+
+pub fn generate_alias_type_cons(
+    universe: &AnalysisUniverse,
+    global_data: &mut GlobalData,
+    scope: &ScopedData,
+    typing_context: &TypingContext,
+    alias_def: &TypeAlias,
+) -> Result<TypeCons, AnalysisError> {
+    let (type_params, type_param_scope, type_param_typing_context) =
+        type_param_map(
+            universe,
+            global_data,
+            alias_def.type_params.as_ref(),
+            None,
+            scope,
+            typing_context,
+        )?;
+
+    let aliased = type_from_ann(
+        &type_param_scope,
+        &type_param_typing_context,
+        &alias_def.aliased,
+    )?;
+
+    Ok(TypeCons::Alias {
+        name: alias_def.name.data().clone(),
+        type_params,
+        aliased,
+    })
+}
+
 pub fn generate_opaque_type_cons(
     universe: &AnalysisUniverse,
     global_data: &mut GlobalData,
@@ -454,6 +600,7 @@ pub fn generate_opaque_type_cons(
             &scope,
             &typing_context,
         )?;
+    no_trait_bounds(&type_params, opaque_def.name.span())?;
 
     let type_cons = TypeCons::Opaque {
         type_id: type_id,
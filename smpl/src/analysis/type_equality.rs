@@ -18,7 +18,7 @@ pub fn equal_types_static(
 ) -> Result<(), AnalysisError> {
     use super::abstract_type::AbstractTypeX::*;
 
-    match (synthesis, constraint) {
+    match (synthesis.expanded(), constraint.expanded()) {
         (
             Record {
                 data: ref synth_span,
@@ -228,7 +228,60 @@ pub fn equal_types_static(
             )
         }
 
-        (synth_app @ App { .. }, constraint) => {
+        (
+            Ref {
+                data: ref synth_span,
+                inner: ref synth_inner,
+            },
+            Ref {
+                inner: ref constraint_inner,
+                ..
+            },
+        ) => equal_types_static(
+            universe,
+            scoped_data,
+            typing_context,
+            synth_inner,
+            constraint_inner,
+            synth_span.clone(),
+        ),
+
+        (
+            Tuple {
+                data: ref synth_span,
+                elements: ref synth_elements,
+            },
+            Tuple {
+                elements: ref constraint_elements,
+                ..
+            },
+        ) => {
+            if synth_elements.len() != constraint_elements.len() {
+                return Err(TypeError::UnexpectedType {
+                    found: synthesis.clone(),
+                    expected: constraint.clone(),
+                    span: span.clone(),
+                }
+                .into());
+            }
+
+            for (synth_element, constraint_element) in
+                synth_elements.iter().zip(constraint_elements.iter())
+            {
+                equal_types_static(
+                    universe,
+                    scoped_data,
+                    typing_context,
+                    synth_element,
+                    constraint_element,
+                    synth_span.clone(),
+                )?;
+            }
+
+            Ok(())
+        }
+
+        (synth_app @ App { .. }, _) => {
             let new_synthesis = synth_app
                 .substitute(universe, scoped_data, typing_context)
                 .unwrap();
@@ -243,7 +296,7 @@ pub fn equal_types_static(
             )
         }
 
-        (synthesis, constraint_app @ App { .. }) => {
+        (_, constraint_app @ App { .. }) => {
             let new_constraint = constraint_app
                 .substitute(universe, scoped_data, typing_context)
                 .unwrap();
@@ -279,7 +332,7 @@ pub fn equal_types_static(
             }
         }
 
-        (TypeVar(ref synth_span, synth_id), constraint) => {
+        (TypeVar(ref synth_span, synth_id), _) => {
             let synth_var_type = typing_context
                 .get_type_var(synth_id.clone())
                 .expect("Missing synth var");
@@ -302,7 +355,7 @@ pub fn equal_types_static(
             })
         }
 
-        (synthesis, TypeVar(ref constraint_span, constraint_id)) => {
+        (_, TypeVar(ref constraint_span, constraint_id)) => {
             let constraint_var_type = typing_context
                 .get_type_var(constraint_id.clone())
                 .expect("Missing synth var");
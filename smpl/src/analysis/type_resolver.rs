@@ -36,7 +36,8 @@ pub fn resolve_types_static(
 ) -> Result<(), AnalysisError> {
     use super::abstract_type::AbstractTypeX::*;
 
-    match (synthesis, constraint) {
+    // Aliases are compared by what they stand for but reported as written
+    match (synthesis.expanded(), constraint.expanded()) {
         (_, Any(_)) => Ok(()),
 
         (
@@ -265,8 +266,9 @@ pub fn resolve_types_static(
                 size: constraint_size,
             },
         ) => {
+            // Any array may be copied into a growable array
             // TODO: Allow synth to be larger?
-            if synth_size != constraint_size {
+            if constraint_size.is_some() && synth_size != constraint_size {
                 return Err(TypeError::UnexpectedType {
                     found: synthesis.clone(),
                     expected: constraint.clone(),
@@ -275,6 +277,11 @@ pub fn resolve_types_static(
                 .into());
             }
 
+            // Empty array literals take on the expected element type
+            if let Any(_) = *synth_element.expanded() {
+                return Ok(());
+            }
+
             resolve_types_static(
                 universe,
                 scoped_data,
@@ -285,7 +292,79 @@ pub fn resolve_types_static(
             )
         }
 
-        (synth_app @ App { .. }, constraint) => {
+        // A reference to a fixed-size array cannot be used as a reference to a growable array
+        (
+            Ref {
+                inner: ref synth_inner,
+                ..
+            },
+            Ref {
+                inner: ref constraint_inner,
+                ..
+            },
+        ) if differing_array_sizes(synth_inner, constraint_inner) => {
+            Err(TypeError::UnexpectedType {
+                found: synthesis.clone(),
+                expected: constraint.clone(),
+                span: span.clone(),
+            }
+            .into())
+        }
+
+        (
+            Ref {
+                data: ref synth_span,
+                inner: ref synth_inner,
+            },
+            Ref {
+                inner: ref constraint_inner,
+                ..
+            },
+        ) => resolve_types_static(
+            universe,
+            scoped_data,
+            typing_context,
+            synth_inner,
+            constraint_inner,
+            synth_span.clone(),
+        ),
+
+        (
+            Tuple {
+                data: ref synth_span,
+                elements: ref synth_elements,
+            },
+            Tuple {
+                elements: ref constraint_elements,
+                ..
+            },
+        ) => {
+            if synth_elements.len() != constraint_elements.len() {
+                return Err(TypeError::UnexpectedType {
+                    found: synthesis.clone(),
+                    expected: constraint.clone(),
+                    span: span.clone(),
+                }
+                .into());
+            }
+
+            for (synth_element, constraint_element) in
+                synth_elements.iter().zip(constraint_elements.iter())
+            {
+                resolve_types_static(
+                    universe,
+                    scoped_data,
+                    typing_context,
+                    synth_element,
+                    constraint_element,
+                    synth_span.clone(),
+                )?;
+            }
+
+            Ok(())
+        }
+
+        (synth_app @ App { .. }, _) => {
             let new_synthesis = synth_app
                 .substitute(universe, scoped_data, typing_context)
                 .unwrap();
@@ -300,7 +379,7 @@ pub fn resolve_types_static(
             )
         }
 
-        (synthesis, constraint_app @ App { .. }) => {
+        (_, constraint_app @ App { .. }) => {
             let new_constraint = constraint_app
                 .substitute(universe, scoped_data, typing_context)
                 .unwrap();
@@ -321,7 +400,7 @@ pub fn resolve_types_static(
         (String(_), String(_)) => Ok(()),
         (Unit(_), Unit(_)) => Ok(()),
 
-        (synthesis @ Opaque { .. }, constraint @ Opaque { .. }) => {
+        (Opaque { .. }, Opaque { .. }) => {
             super::type_equality::equal_types_static(
                 universe,
                 scoped_data,
@@ -347,7 +426,7 @@ pub fn resolve_types_static(
             }
         }
 
-        (TypeVar(ref synth_span, synth_id), constraint) => {
+        (TypeVar(ref synth_span, synth_id), _) => {
             let synth_var_type = typing_context
                 .get_type_var(synth_id.clone())
                 .expect("Missing synth var");
@@ -370,7 +449,7 @@ pub fn resolve_types_static(
             })
         }
 
-        (synthesis, TypeVar(ref constraint_span, constraint_id)) => {
+        (_, TypeVar(ref constraint_span, constraint_id)) => {
             let constraint_var_type = typing_context
                 .get_type_var(constraint_id.clone())
                 .expect("Missing synth var");
@@ -430,7 +509,7 @@ fn resolve_param_static(
 ) -> Result<(), AnalysisError> {
     use super::abstract_type::AbstractTypeX::*;
 
-    match (synth, constraint) {
+    match (synth.expanded(), constraint.expanded()) {
         (Any(_), Any(_)) => Ok(()),
 
         // If the synth is Any but a specific type is expected, reject
@@ -625,7 +704,7 @@ fn resolve_param_static(
             })
         }
 
-        (synth_app @ App { .. }, constraint) => {
+        (synth_app @ App { .. }, _) => {
             let new_synthesis = synth_app
                 .substitute(universe, scoped_data, typing_context)
                 .unwrap();
@@ -640,7 +719,7 @@ fn resolve_param_static(
             )
         }
 
-        (synthesis, constraint_app @ App { .. }) => {
+        (_, constraint_app @ App { .. }) => {
             let new_constraint = constraint_app
                 .substitute(universe, scoped_data, typing_context)
                 .unwrap();
@@ -649,7 +728,7 @@ fn resolve_param_static(
                 universe,
                 scoped_data,
                 typing_context,
-                synthesis,
+                synth,
                 &new_constraint,
                 span,
             )
@@ -665,3 +744,20 @@ fn resolve_param_static(
         ),
     }
 }
+
+fn differing_array_sizes(synth: &AbstractType, constraint: &AbstractType) -> bool {
+    match (synth.expanded(), constraint.expanded()) {
+        (
+            AbstractType::Array {
+                size: synth_size,
+                ..
+            },
+            AbstractType::Array {
+                size: constraint_size,
+                ..
+            },
+        ) => synth_size != constraint_size,
+
+        _ => false,
+    }
+}
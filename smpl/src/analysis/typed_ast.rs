@@ -46,6 +46,7 @@ pub struct Assignment {
     field_access: FieldAccess,
     access_span: Span,
     value: self::Expr,
+    deref: bool,
 }
 
 impl Assignment {
@@ -54,6 +55,7 @@ impl Assignment {
         local_data: &mut LocalData,
         assignment: ast::Assignment,
     ) -> (AnonStorage<ReservedAnonymousFn>, Self) {
+        let deref = assignment.deref;
         let (name, name_span) = assignment.name.to_data();
         let (mut anon_1, field_access) =
             FieldAccess::new(global_data, local_data, name);
@@ -68,6 +70,7 @@ impl Assignment {
             field_access: field_access,
             value,
             access_span: name_span,
+            deref,
         };
 
         (anon, a)
@@ -92,13 +95,19 @@ impl Assignment {
     pub fn value_mut(&mut self) -> &mut self::Expr {
         &mut self.value
     }
+
+    /// Writes through the reference stored in the assignee
+    pub fn is_deref(&self) -> bool {
+        self.deref
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct LocalVarDecl {
     type_ann: Option<ast::AstNode<ast::TypeAnnotation>>,
     var_name: ast::AstNode<ast::Ident>,
-    var_init: self::Expr,
+    var_init: Option<self::Expr>,
+    tuple_arity: Option<usize>,
     var_id: VarId,
     span: Span,
 }
@@ -112,13 +121,20 @@ impl LocalVarDecl {
         stmt_span: Span,
     ) -> (AnonStorage<ReservedAnonymousFn>, Self) {
 
-        let (anon, var_init) =
-            expr_flow::flatten(global_data, local_data, decl.var_init);
+        let (anon, var_init) = match decl.var_init {
+            Some(init) => {
+                let (anon, init) = expr_flow::flatten(global_data, local_data, init);
+                (anon, Some(init))
+            }
+
+            None => (AnonStorage::new(), None),
+        };
 
         let l = LocalVarDecl {
             type_ann: decl.var_type,
             var_name: decl.var_name,
             var_init,
+            tuple_arity: decl.tuple_arity,
             var_id: local_data.new_var_id(),
             span: stmt_span,
         };
@@ -142,12 +158,18 @@ impl LocalVarDecl {
         self.var_id
     }
 
-    pub fn init_expr(&self) -> &self::Expr {
-        &self.var_init
+    /// `None` for hidden locals assigned by each branch of an `if` or block expression
+    pub fn init_expr(&self) -> Option<&self::Expr> {
+        self.var_init.as_ref()
     }
 
-    pub fn init_expr_mut(&mut self) -> &mut self::Expr {
-        &mut self.var_init
+    pub fn init_expr_mut(&mut self) -> Option<&mut self::Expr> {
+        self.var_init.as_mut()
+    }
+
+    /// Number of names a destructuring `let` binds from the tuple in this local
+    pub fn tuple_arity(&self) -> Option<usize> {
+        self.tuple_arity
     }
 }
 
@@ -264,15 +286,25 @@ pub enum Value {
     Binding(self::Binding),
     FieldAccess(self::FieldAccess),
     FnCall(self::FnCall),
+    /// Function call with `_` placeholders. Produces a function over the placeholder parameters.
+    PartialApp(self::PartialApp),
+    MethodCall(self::MethodCall),
     BinExpr(ast::BinOp, Typed<TmpId>, Typed<TmpId>),
     UniExpr(ast::UniOp, Typed<TmpId>),
     StructInit(StructInit),
     AnonStructInit(AnonStructInit),
     ArrayInit(self::ArrayInit),
+    TupleInit(self::TupleInit),
     Indexing(Indexing),
+    Slice(self::Slice),
     ModAccess(self::ModAccess),
     AnonymousFn(self::AnonymousFnValue),
     TypeInst(self::TypeInst),
+    Iter(self::IterOp),
+    /// Concatenation of String tmps. Produced by interpolated strings.
+    Format(Vec<Typed<TmpId>>),
+    /// String form of an interpolated value
+    Stringify(Typed<TmpId>),
 }
 
 // Can currently only type instantiate on static functions
@@ -356,31 +388,70 @@ pub struct Indexing {
     pub indexer: Typed<TmpId>,
 }
 
+#[derive(Debug, Clone)]
+pub struct Slice {
+    pub array: Typed<TmpId>,
+    pub start: Typed<TmpId>,
+    pub end: Typed<TmpId>,
+}
+
+///
+/// Iteration protocol operations generated by `for` loops.
+///
+#[derive(Debug, Clone)]
+pub enum IterOp {
+    Len(Typed<TmpId>),
+    Get {
+        iterable: Typed<TmpId>,
+        index: Typed<TmpId>,
+    },
+}
+
 #[derive(Debug, Clone)]
 pub enum ArrayInit {
     List(Vec<Typed<TmpId>>),
     Value(Typed<TmpId>, u64),
 }
 
+#[derive(Debug, Clone)]
+pub struct MethodCall {
+    pub receiver: Typed<TmpId>,
+    pub method: AstNode<ast::Ident>,
+    pub args: Vec<Typed<TmpId>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TupleInit {
+    pub elements: Vec<Typed<TmpId>>,
+}
+
 #[derive(Debug, Clone)]
 pub struct StructInit {
     struct_type_name: ast::TypedPath,
     field_init: Vec<(ast::Ident, Typed<TmpId>)>,
     mapped_field_init: Option<Vec<(FieldId, Typed<TmpId>)>>,
+    base: Option<Typed<TmpId>>,
 }
 
 impl StructInit {
     pub fn new(
         struct_type_name: ast::TypedPath,
         field_init: Vec<(ast::Ident, Typed<TmpId>)>,
+        base: Option<Typed<TmpId>>,
     ) -> StructInit {
         StructInit {
             struct_type_name: struct_type_name,
             field_init: field_init,
             mapped_field_init: None,
+            base: base,
         }
     }
 
+    /// `..base` struct the fields left out are copied from
+    pub fn base(&self) -> Option<&Typed<TmpId>> {
+        self.base.as_ref()
+    }
+
     pub fn type_name(&self) -> &ast::ModulePath {
         self.struct_type_name.module_path()
     }
@@ -529,14 +600,44 @@ impl FnCall {
     }
 }
 
+///
+/// `None` arguments are the placeholders left for the produced function.
+///
+#[derive(Debug, Clone)]
+pub struct PartialApp {
+    fn_value: TmpId,
+    args: Vec<Option<Typed<TmpId>>>,
+}
+
+impl PartialApp {
+    pub fn new(fn_value: TmpId, args: Vec<Option<Typed<TmpId>>>) -> PartialApp {
+        PartialApp { fn_value, args }
+    }
+
+    pub fn fn_value(&self) -> TmpId {
+        self.fn_value
+    }
+
+    pub fn args(&self) -> &[Option<Typed<TmpId>>] {
+        &self.args
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Path {
     root_name: ast::AstNode<ast::Ident>,
     root_indexing: Option<Expr>,
-    root_var: Option<Typed<VarId>>,
+    root: Option<PathRoot>,
     path: Vec<self::PathSegment>,
 }
 
+/// Storage a path starts from
+#[derive(Debug, Clone, Copy)]
+pub enum PathRoot {
+    Var(VarId),
+    Static(StaticId),
+}
+
 impl self::Path {
     fn new(global_data: &mut GlobalData, local_data: &mut LocalData, path: ast::Path)
         -> (AnonStorage<ReservedAnonymousFn>, self::Path) {
@@ -575,7 +676,7 @@ impl self::Path {
         let path = self::Path {
             root_name: name,
             root_indexing: indexing,
-            root_var: None,
+            root: None,
             path: path,
         };
 
@@ -594,19 +695,16 @@ impl self::Path {
         self.root_indexing.as_ref()
     }
 
-    pub fn root_var_id(&self) -> VarId {
-        match self.root_var {
-            Some(ref typed_var_id) => *typed_var_id.data(),
-            None => panic!("No root var"),
-        }
+    pub fn root(&self) -> PathRoot {
+        self.root.expect("No path root")
     }
 
-    pub fn set_root_var(&mut self, id: VarId) {
-        if self.root_var.is_some() {
-            panic!("Attempting to overwrite root VarId");
+    pub fn set_root(&mut self, root: PathRoot) {
+        if self.root.is_some() {
+            panic!("Attempting to overwrite path root");
         }
 
-        self.root_var = Some(Typed::untyped(id));
+        self.root = Some(root);
     }
 
     pub fn path(&self) -> &[self::PathSegment] {
@@ -64,8 +64,11 @@ where
     }
 }
 
+/// Nested module names (`mod a::b;`) are joined by '::'
+///
+/// The last field holds the annotations of the `mod` declaration.
 #[derive(Clone)]
-pub struct Module(pub Option<AstNode<Ident>>, pub Vec<DeclStmt>);
+pub struct Module(pub Option<AstNode<Ident>>, pub Vec<DeclStmt>, pub Vec<Annotation>);
 
 impl Module {
     pub fn name(&self) -> Option<&Ident> {
@@ -83,10 +86,125 @@ pub enum DeclStmt {
     Struct(AstNode<Struct>),
     Function(AstNode<Function>),
     BuiltinFunction(AstNode<BuiltinFunction>),
+    Impl(AstNode<Impl>),
+    Trait(AstNode<Trait>),
+    Const(AstNode<Global>),
+    Static(AstNode<Global>),
+    TypeAlias(AstNode<TypeAlias>),
+}
+
+///
+/// `impl(type T) Name(type T) { ... }` or `impl Trait for Name { ... }`
+///
+/// Every method takes `self` as its first parameter, typed as `target`.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Impl {
+    pub target: AstNode<TypeAnnotation>,
+    pub trait_name: Option<AstNode<ModulePath>>,
+    pub type_params: Option<TypeParams>,
+    pub where_clause: Option<WhereClause>,
+    pub methods: Vec<Method>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct UseDecl(pub AstNode<Ident>);
+pub enum Method {
+    Function(AstNode<Function>),
+    Builtin(AstNode<BuiltinFunction>),
+}
+
+/// Name of the implementing type within trait method signatures
+pub const TRAIT_SELF: &str = "Self";
+
+///
+/// `type Name(type T) = <annotation>;`
+///
+/// Uses of the alias expand to the aliased type during type checking.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAlias {
+    pub name: AstNode<Ident>,
+    pub type_params: Option<TypeParams>,
+    pub aliased: AstNode<TypeAnnotation>,
+    pub public: bool,
+}
+
+///
+/// `const NAME: T = value;` or `static NAME: T = value;`
+///
+/// Constants are evaluated during analysis. Statics are module variables initialized
+///   at runtime.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Global {
+    pub name: AstNode<Ident>,
+    pub global_type: AstNode<TypeAnnotation>,
+    pub value: AstNode<Expr>,
+    /// Declared 'pub'. Private items are only reachable from their own module.
+    pub public: bool,
+}
+
+///
+/// `trait Name { fn method(self, ...) -> T; }`
+///
+/// Within method signatures, `Self` is the implementing type.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trait {
+    pub name: AstNode<Ident>,
+    pub methods: Vec<AstNode<TraitMethod>>,
+    /// Declared 'pub'. Private items are only reachable from their own module.
+    pub public: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraitMethod {
+    pub name: AstNode<Ident>,
+    pub params: Vec<AstNode<FnParameter>>,
+    pub return_type: Option<AstNode<TypeAnnotation>>,
+}
+
+///
+/// `use a::b;`, `use a::b as c;` or `use a::b::{x, y as z};`
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct UseDecl {
+    pub module: AstNode<ModulePath>,
+    pub import: Import,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Import {
+    /// Items are accessed through the alias, or the last segment of the module path
+    Module(Option<AstNode<Ident>>),
+    /// Only the listed items are brought into scope
+    Items(Vec<ImportItem>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportItem {
+    pub name: AstNode<Ident>,
+    pub alias: Option<AstNode<Ident>>,
+}
+
+impl UseDecl {
+    ///
+    /// Name the items of a module import are accessed through (`prefix::item`)
+    ///
+    pub fn prefix(&self) -> Option<&AstNode<Ident>> {
+        match self.import {
+            Import::Module(Some(ref alias)) => Some(alias),
+            Import::Module(None) => self.module.data().0.last(),
+            Import::Items(..) => None,
+        }
+    }
+}
+
+impl ImportItem {
+    pub fn bound_name(&self) -> &AstNode<Ident> {
+        self.alias.as_ref().unwrap_or(&self.name)
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct BuiltinFunction {
@@ -96,6 +214,8 @@ pub struct BuiltinFunction {
     pub annotations: Vec<Annotation>,
     pub type_params: Option<TypeParams>,
     pub where_clause: Option<WhereClause>,
+    /// Declared 'pub'. Private items are only reachable from their own module.
+    pub public: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -113,6 +233,8 @@ pub struct Function {
     pub annotations: Vec<Annotation>,
     pub type_params: Option<TypeParams>,
     pub where_clause: Option<WhereClause>,
+    /// Declared 'pub'. Private items are only reachable from their own module.
+    pub public: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -127,6 +249,8 @@ pub struct Opaque {
     pub annotations: Vec<Annotation>,
     pub type_params: Option<TypeParams>,
     pub where_clause: Option<WhereClause>,
+    /// Declared 'pub'. Private items are only reachable from their own module.
+    pub public: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -136,6 +260,8 @@ pub struct Struct {
     pub annotations: Vec<Annotation>,
     pub type_params: Option<TypeParams>,
     pub where_clause: Option<WhereClause>,
+    /// Declared 'pub'. Private items are only reachable from their own module.
+    pub public: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -148,6 +274,8 @@ pub struct StructBody(pub Option<Vec<StructField>>);
 pub struct StructField {
     pub name: AstNode<Ident>,
     pub field_type: AstNode<TypeAnnotation>,
+    /// Constant used when a struct init leaves the field out
+    pub default: Option<AstNode<Expr>>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -160,11 +288,17 @@ pub enum Stmt {
 pub enum ExprStmt {
     If(If),
     While(While),
+    Loop(Loop),
+    For(For),
     LocalVarDecl(LocalVarDecl),
+    LocalTupleDecl(LocalTupleDecl),
     Assignment(Assignment),
+    CompoundAssignment(CompoundAssignment),
     Return(Span, Option<Expr>),
-    Break(Span),
-    Continue(Span),
+    /// `break 'label value;` The label defaults to the innermost loop. Only `loop` expressions
+    ///   take a value.
+    Break(Span, Option<AstNode<Ident>>, Option<Expr>),
+    Continue(Span, Option<AstNode<Ident>>),
 }
 
 impl PartialEq for ExprStmt {
@@ -174,11 +308,17 @@ impl PartialEq for ExprStmt {
         match (self, other) {
             (&If(ref lhs), &If(ref rhs)) => lhs == rhs,
             (&While(ref lhs), &While(ref rhs)) => lhs == rhs,
+            (&Loop(ref lhs), &Loop(ref rhs)) => lhs == rhs,
+            (&For(ref lhs), &For(ref rhs)) => lhs == rhs,
             (&LocalVarDecl(ref lhs), &LocalVarDecl(ref rhs)) => lhs == rhs,
+            (&LocalTupleDecl(ref lhs), &LocalTupleDecl(ref rhs)) => lhs == rhs,
             (&Assignment(ref lhs), &Assignment(ref rhs)) => lhs == rhs,
+            (&CompoundAssignment(ref lhs), &CompoundAssignment(ref rhs)) => lhs == rhs,
             (&Return(_, ref lhs), &Return(_, ref rhs)) => lhs == rhs,
-            (&Break(..), &Break(..)) => true,
-            (&Continue(..), &Continue(..)) => true,
+            (&Break(_, ref lhs_label, ref lhs), &Break(_, ref rhs_label, ref rhs)) => {
+                lhs_label == rhs_label && lhs == rhs
+            }
+            (&Continue(_, ref lhs), &Continue(_, ref rhs)) => lhs == rhs,
 
             _ => false,
         }
@@ -194,18 +334,47 @@ pub struct AnonStructInit {
 pub struct StructInit {
     pub struct_name: TypedPath,
     pub field_init: Vec<(AstNode<Ident>, Box<Expr>)>,
+    /// `..base` struct the fields left out are copied from
+    pub base: Option<Box<Expr>>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Assignment {
     pub name: AstNode<Path>,
     pub value: Expr,
+    /// `*name = value` (write through the reference `name`)
+    pub deref: bool,
+}
+
+/// `name op= value` (i.e. `count += 1`). Lowered into an `Assignment` by the CFG.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompoundAssignment {
+    pub name: AstNode<Path>,
+    pub op: BinOp,
+    pub value: Expr,
+    /// `*name op= value` (write through the reference `name`)
+    pub deref: bool,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct LocalVarDecl {
     pub var_type: Option<AstNode<TypeAnnotation>>,
     pub var_name: AstNode<Ident>,
+    /// Only `None` for the hidden result of an `if` or block expression. The local takes the
+    ///   type of its first assignment.
+    pub var_init: Option<Expr>,
+    /// Number of names bound by a destructuring `let (a, b) = ...;`. The value must be a tuple
+    ///   with exactly this many elements.
+    pub tuple_arity: Option<usize>,
+}
+
+///
+/// `let (a, b) = expr;`
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalTupleDecl {
+    pub var_type: Option<AstNode<TypeAnnotation>>,
+    pub var_names: Vec<AstNode<Ident>>,
     pub var_init: Expr,
 }
 
@@ -221,12 +390,64 @@ pub struct Branch {
     pub block: AstNode<Block>,
 }
 
+///
+/// `if a { x } elif b { y } else { z }` as a value. The 'else' branch is required.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct IfExpr {
+    pub branches: Vec<ValueBranch>,
+    pub default_block: AstNode<BlockExpr>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValueBranch {
+    pub conditional: AstNode<Expr>,
+    pub block: AstNode<BlockExpr>,
+}
+
+///
+/// `{ stmt; ... value }`, a block ending with the value it produces.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockExpr {
+    pub stmts: Vec<Stmt>,
+    pub value: Box<AstNode<Expr>>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct While {
+    pub label: Option<AstNode<Ident>>,
     pub conditional: AstNode<Expr>,
     pub block: AstNode<Block>,
 }
 
+///
+/// `'label: loop { ... }`, only left by `break` or `return`.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Loop {
+    pub label: Option<AstNode<Ident>>,
+    pub block: AstNode<Block>,
+    /// Hidden local receiving the `break` values of a `loop` expression. Set by the CFG.
+    pub value: Option<Ident>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct For {
+    pub label: Option<AstNode<Ident>>,
+    pub var_name: AstNode<Ident>,
+    pub iterable: ForIterable,
+    pub block: AstNode<Block>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ForIterable {
+    /// `start..end`, excluding `end`
+    Range(AstNode<Expr>, AstNode<Expr>),
+    /// Arrays and opaque types following the iteration protocol
+    Collection(AstNode<Expr>),
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Expr {
     Bin(AstNode<BinExpr>),
@@ -235,13 +456,29 @@ pub enum Expr {
     Binding(AstNode<Ident>),
     FieldAccess(AstNode<Path>),
     FnCall(AstNode<FnCall>),
+    MethodCall(AstNode<MethodCall>),
     StructInit(AstNode<StructInit>),
     AnonStructInit(AstNode<AnonStructInit>),
     ArrayInit(AstNode<ArrayInit>),
+    TupleInit(AstNode<TupleInit>),
     Indexing(AstNode<Indexing>),
+    Slice(AstNode<Slice>),
     AnonymousFn(AstNode<AnonymousFn>),
     FnCallChain(AstNode<FnCallChain>),
     Path(AstNode<TypedPath>),
+    Iter(AstNode<IterOp>),
+    Format(AstNode<FormatString>),
+    /// `_` argument of a partial application. Only produced as a direct function call argument.
+    Placeholder(AstNode<()>),
+    /// Only produced as a `let` initializer, assigned, returned or `break` value. Lowered by the
+    ///   CFG.
+    If(AstNode<IfExpr>),
+    /// Only produced as a `let` initializer, assigned, returned or `break` value. Lowered by the
+    ///   CFG.
+    Block(AstNode<BlockExpr>),
+    /// Only produced as a `let` initializer, assigned, returned or `break` value. Lowered by the
+    ///   CFG.
+    Loop(AstNode<Loop>),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -251,18 +488,57 @@ pub struct AnonymousFn {
     pub body: AstNode<Block>,
 }
 
+///
+/// Iteration protocol operations on a collection. Only produced when desugaring `for` loops.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum IterOp {
+    /// Number of elements in the collection
+    Len(Box<Expr>),
+    /// Element of the collection at an index
+    Get(Box<Expr>, Box<Expr>),
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Indexing {
     pub array: Box<Expr>,
     pub indexer: Box<Expr>,
 }
 
+/// `array[start..end]`. Copies the elements into a new growable array.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Slice {
+    pub array: Box<Expr>,
+    pub start: Box<Expr>,
+    pub end: Box<Expr>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum ArrayInit {
     InitList(Vec<Expr>),
     Value(Box<Expr>, u64),
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct TupleInit {
+    pub elements: Vec<Expr>,
+}
+
+///
+/// Interpolated string literal (`"HP: {hp}"`). Values are converted to strings and
+///   concatenated with the surrounding text.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormatString {
+    pub parts: Vec<FormatPart>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormatPart {
+    Text(String),
+    Value(Expr),
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct FnCallChain {
     pub base: AstNode<FnCall>,
@@ -275,6 +551,26 @@ pub struct FnCall {
     pub args: Option<Vec<Expr>>,
 }
 
+impl FnCall {
+    /// Calls with a `_` argument build a new function from the remaining parameters.
+    pub fn is_partial(&self) -> bool {
+        self.args
+            .iter()
+            .flatten()
+            .any(|arg| matches!(arg, Expr::Placeholder(_)))
+    }
+}
+
+///
+/// `receiver.method(args)`
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MethodCall {
+    pub receiver: Box<Expr>,
+    pub method: AstNode<Ident>,
+    pub args: Option<Vec<Expr>>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct BinExpr {
     pub op: BinOp,
@@ -298,6 +594,12 @@ pub enum BinOp {
     Lesser,
     Eq,
     InEq,
+
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
 }
 
 impl std::fmt::Display for BinOp {
@@ -317,6 +619,12 @@ impl std::fmt::Display for BinOp {
             BinOp::Lesser => write!(f, "<"),
             BinOp::Eq => write!(f, "=="),
             BinOp::InEq => write!(f, "!="),
+
+            BinOp::BitAnd => write!(f, "&"),
+            BinOp::BitOr => write!(f, "|"),
+            BinOp::BitXor => write!(f, "^"),
+            BinOp::Shl => write!(f, "<<"),
+            BinOp::Shr => write!(f, ">>"),
         }
     }
 }
@@ -333,6 +641,7 @@ pub enum UniOp {
     Deref,
     Negate,
     LogicalInvert,
+    BitNot,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -449,7 +758,10 @@ impl<'a> From<TypedPathRef<'a>> for TypedPath {
 #[derive(Clone, Debug, PartialEq)]
 pub enum TypeAnnotation {
     Path(TypedPath),
-    Array(Box<AstNode<TypeAnnotation>>, u64),
+    /// `[T; N]` has a fixed size of N; `[T]` (no size) is a growable array
+    Array(Box<AstNode<TypeAnnotation>>, Option<u64>),
+    Ref(Box<AstNode<TypeAnnotation>>),
+    Tuple(Vec<AstNode<TypeAnnotation>>),
     FnType(
         Option<TypeParams>,
         Option<Vec<AstNode<TypeAnnotation>>>,
@@ -482,12 +794,8 @@ impl ModulePath {
 
 impl fmt::Display for ModulePath {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let buffer =
-            self.0.iter().fold(String::new(), |mut buffer, ref item| {
-                buffer.push_str(&item.data().0);
-                buffer
-            });
-        write!(f, "{}", buffer)
+        let segments: Vec<_> = self.0.iter().map(|item| item.data().as_str()).collect();
+        write!(f, "{}", segments.join("::"))
     }
 }
 
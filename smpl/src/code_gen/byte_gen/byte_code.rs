@@ -9,6 +9,8 @@ pub enum Instruction {
     StoreStructure(Location, HashMap<String, Arg>),
     StoreArray1(Location, Vec<Arg>),
     StoreArray2(Location, Arg, u64),
+    Len(Location, Arg), // Store the length of the array at Arg
+    Slice(Location, Arg, Arg, Arg), // Store a copy of the array Arg1 from index Arg2 up to Arg3
 
     AddI(Location, Arg, Arg),
     SubI(Location, Arg, Arg),
@@ -41,7 +43,21 @@ pub enum Instruction {
     Negate(Location, Arg),
     Invert(Location, Arg),
 
+    BitAnd(Location, Arg, Arg),
+    BitOr(Location, Arg, Arg),
+    BitXor(Location, Arg, Arg),
+    Shl(Location, Arg, Arg), // Shifting by 64 or more bits results in 0
+    Shr(Location, Arg, Arg), // Arithmetic shift. Shifting by 64 or more bits keeps only the sign
+    BitNot(Location, Arg),
+
+    Format(Location, Vec<Arg>), // Concatenate the string forms of ints, floats, bools and strings
+
+    TakeRef(Location, Location), // Store a reference to the second location
+    Deref(Location, Arg), // Store the value referenced by Arg
+    StoreDeref(Location, Arg), // Store Arg into the value referenced at Location
+
     FnCall(Location, Vec<Arg>), // Function to call, args
+    PartialApp(Location, Location, Vec<Option<Arg>>), // Store, function to apply, args (None for placeholders)
     Return(Option<Arg>),
     TakeReturn(Location), // Where to store return value
 
@@ -89,6 +105,16 @@ impl fmt::Display for Instruction {
                 size, location, element
             ),
 
+            Len(ref location, ref array) => {
+                write!(f, "len {}, {}", location, array)
+            }
+
+            Slice(ref location, ref array, ref start, ref end) => write!(
+                f,
+                "slice {}, {}, {}, {}",
+                location, array, start, end
+            ),
+
             AddI(ref location, ref arg1, ref arg2) => {
                 write!(f, "addi {}, {}, {}", location, arg1, arg2)
             }
@@ -185,6 +211,52 @@ impl fmt::Display for Instruction {
                 write!(f, "negate {}, {}", location, arg)
             }
 
+            BitAnd(ref location, ref arg1, ref arg2) => {
+                write!(f, "band {}, {}, {}", location, arg1, arg2)
+            }
+
+            BitOr(ref location, ref arg1, ref arg2) => {
+                write!(f, "bor {}, {}, {}", location, arg1, arg2)
+            }
+
+            BitXor(ref location, ref arg1, ref arg2) => {
+                write!(f, "bxor {}, {}, {}", location, arg1, arg2)
+            }
+
+            Shl(ref location, ref arg1, ref arg2) => {
+                write!(f, "shl {}, {}, {}", location, arg1, arg2)
+            }
+
+            Shr(ref location, ref arg1, ref arg2) => {
+                write!(f, "shr {}, {}, {}", location, arg1, arg2)
+            }
+
+            BitNot(ref location, ref arg) => {
+                write!(f, "bnot {}, {}", location, arg)
+            }
+
+            Format(ref location, ref args) => {
+                write!(f, "format {}", location)?;
+
+                for arg in args.iter() {
+                    write!(f, ", {}", arg)?;
+                }
+
+                Ok(())
+            }
+
+            TakeRef(ref location, ref referent) => {
+                write!(f, "ref {}, {}", location, referent)
+            }
+
+            Deref(ref location, ref arg) => {
+                write!(f, "deref {}, {}", location, arg)
+            }
+
+            StoreDeref(ref location, ref arg) => {
+                write!(f, "store_deref {}, {}", location, arg)
+            }
+
             FnCall(ref location, ref args) => {
                 write!(f, "call size={}, {} ", args.len(), location)?;
 
@@ -195,6 +267,19 @@ impl fmt::Display for Instruction {
                 Ok(())
             }
 
+            PartialApp(ref location, ref to_apply, ref args) => {
+                write!(f, "partial_app size={}, {}, {} ", args.len(), location, to_apply)?;
+
+                for arg in args.iter() {
+                    match *arg {
+                        Some(ref arg) => write!(f, ", {}", arg)?,
+                        None => write!(f, ", _")?,
+                    }
+                }
+
+                Ok(())
+            }
+
             Return(ref arg) => match *arg {
                 Some(ref to_return) => write!(f, "return {}", to_return),
 
@@ -318,4 +403,8 @@ pub enum Arg {
 
     #[display(fmt = "\"{}\"", _0)]
     String(String),
+
+    /// Placeholder for a local declared without a value
+    #[display(fmt = "()")]
+    Unit,
 }
@@ -11,13 +11,14 @@ pub fn translate_expr(
 
     let mut translated = Vec::new();
     for tmp in execution_order {
-        translated.extend(translate_tmp(expr.get_tmp(tmp), typing_context));
+        translated.extend(translate_tmp(expr, expr.get_tmp(tmp), typing_context));
     }
 
     translated
 }
 
 fn translate_tmp(
+    expr: &Expr,
     tmp: &Tmp,
     typing_context: &TypingContext,
 ) -> Vec<Instruction> {
@@ -51,6 +52,7 @@ fn translate_tmp(
             {
                 BindingId::Fn(id) => fn_id(id),
                 BindingId::Var(_) => var.ident().data().as_str().to_owned(),
+                BindingId::Static(id) => static_id(id),
             };
 
             Store(
@@ -63,51 +65,22 @@ fn translate_tmp(
             let mut instruction_buffer: Vec<Instruction> = Vec::new();
 
             let internal_path = access.path();
-            let root_var = internal_path.root_name().data().as_str().to_owned();
-            let root_indexing_expr = internal_path.root_indexing_expr();
 
-            if let Some(root_indexing_expr) = root_indexing_expr {
+            if let Some(root_indexing_expr) = internal_path.root_indexing_expr() {
                 instruction_buffer
                     .extend(translate_expr(root_indexing_expr, typing_context));
             }
 
-            let path: Vec<_> = internal_path
-                .path()
-                .iter()
-                .map(|path_segment| match path_segment {
-                    PathSegment::Ident(ref field) => {
-                        super::byte_code::FieldAccess::Field(
-                            field.name().to_string(),
-                        )
-                    }
-
-                    PathSegment::Indexing(ref field, ref index_expr) => {
-                        instruction_buffer
-                            .extend(translate_expr(index_expr, typing_context));
-                        super::byte_code::FieldAccess::FieldIndex {
-                            field: field.name().to_string(),
-                            index_tmp: tmp_id(index_expr.last()),
-                        }
-                    }
-                })
-                .collect();
-
-            // If assignment has field accesses or indexing, location is Location::Compound
-            let field_access_location =
-                if path.len() > 0 || root_indexing_expr.is_some() {
-                    Location::Compound {
-                        root: root_var,
-                        root_index: root_indexing_expr
-                            .map(|expr| tmp_id(expr.last())),
-                        path: path,
-                    }
-                } else {
-                    Location::Namespace(root_var)
-                };
+            for path_segment in internal_path.path().iter() {
+                if let PathSegment::Indexing(_, ref index_expr) = *path_segment {
+                    instruction_buffer
+                        .extend(translate_expr(index_expr, typing_context));
+                }
+            }
 
             let access_instr = Store(
                 Location::Tmp(store),
-                Arg::Location(field_access_location),
+                Arg::Location(field_access_location(access)),
             );
 
             instruction_buffer.push(access_instr);
@@ -128,7 +101,7 @@ fn translate_tmp(
                 };
             }
 
-            let ty = typing_context.tmp_type(*lhs.data());
+            let ty = typing_context.tmp_type(*lhs.data()).expanded();
             let lhs = Arg::Location(Location::Tmp(tmp_id(*lhs.data())));
             let rhs = Arg::Location(Location::Tmp(tmp_id(*rhs.data())));
             match op {
@@ -211,21 +184,33 @@ fn translate_tmp(
 
                 BinOp::Eq => Eq(Location::Tmp(store), lhs, rhs),
                 BinOp::InEq => InEq(Location::Tmp(store), lhs, rhs),
+
+                BinOp::BitAnd => BitAnd(Location::Tmp(store), lhs, rhs),
+                BinOp::BitOr => BitOr(Location::Tmp(store), lhs, rhs),
+                BinOp::BitXor => BitXor(Location::Tmp(store), lhs, rhs),
+                BinOp::Shl => Shl(Location::Tmp(store), lhs, rhs),
+                BinOp::Shr => Shr(Location::Tmp(store), lhs, rhs),
             }
         }
 
         Value::UniExpr(ref op, ref tmp) => {
-            let tmp = Arg::Location(Location::Tmp(tmp_id(*tmp.data())));
+            let operand = *tmp.data();
+            let tmp = Arg::Location(Location::Tmp(tmp_id(operand)));
             match op {
                 UniOp::Negate => Negate(Location::Tmp(store), tmp),
                 UniOp::LogicalInvert => Invert(Location::Tmp(store), tmp),
+                UniOp::BitNot => BitNot(Location::Tmp(store), tmp),
 
-                _ => unimplemented!(),
+                UniOp::Ref => TakeRef(
+                    Location::Tmp(store),
+                    referent_location(expr, operand),
+                ),
+                UniOp::Deref => Deref(Location::Tmp(store), tmp),
             }
         }
 
         Value::FnCall(ref fn_call) => {
-            let args = match fn_call.args() {
+            let mut args: Vec<_> = match fn_call.args() {
                 Some(args) => args
                     .iter()
                     .map(|tmp| {
@@ -237,10 +222,60 @@ fn translate_tmp(
 
             let to_call = Location::Tmp(tmp_id(fn_call.fn_value()));
 
+            let (mut instructions, dictionaries) = translate_dictionaries(id, typing_context);
+            args.extend(dictionaries);
+
             let fn_call = FnCall(to_call, args);
             let result_store = TakeReturn(Location::Tmp(store));
 
-            return vec![fn_call, result_store];
+            instructions.push(fn_call);
+            instructions.push(result_store);
+            return instructions;
+        }
+
+        // Trait dictionaries are bound after the arguments
+        Value::PartialApp(ref partial) => {
+            let mut args: Vec<_> = partial
+                .args()
+                .iter()
+                .map(|arg| {
+                    arg.as_ref()
+                        .map(|tmp| Arg::Location(Location::Tmp(tmp_id(*tmp.data()))))
+                })
+                .collect();
+
+            let to_apply = Location::Tmp(tmp_id(partial.fn_value()));
+
+            let (mut instructions, dictionaries) = translate_dictionaries(id, typing_context);
+            args.extend(dictionaries.into_iter().map(Some));
+
+            instructions.push(PartialApp(Location::Tmp(store), to_apply, args));
+            return instructions;
+        }
+
+        // Methods are called by their FnId with the receiver as the first argument
+        Value::MethodCall(ref call) => {
+            let to_call = method_location(typing_context.method_call(id));
+
+            let mut args: Vec<_> = std::iter::once(&call.receiver)
+                .chain(call.args.iter())
+                .map(|tmp| Arg::Location(Location::Tmp(tmp_id(*tmp.data()))))
+                .collect();
+
+            let (mut instructions, dictionaries) = translate_dictionaries(id, typing_context);
+            args.extend(dictionaries);
+
+            // References to the receiver are followed into the store, which is only written
+            //   again by the return value
+            for _ in 0..typing_context.receiver_derefs(id) {
+                let receiver = args[0].clone();
+                instructions.push(Deref(Location::Tmp(store.clone()), receiver));
+                args[0] = Arg::Location(Location::Tmp(store.clone()));
+            }
+
+            instructions.push(FnCall(to_call, args));
+            instructions.push(TakeReturn(Location::Tmp(store)));
+            return instructions;
         }
 
         Value::StructInit(ref struct_init) => {
@@ -255,6 +290,39 @@ fn translate_tmp(
                 );
             }
 
+            // Fields left out are defaults or copies of the base struct's fields
+            for (field, fill) in typing_context.struct_fills(id).iter() {
+                let arg = match *fill {
+                    FieldFill::Default(Literal::Int(int)) => Arg::Int(int),
+                    FieldFill::Default(Literal::Float(float)) => Arg::Float(float),
+                    FieldFill::Default(Literal::Bool(boolean)) => Arg::Bool(boolean),
+                    FieldFill::Default(Literal::String(ref string)) => Arg::String(string.to_string()),
+
+                    FieldFill::Base(base) => Arg::Location(Location::Compound {
+                        root: tmp_id(base),
+                        root_index: None,
+                        path: vec![super::byte_code::FieldAccess::Field(field.to_string())],
+                    }),
+                };
+
+                map.insert(field.to_string(), arg);
+            }
+
+            StoreStructure(Location::Tmp(store), map)
+        }
+
+        // Tuples are structures with fields named by position
+        Value::TupleInit(ref tuple_init) => {
+            let map = tuple_init
+                .elements
+                .iter()
+                .enumerate()
+                .map(|(index, typed_tmp)| {
+                    let tmp = tmp_id(*typed_tmp.data());
+                    (index.to_string(), Arg::Location(Location::Tmp(tmp)))
+                })
+                .collect();
+
             StoreStructure(Location::Tmp(store), map)
         }
 
@@ -294,6 +362,85 @@ fn translate_tmp(
             Store(Location::Tmp(store), Arg::Location(access_location))
         }
 
+        Value::Slice(ref slice) => {
+            let array = Arg::Location(Location::Tmp(tmp_id(*slice.array.data())));
+            let start = Arg::Location(Location::Tmp(tmp_id(*slice.start.data())));
+            let end = Arg::Location(Location::Tmp(tmp_id(*slice.end.data())));
+
+            Slice(Location::Tmp(store), array, start, end)
+        }
+
+        Value::Format(ref parts) => {
+            let parts = parts
+                .iter()
+                .map(|part| Arg::Location(Location::Tmp(tmp_id(*part.data()))))
+                .collect();
+
+            Format(Location::Tmp(store), parts)
+        }
+
+        Value::Stringify(ref value) => {
+            let value = Arg::Location(Location::Tmp(tmp_id(*value.data())));
+            match typing_context.stringify_method(id) {
+                Some(method) => {
+                    let (mut instructions, dictionaries) =
+                        translate_dictionaries(id, typing_context);
+                    let args = std::iter::once(value).chain(dictionaries).collect();
+
+                    instructions.push(FnCall(method_location(method), args));
+                    instructions.push(TakeReturn(Location::Tmp(store)));
+                    return instructions;
+                }
+
+                None => Format(Location::Tmp(store), vec![value]),
+            }
+        }
+
+        Value::Iter(ref iter) => {
+            let iterable = match iter {
+                IterOp::Len(ref iterable) => iterable,
+                IterOp::Get { ref iterable, .. } => iterable,
+            };
+            let iterable = tmp_id(*iterable.data());
+
+            match (iter, typing_context.iter_protocol(id)) {
+                (IterOp::Len(..), IterProtocol::Array(Some(size))) => {
+                    Store(Location::Tmp(store), Arg::Int(*size as i64))
+                }
+
+                (IterOp::Len(..), IterProtocol::Array(None)) => {
+                    Len(Location::Tmp(store), Arg::Location(Location::Tmp(iterable)))
+                }
+
+                (IterOp::Get { ref index, .. }, IterProtocol::Array(..)) => {
+                    let access_location = Location::Compound {
+                        root: iterable,
+                        root_index: Some(tmp_id(*index.data())),
+                        path: Vec::with_capacity(0),
+                    };
+
+                    Store(Location::Tmp(store), Arg::Location(access_location))
+                }
+
+                (IterOp::Len(..), IterProtocol::Opaque { len, .. }) => {
+                    let to_call = Location::Namespace(fn_id(*len));
+                    let args = vec![Arg::Location(Location::Tmp(iterable))];
+
+                    return vec![FnCall(to_call, args), TakeReturn(Location::Tmp(store))];
+                }
+
+                (IterOp::Get { ref index, .. }, IterProtocol::Opaque { get_value, .. }) => {
+                    let to_call = Location::Namespace(fn_id(*get_value));
+                    let args = vec![
+                        Arg::Location(Location::Tmp(iterable)),
+                        Arg::Location(Location::Tmp(tmp_id(*index.data()))),
+                    ];
+
+                    return vec![FnCall(to_call, args), TakeReturn(Location::Tmp(store))];
+                }
+            }
+        }
+
         Value::ModAccess(ref mod_access) => {
             let func = fn_id(mod_access.fn_id().unwrap());
             let location = Location::Namespace(func);
@@ -335,6 +482,134 @@ fn translate_tmp(
     vec![single]
 }
 
+fn method_location(dispatch: &MethodDispatch) -> Location {
+    match dispatch {
+        MethodDispatch::Static(method) => Location::Namespace(fn_id(*method)),
+
+        MethodDispatch::Dictionary { index, ref method } => Location::Compound {
+            root: dictionary_param(*index),
+            root_index: None,
+            path: vec![super::byte_code::FieldAccess::Field(method.to_string())],
+        },
+    }
+}
+
+///
+/// Location of the variable (or part of a variable) accessed by `access`
+///
+/// Index expressions are expected to already be evaluated into their tmps
+///
+pub fn field_access_location(access: &crate::analysis::FieldAccess) -> Location {
+    let internal_path = access.path();
+    let root_var = match internal_path.root() {
+        PathRoot::Var(_) => internal_path.root_name().data().as_str().to_owned(),
+        PathRoot::Static(id) => static_id(id),
+    };
+    let root_indexing_expr = internal_path.root_indexing_expr();
+
+    let path: Vec<_> = internal_path
+        .path()
+        .iter()
+        .map(|path_segment| match path_segment {
+            PathSegment::Ident(ref field) => {
+                super::byte_code::FieldAccess::Field(field.name().to_string())
+            }
+
+            PathSegment::Indexing(ref field, ref index_expr) => {
+                super::byte_code::FieldAccess::FieldIndex {
+                    field: field.name().to_string(),
+                    index_tmp: tmp_id(index_expr.last()),
+                }
+            }
+        })
+        .collect();
+
+    // If the access has field accesses or indexing, location is Location::Compound
+    if path.len() > 0 || root_indexing_expr.is_some() {
+        Location::Compound {
+            root: root_var,
+            root_index: root_indexing_expr.map(|expr| tmp_id(expr.last())),
+            path,
+        }
+    } else {
+        Location::Namespace(root_var)
+    }
+}
+
+///
+/// Location a reference to the tmp `operand` should point to
+///
+/// Referencing a variable (or one of its fields or elements) points to the variable's storage.
+/// Referencing any other value points to the tmp holding that value.
+///
+fn referent_location(expr: &Expr, operand: TmpId) -> Location {
+    let var_name = |tmp: TmpId| match *expr.get_tmp(tmp).value().data() {
+        Value::Binding(ref var) => match var.get_id() {
+            Some(BindingId::Var(_)) => Some(var.ident().data().as_str().to_owned()),
+            Some(BindingId::Static(id)) => Some(static_id(id)),
+            _ => None,
+        },
+
+        _ => None,
+    };
+
+    match *expr.get_tmp(operand).value().data() {
+        Value::Binding(..) => var_name(operand)
+            .map(Location::Namespace)
+            .unwrap_or(Location::Tmp(tmp_id(operand))),
+
+        Value::FieldAccess(ref access) => field_access_location(access),
+
+        Value::Indexing(ref indexing) => match var_name(*indexing.array.data()) {
+            Some(name) => Location::Compound {
+                root: name,
+                root_index: Some(tmp_id(*indexing.indexer.data())),
+                path: Vec::with_capacity(0),
+            },
+
+            None => Location::Tmp(tmp_id(operand)),
+        },
+
+        _ => Location::Tmp(tmp_id(operand)),
+    }
+}
+
+///
+/// Trait dictionaries are passed as structures mapping method names to functions.
+///   Returns the instructions building the dictionaries and the arguments passing them.
+///
+fn translate_dictionaries(
+    call: TmpId,
+    typing_context: &TypingContext,
+) -> (Vec<Instruction>, Vec<Arg>) {
+    let mut instructions = Vec::new();
+    let args = typing_context
+        .call_dictionaries(call)
+        .iter()
+        .enumerate()
+        .map(|(index, dictionary)| match *dictionary {
+            TraitDictionary::Impl(ref methods) => {
+                let store = Location::Tmp(format!("{}_dict{}", tmp_id(call), index));
+                let map = methods
+                    .iter()
+                    .map(|(name, method)| {
+                        (name.to_string(), Arg::Location(Location::Namespace(fn_id(*method))))
+                    })
+                    .collect();
+
+                instructions.push(Instruction::StoreStructure(store.clone(), map));
+                Arg::Location(store)
+            }
+
+            TraitDictionary::Param(index) => {
+                Arg::Location(Location::Namespace(dictionary_param(index)))
+            }
+        })
+        .collect();
+
+    (instructions, args)
+}
+
 pub fn tmp_id(id: TmpId) -> String {
     format!("_tmp{}", id.raw())
 }
@@ -342,3 +617,7 @@ pub fn tmp_id(id: TmpId) -> String {
 pub fn fn_id(id: FnId) -> String {
     format!("_fn{}", id.raw())
 }
+
+pub fn static_id(id: StaticId) -> String {
+    format!("_static{}", id.raw())
+}
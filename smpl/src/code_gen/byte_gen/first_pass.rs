@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use petgraph::graph::NodeIndex;
 
 use crate::analysis::*;
+use crate::span::Span;
 
 use super::byte_code::*;
 use super::byte_expr;
@@ -15,6 +16,7 @@ pub(super) enum PartialInstruction {
     Branch(BranchingId),
     Continue(LoopId),
     Break(LoopId),
+    Span(Span), // Marker, following instructions were generated from this span
 }
 
 impl From<Instruction> for PartialInstruction {
@@ -33,6 +35,7 @@ enum State {
 
 pub(super) struct LoopFrame {
     result_location: Option<Arg>,
+    condition_span: Option<Span>,
     condition: Option<Vec<PartialInstruction>>,
     body: Option<Vec<PartialInstruction>>,
 }
@@ -41,6 +44,7 @@ impl LoopFrame {
     fn new() -> LoopFrame {
         LoopFrame {
             result_location: None,
+            condition_span: None,
             condition: None,
             body: None,
         }
@@ -57,12 +61,19 @@ impl LoopFrame {
     fn set_condition<I: Iterator<Item = PartialInstruction>>(
         &mut self,
         condition: I,
+        span: Span,
     ) {
         if self.condition.is_some() {
             panic!("Attempting to double set the loop condition.");
         }
 
-        self.condition = Some(condition.collect());
+        self.condition = Some(
+            Some(PartialInstruction::Span(span.clone()))
+                .into_iter()
+                .chain(condition)
+                .collect(),
+        );
+        self.condition_span = Some(span);
     }
 
     fn set_body<I: Iterator<Item = PartialInstruction>>(&mut self, body: I) {
@@ -77,6 +88,10 @@ impl LoopFrame {
         self.condition.as_ref().expect("Condition none")
     }
 
+    pub(super) fn get_condition_span(&self) -> &Span {
+        self.condition_span.as_ref().expect("Condition span none")
+    }
+
     pub(super) fn get_body(&self) -> &[PartialInstruction] {
         self.body.as_ref().expect("Branch none")
     }
@@ -88,6 +103,7 @@ impl LoopFrame {
 
 pub(super) struct BranchFrame {
     result_location: Option<Arg>,
+    condition_span: Option<Span>,
     condition: Option<Vec<PartialInstruction>>,
     true_branch: Option<Vec<PartialInstruction>>,
     false_branch: Option<Vec<PartialInstruction>>,
@@ -97,6 +113,7 @@ impl BranchFrame {
     fn new() -> BranchFrame {
         BranchFrame {
             result_location: None,
+            condition_span: None,
             condition: None,
             true_branch: None,
             false_branch: None,
@@ -114,12 +131,19 @@ impl BranchFrame {
     fn set_condition<I: Iterator<Item = PartialInstruction>>(
         &mut self,
         condition: I,
+        span: Span,
     ) {
         if self.condition.is_some() {
             panic!("Attempting to double set the condition.");
         }
 
-        self.condition = Some(condition.collect());
+        self.condition = Some(
+            Some(PartialInstruction::Span(span.clone()))
+                .into_iter()
+                .chain(condition)
+                .collect(),
+        );
+        self.condition_span = Some(span);
     }
 
     fn set_true_branch<I: Iterator<Item = PartialInstruction>>(
@@ -148,6 +172,10 @@ impl BranchFrame {
         self.condition.as_ref().expect("Condition none")
     }
 
+    pub(super) fn get_condition_span(&self) -> &Span {
+        self.condition_span.as_ref().expect("Condition span none")
+    }
+
     pub(super) fn get_true_branch(&self) -> &[PartialInstruction] {
         self.true_branch.as_ref().expect("True branch none")
     }
@@ -322,7 +350,7 @@ impl<'a> Passenger<FirstPassError> for FirstPass<'a> {
                 .map(|instr| PartialInstruction::Instruction(instr));
 
         let frame = self.get_loop_frame_mut(ld.loop_id);
-        frame.set_condition(condition_instructions);
+        frame.set_condition(condition_instructions, condition.span.clone());
 
         let result_location = Arg::Location(Location::Tmp(byte_expr::tmp_id(
             condition.expr.last(),
@@ -361,6 +389,7 @@ impl<'a> Passenger<FirstPassError> for FirstPass<'a> {
         _id: NodeIndex,
         ld: &LoopData,
     ) -> Result<(), FirstPassError> {
+        self.push_to_current_frame(PartialInstruction::Span(ld.span.clone()));
         self.push_to_current_frame(PartialInstruction::Continue(ld.loop_id));
         Ok(())
     }
@@ -370,6 +399,7 @@ impl<'a> Passenger<FirstPassError> for FirstPass<'a> {
         _id: NodeIndex,
         ld: &LoopData,
     ) -> Result<(), FirstPassError> {
+        self.push_to_current_frame(PartialInstruction::Span(ld.span.clone()));
         self.push_to_current_frame(PartialInstruction::Break(ld.loop_id));
         Ok(())
     }
@@ -387,17 +417,24 @@ impl<'a> Passenger<FirstPassError> for FirstPass<'a> {
         _id: NodeIndex,
         decl: &LocalVarDeclData,
     ) -> Result<(), FirstPassError> {
-        let init_instructions = byte_expr::translate_expr(
-            decl.decl.init_expr(),
-            self.typing_context,
-        );
-        self.extend_current_frame(init_instructions.into_iter());
+        self.push_to_current_frame(PartialInstruction::Span(decl.span.clone()));
 
         let key = decl.decl.var_name().as_str().to_owned();
         let store_location = Location::Namespace(key);
-        let value = Arg::Location(Location::Tmp(byte_expr::tmp_id(
-            decl.decl.init_expr().last(),
-        )));
+
+        // Locals without an initializer (the result of an 'if' or block expression) still need
+        //   storage for the branches to assign into
+        let value = match decl.decl.init_expr() {
+            Some(init) => {
+                let init_instructions =
+                    byte_expr::translate_expr(init, self.typing_context);
+                self.extend_current_frame(init_instructions.into_iter());
+
+                Arg::Location(Location::Tmp(byte_expr::tmp_id(init.last())))
+            }
+
+            None => Arg::Unit,
+        };
         self.push_to_current_frame(Instruction::Store(store_location, value));
 
         Ok(())
@@ -408,6 +445,8 @@ impl<'a> Passenger<FirstPassError> for FirstPass<'a> {
         _id: NodeIndex,
         assign: &AssignmentData,
     ) -> Result<(), FirstPassError> {
+        self.push_to_current_frame(PartialInstruction::Span(assign.span.clone()));
+
         let assignment = &assign.assignment;
         let assignee = assignment.assignee();
 
@@ -429,55 +468,39 @@ impl<'a> Passenger<FirstPassError> for FirstPass<'a> {
             })
             .unwrap_or(Vec::new());
 
-        let assign_root_var =
-            internal_path.root_name().data().as_str().to_owned();
-        let assign_root_indexing_expr = internal_path
-            .root_indexing_expr()
-            .map(|index_expr| byte_expr::tmp_id(index_expr.last()));
-        let path: Vec<_> = internal_path
-            .path()
-            .iter()
-            .map(|path_segment| {
-                match path_segment {
-                    PathSegment::Ident(ref field) => {
-                        super::byte_code::FieldAccess::Field(
-                            field.name().to_string(),
-                        )
-                    }
-
-                    PathSegment::Indexing(ref field, ref index_expr) => {
-                        // Generate temporaries for indexing expr
-                        access_tmps.extend(byte_expr::translate_expr(
-                            index_expr,
-                            self.typing_context,
-                        ));
-
-                        super::byte_code::FieldAccess::FieldIndex {
-                            field: field.name().to_string(),
-                            index_tmp: byte_expr::tmp_id(index_expr.last()),
-                        }
-                    }
-                }
-            })
-            .collect();
-
-        // If assignment has field accesses or indexing, location is Location::Compound
-        let assign_location =
-            if path.len() > 0 || assign_root_indexing_expr.is_some() {
-                Location::Compound {
-                    root: assign_root_var,
-                    root_index: assign_root_indexing_expr,
-                    path: path,
-                }
-            } else {
-                Location::Namespace(assign_root_var)
-            };
+        // Generate temporaries for indexing exprs
+        for path_segment in internal_path.path().iter() {
+            if let PathSegment::Indexing(_, ref index_expr) = *path_segment {
+                access_tmps.extend(byte_expr::translate_expr(
+                    index_expr,
+                    self.typing_context,
+                ));
+            }
+        }
+
+        // Assigning to a variable writes to its existing storage so that references to the
+        // variable observe the new value
+        let assign_location = match byte_expr::field_access_location(assignee) {
+            Location::Namespace(root) => Location::Compound {
+                root,
+                root_index: None,
+                path: Vec::new(),
+            },
+
+            location => location,
+        };
+
+        let store = if assignment.is_deref() {
+            Instruction::StoreDeref(assign_location, value)
+        } else {
+            Instruction::Store(assign_location, value)
+        };
 
         // Emit access expressions before evaluating the assignment
         // Emit storage instruction last
         self.extend_current_frame(value_tmps.into_iter());
         self.extend_current_frame(access_tmps.into_iter());
-        self.push_to_current_frame(Instruction::Store(assign_location, value));
+        self.push_to_current_frame(store);
         Ok(())
     }
 
@@ -486,6 +509,8 @@ impl<'a> Passenger<FirstPassError> for FirstPass<'a> {
         _id: NodeIndex,
         expr: &ExprData,
     ) -> Result<(), FirstPassError> {
+        self.push_to_current_frame(PartialInstruction::Span(expr.span.clone()));
+
         let expr_instructions =
             byte_expr::translate_expr(&expr.expr, self.typing_context);
         self.extend_current_frame(expr_instructions.into_iter());
@@ -497,6 +522,8 @@ impl<'a> Passenger<FirstPassError> for FirstPass<'a> {
         _id: NodeIndex,
         rdata: &ReturnData,
     ) -> Result<(), FirstPassError> {
+        self.push_to_current_frame(PartialInstruction::Span(rdata.span.clone()));
+
         if let Some(ref return_expr) = rdata.expr {
             // Return expression
 
@@ -551,7 +578,7 @@ impl<'a> Passenger<FirstPassError> for FirstPass<'a> {
                 .map(|instr| PartialInstruction::Instruction(instr));
 
         let frame = self.get_branch_frame_mut(b.branch_id);
-        frame.set_condition(condition_instructions);
+        frame.set_condition(condition_instructions, condition.span.clone());
         let result_location = Arg::Location(Location::Tmp(byte_expr::tmp_id(
             condition.expr.last(),
         )));
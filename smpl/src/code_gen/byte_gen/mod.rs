@@ -6,6 +6,7 @@ mod byte_code;
 mod byte_expr;
 mod first_pass;
 mod second_pass;
+mod source_map;
 mod third_pass;
 
 use std::fmt;
@@ -18,12 +19,16 @@ pub use byte_code::{
 };
 
 pub use byte_expr::fn_id as to_fn_id;
+pub use byte_expr::static_id as to_static_id;
+
+pub use source_map::SourceMap;
 
 use crate::analysis::Traverser;
 
 #[derive(Debug, Clone)]
 pub struct ByteCodeFunction {
     instructions: Vec<Instruction>,
+    source_map: SourceMap,
     validated_flag: bool,
 }
 
@@ -33,6 +38,7 @@ impl ByteCodeFunction {
     ) -> ByteCodeFunction {
         ByteCodeFunction {
             instructions: instructions,
+            source_map: SourceMap::new(),
             validated_flag: false,
         }
     }
@@ -42,10 +48,16 @@ impl ByteCodeFunction {
     ) -> ByteCodeFunction {
         ByteCodeFunction {
             instructions: instructions,
+            source_map: SourceMap::new(),
             validated_flag: true,
         }
     }
 
+    fn with_source_map(mut self, source_map: SourceMap) -> ByteCodeFunction {
+        self.source_map = source_map;
+        self
+    }
+
     pub fn validate(mut self) -> ByteCodeFunction {
         self.validated_flag = true;
         self
@@ -58,6 +70,10 @@ impl ByteCodeFunction {
     pub fn instructions(&self) -> &[Instruction] {
         &self.instructions
     }
+
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
 }
 
 impl fmt::Display for ByteCodeFunction {
@@ -92,7 +108,10 @@ pub fn compile_to_byte_code(function: &CompilableFn) -> ByteCodeFunction {
 
     // Takes the data from the second pass and resolves break/continue statements
     //   to jump to the correct index
+    // Span markers are stripped and collected into a SourceMap
     let third_pass = third_pass::ThirdPass::new(second_pass.pass());
+    let (instructions, source_map) = third_pass.pass();
 
-    ByteCodeFunction::new_pre_validated(third_pass.pass())
+    ByteCodeFunction::new_pre_validated(instructions)
+        .with_source_map(source_map)
 }
@@ -4,6 +4,7 @@ use super::byte_code::*;
 use super::first_pass::PartialInstruction as PartialInstructionFP;
 use super::first_pass::*;
 use crate::analysis::*;
+use crate::span::Span;
 
 #[derive(Debug, Clone)]
 pub(super) enum PartialInstruction {
@@ -12,6 +13,8 @@ pub(super) enum PartialInstruction {
     LoopEnd(LoopId),   // Marker instruction, points to after looper instruction
     Continue(LoopId),
     Break(LoopId),
+    Span(Span),   // Marker instruction, following instructions were generated from this span
+    Branch(Span), // Marker instruction, next instruction is the conditional jump of a branch
 }
 
 impl From<Instruction> for PartialInstruction {
@@ -60,6 +63,7 @@ impl SecondPass {
                         .expect(&format!("Could not find: {:?}", loop_id));
 
                     let result_arg = loop_frame.get_result_location();
+                    let condition_span = loop_frame.get_condition_span();
 
                     let mut condition =
                         self.flatten(loop_frame.get_condition());
@@ -87,6 +91,9 @@ impl SecondPass {
                         RelJumpTarget::new(skip_loop_rel_target),
                         result_arg.clone(),
                     );
+                    instructions.push(PartialInstruction::Branch(
+                        condition_span.clone(),
+                    ));
                     instructions.push(skip_loop_instr.into());
 
                     // Append the body instructions
@@ -115,6 +122,7 @@ impl SecondPass {
 
                     let condition = branch_frame.get_condition();
                     let result_arg = branch_frame.get_result_location();
+                    let condition_span = branch_frame.get_condition_span();
                     let true_branch = branch_frame.get_true_branch();
                     let false_branch = branch_frame.get_false_branch();
 
@@ -141,6 +149,9 @@ impl SecondPass {
                         RelJumpTarget::new(true_rel_jump_target),
                         result_arg.clone(),
                     );
+                    instructions.push(PartialInstruction::Branch(
+                        condition_span.clone(),
+                    ));
                     instructions.push(true_rel_jump_instr.into());
 
                     // Append the false branch
@@ -169,6 +180,10 @@ impl SecondPass {
                     instructions
                         .push(PartialInstruction::Break(loop_id.clone()));
                 }
+
+                PartialInstructionFP::Span(span) => {
+                    instructions.push(PartialInstruction::Span(span.clone()));
+                }
             }
         }
 
@@ -183,15 +198,17 @@ impl Flattened {
         Flattened(Vec::new())
     }
 
-    /// Custom len() implementation needed in order to not count LoopBegin and LoopEnd as
-    /// instructions to jump over
+    /// Custom len() implementation needed in order to not count marker instructions
+    /// (LoopBegin, LoopEnd, Span, Branch) as instructions to jump over
     fn len(&self) -> usize {
         let mut size = 0;
 
         for i in self.0.iter() {
             match *i {
                 PartialInstruction::LoopBegin(..)
-                | PartialInstruction::LoopEnd(..) => (),
+                | PartialInstruction::LoopEnd(..)
+                | PartialInstruction::Span(..)
+                | PartialInstruction::Branch(..) => (),
 
                 _ => size += 1,
             }
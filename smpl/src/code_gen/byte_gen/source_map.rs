@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use crate::span::Span;
+
+use super::byte_code::InstructionPointerType;
+
+///
+/// Maps the instructions of a `ByteCodeFunction` back to the spans of the
+///   CFG nodes that generated them.
+///
+/// Conditional jumps generated from a `BranchSplit` or a `LoopHead` are
+///   recorded as branch probes, keyed by their instruction pointer.
+///
+#[derive(Debug, Clone)]
+pub struct SourceMap {
+    spans: Vec<Option<Span>>,
+    branches: HashMap<InstructionPointerType, Span>,
+}
+
+impl SourceMap {
+    pub(super) fn new() -> SourceMap {
+        SourceMap {
+            spans: Vec::new(),
+            branches: HashMap::new(),
+        }
+    }
+
+    pub(super) fn push(&mut self, span: Option<Span>) {
+        self.spans.push(span);
+    }
+
+    pub(super) fn insert_branch(
+        &mut self,
+        ip: InstructionPointerType,
+        condition_span: Span,
+    ) {
+        if self.branches.insert(ip, condition_span).is_some() {
+            panic!("Multiple branch probes for instruction {}", ip);
+        }
+    }
+
+    ///
+    /// Span of the CFG node which generated the instruction at `ip`
+    ///
+    pub fn span(&self, ip: InstructionPointerType) -> Option<&Span> {
+        self.spans
+            .get(ip as usize)
+            .and_then(|span| span.as_ref())
+    }
+
+    ///
+    /// All instruction pointers with their spans (if any), in instruction order
+    ///
+    pub fn spans(&self) -> impl Iterator<Item = (InstructionPointerType, Option<&Span>)> {
+        self.spans
+            .iter()
+            .enumerate()
+            .map(|(ip, span)| (ip as InstructionPointerType, span.as_ref()))
+    }
+
+    ///
+    /// Span of the branch condition if the instruction at `ip` is a branch probe
+    ///
+    pub fn branch(&self, ip: InstructionPointerType) -> Option<&Span> {
+        self.branches.get(&ip)
+    }
+
+    ///
+    /// All branch probes, sorted by instruction pointer
+    ///
+    pub fn branches(&self) -> Vec<(InstructionPointerType, &Span)> {
+        let mut branches: Vec<_> = self
+            .branches
+            .iter()
+            .map(|(ip, span)| (*ip, span))
+            .collect();
+
+        branches.sort_by_key(|(ip, _)| *ip);
+        branches
+    }
+}
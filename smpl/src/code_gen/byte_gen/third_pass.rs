@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use super::byte_code::*;
 use super::second_pass::PartialInstruction as PartialInstructionSP;
+use super::source_map::SourceMap;
 use crate::analysis::*;
 
 pub(super) struct ThirdPass {
@@ -19,13 +20,19 @@ impl ThirdPass {
         }
     }
 
-    pub(super) fn pass(mut self) -> Vec<Instruction> {
+    pub(super) fn pass(mut self) -> (Vec<Instruction>, SourceMap) {
         self.gather_loop_indexes();
 
         let mut instructions: Vec<Instruction> = Vec::new();
+        let mut source_map = SourceMap::new();
+        let mut current_span = None;
+        let mut branch_span = None;
 
         // Replace continue/breaks with the correct jump instructions
-        for (index, p_instr) in self.main_body.into_iter().enumerate() {
+        // Indexes only count emitted instructions (markers are stripped)
+        for p_instr in self.main_body.into_iter() {
+            let index = instructions.len();
+
             match p_instr {
                 PartialInstructionSP::Instruction(instr) => {
                     instructions.push(instr);
@@ -45,7 +52,8 @@ impl ThirdPass {
                         );
                     }
 
-                    let rel_jump_target = (index - loop_begin_index) as i64;
+                    // Jump backwards to the loop condition
+                    let rel_jump_target = -((index - loop_begin_index) as i64);
                     let instr = Instruction::RelJump(RelJumpTarget::new(
                         rel_jump_target,
                     ));
@@ -54,13 +62,13 @@ impl ThirdPass {
 
                 PartialInstructionSP::Break(loop_id) => {
                     let loop_end_index =
-                        self.loop_begin_indexes.get(&loop_id).expect(&format!(
+                        self.loop_end_indexes.get(&loop_id).expect(&format!(
                             "Could not find end index for loop id: {:?}",
                             loop_id
                         ));
 
                     if *loop_end_index < index {
-                        panic!("Attempting to break to a loop end {} before the continue instruction {}",
+                        panic!("Attempting to break to a loop end {} before the break instruction {}",
                                loop_end_index,
                                index
                         );
@@ -73,18 +81,35 @@ impl ThirdPass {
                     instructions.push(instr);
                 }
 
+                PartialInstructionSP::Span(span) => {
+                    current_span = Some(span);
+                    continue;
+                }
+
+                PartialInstructionSP::Branch(span) => {
+                    branch_span = Some(span);
+                    continue;
+                }
+
                 PartialInstructionSP::LoopBegin(..)
-                | PartialInstructionSP::LoopEnd(..) => (),
+                | PartialInstructionSP::LoopEnd(..) => continue,
+            }
+
+            // Record the span of the instruction just emitted
+            source_map.push(current_span.clone());
+            if let Some(span) = branch_span.take() {
+                source_map.insert_branch(index as InstructionPointerType, span);
             }
         }
 
-        instructions
+        (instructions, source_map)
     }
 
     fn gather_loop_indexes(&mut self) {
-        for (index, p_instr) in self.main_body.iter().enumerate() {
+        let mut index = 0;
+        for p_instr in self.main_body.iter() {
             match p_instr {
-                PartialInstructionSP::Instruction(..) => (),
+                PartialInstructionSP::Instruction(..) => index += 1,
 
                 PartialInstructionSP::LoopBegin(loop_id) => {
                     if self
@@ -113,7 +138,10 @@ impl ThirdPass {
                 }
 
                 PartialInstructionSP::Continue(..)
-                | PartialInstructionSP::Break(..) => (),
+                | PartialInstructionSP::Break(..) => index += 1,
+
+                PartialInstructionSP::Span(..)
+                | PartialInstructionSP::Branch(..) => (),
             }
         }
     }
@@ -925,8 +925,8 @@ mod RustGenFmt {
         use crate::ast::UniOp::*;
 
         match *op {
-            Ref => unimplemented!(),
-            Deref => unimplemented!(),
+            Ref => "&mut ".to_string(),
+            Deref => "*".to_string(),
             Negate => "-".to_string(),
             LogicalInvert => "!".to_string(),
         }
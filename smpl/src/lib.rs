@@ -35,7 +35,7 @@ mod ast;
 mod analysis;
 mod code_gen;
 pub mod program;
-mod span;
+pub mod span;
 
 pub mod parser;
 pub mod error;
@@ -104,6 +104,7 @@ impl<'a> UnparsedModule<'a> {
 ///
 /// Created by calling `smpl::parser::parse_module`.
 /// 
+#[derive(Clone)]
 pub struct ParsedModule {
     pub(crate) source: ModuleSource,
     pub(crate) module: Module,
@@ -123,6 +124,13 @@ impl ParsedModule {
         self.id
     }
 
+    ///
+    /// Returns the name declared by the module (`mod <name>;`), if any
+    ///
+    pub fn name(&self) -> Option<&str> {
+        self.module.name().map(|name| name.as_str())
+    }
+
     ///
     /// Returns a reference to a SMPL module's source data
     ///
@@ -99,6 +99,30 @@ pub enum ParserErrorKind {
     #[fail(display = "Can only pipe function calls.")]
     InvalidPiping(Expr),
 
+    #[fail(display = "Expected a value at the end of the block.")]
+    MissingValue,
+
+    #[fail(display = "Blocks used as values must end with a value. Branches ending in 'return', 'break' or 'continue' are not supported.")]
+    DivergingValueBlock,
+
+    #[fail(display = "Unexpected value at the end of the block. Expected ';'.")]
+    UnexpectedValue,
+
+    #[fail(display = "'if' expressions require an 'else' branch.")]
+    MissingElse,
+
+    #[fail(display = "'if', block and 'loop' expressions are only allowed as a 'let' initializer, assigned value, return value or 'break' value.")]
+    MisplacedValueExpr,
+
+    #[fail(display = "Only fn, builtin fn, struct, opaque, trait and const declarations can be 'pub'.")]
+    MisplacedPub,
+
+    #[fail(display = "'_' placeholders are only allowed as arguments of a (non-piped) function call.")]
+    MisplacedPlaceholder,
+
+    #[fail(display = "Fields can only be accessed through a variable. Only method calls may follow a call or parenthesized expression.")]
+    MisplacedFieldAccess,
+
     #[fail(display = "'{}'", _0)]
     TokenizerError(SpannedError),
 }
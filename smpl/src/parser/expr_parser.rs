@@ -16,7 +16,10 @@ pub enum Delimiter {
     Comma,
     Semi,
     LBrace,
+    DotDot,
     Pipe,
+    /// End of a string interpolation
+    FormatHole,
 }
 
 pub fn piped_expr(
@@ -30,6 +33,20 @@ pub fn piped_expr(
     prebase_piped_expr(tokens, expr_base, delim_tokens)
 }
 
+///
+/// Continues an expression whose leading primary was already consumed (i.e. by statement
+/// parsing).
+///
+pub fn prebase_expr(
+    tokens: &mut BufferedTokenizer,
+    primary_base: AstNode<Expr>,
+    delim_tokens: &[Delimiter],
+) -> ParseErr<AstNode<Expr>> {
+    let expr_base = expr(tokens, primary_base, delim_tokens, 0)?;
+
+    prebase_piped_expr(tokens, expr_base, delim_tokens)
+}
+
 pub fn prebase_piped_expr(
     tokens: &mut BufferedTokenizer,
     expr_base: AstNode<Expr>,
@@ -86,6 +103,11 @@ pub fn prebase_piped_expr(
             .map(|e| {
                 let (e, _espan) = e.to_data();
                 match e {
+                    Expr::FnCall(ref f) if f.data().is_partial() => Err(parser_error!(
+                        ParserErrorKind::MisplacedPlaceholder,
+                        parser_state!("prebase-piped-expr", "pipe-validation")
+                    )),
+
                     Expr::FnCall(f) => Ok(f),
                     e @ _ => Err(parser_error!(
                         ParserErrorKind::InvalidPiping(e),
@@ -224,9 +246,22 @@ fn expr(
 }
 
 fn parse_primary(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
+    let operand = production!(
+        primary_operand(tokens),
+        parser_state!("parse-primary", "operand")
+    );
+
+    Ok(production!(
+        chained_method_calls(tokens, operand),
+        parser_state!("parse-primary", "method-calls")
+    ))
+}
+
+fn primary_operand(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
     enum PrimaryDec {
         Ident,
         Literal,
+        Format,
         UniExpr,
         LParen,
         Err,
@@ -241,11 +276,15 @@ fn parse_primary(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
             Token::Plus => PrimaryDec::UniExpr,
             Token::Minus => PrimaryDec::UniExpr,
             Token::Invert => PrimaryDec::UniExpr,
+            Token::Tilde => PrimaryDec::UniExpr,
+            Token::Ref => PrimaryDec::UniExpr,
+            Token::Star => PrimaryDec::UniExpr,
 
             Token::IntLiteral(_) => PrimaryDec::Literal,
             Token::FloatLiteral(_) => PrimaryDec::Literal,
             Token::BoolLiteral(_) => PrimaryDec::Literal,
             Token::StringLiteral(_) => PrimaryDec::Literal,
+            Token::FormatStart(_) => PrimaryDec::Format,
 
             Token::LParen => PrimaryDec::LParen,
 
@@ -281,6 +320,12 @@ fn parse_primary(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
 
                 Token::Invert => UniOp::LogicalInvert,
 
+                Token::Tilde => UniOp::BitNot,
+
+                Token::Ref => UniOp::Ref,
+
+                Token::Star => UniOp::Deref,
+
                 _ => unreachable!(),
             };
 
@@ -300,6 +345,8 @@ fn parse_primary(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
             Ok(AstNode::new(Expr::Uni(AstNode::new(uexpr, span.clone())), span))
         }
 
+        PrimaryDec::Format => format_string(tokens),
+
         PrimaryDec::Literal => {
             let (next_span, next) = tokens
                 .next()
@@ -332,10 +379,24 @@ fn parse_primary(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
             );
 
             let inner = production!(
-                piped_expr(tokens, &[Delimiter::RParen]),
+                piped_expr(tokens, &[Delimiter::RParen, Delimiter::Comma]),
                 parser_state!("paren-expr", "inner-expr")
             );
 
+            if peek_token!(
+                tokens,
+                |tok| match tok {
+                    Token::Comma => true,
+                    _ => false,
+                },
+                parser_state!("paren-expr", "tuple comma?")
+            ) {
+                return Ok(production!(
+                    tuple_init(tokens, lspan, inner),
+                    parser_state!("paren-expr", "tuple-init")
+                ));
+            }
+
             let (rspan, _) = consume_token!(
                 tokens,
                 Token::RParen,
@@ -363,7 +424,25 @@ fn parse_primary(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
             parser_state!("primary", "anonymous-fn")
         )),
 
-        PrimaryDec::Err => unimplemented!(),
+        PrimaryDec::Err => {
+            let (span, token) =
+                consume_token!(tokens, parser_state!("parse-primary", "unexpected"));
+
+            let kind = match token {
+                // Only lowered where the value is bound to a variable or returned
+                Token::If | Token::LBrace | Token::Loop | Token::Label(_) => {
+                    ParserErrorKind::MisplacedValueExpr
+                }
+
+                token => ParserErrorKind::UnexpectedToken(token),
+            };
+
+            Err(parser_error!(
+                kind,
+                parser_state!("parse-primary", "unexpected"),
+                Some(span)
+            ))
+        }
     }
 }
 
@@ -394,9 +473,13 @@ fn parse_ident_leaf(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
         IdentLeafDec::AccessPath => {
             let span = base_span;
             let root = PathSegment::Ident(AstNode::new(base_ident, span));
-            Ok(production!(
+            let path = production!(
                 access_path(tokens, root),
                 parser_state!("ident-leaf", "access-path")
+            );
+            Ok(production!(
+                maybe_method_call(tokens, path),
+                parser_state!("ident-leaf", "method-call?")
             ))
         }
         IdentLeafDec::ModulePath => Ok(production!(
@@ -495,10 +578,50 @@ fn parse_ident_leaf(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
                 parser_state!("indexing-expr", "lbracket")
             );
             let indexer = production!(
-                piped_expr(tokens, &[Delimiter::RBracket]),
+                piped_expr(tokens, &[Delimiter::RBracket, Delimiter::DotDot]),
                 parser_state!("indexing-expr", "indexer")
             );
             let (indexer, _) = indexer.to_data();
+
+            if peek_token!(
+                tokens,
+                |tok| match tok {
+                    Token::DotDot => true,
+                    _ => false,
+                },
+                parser_state!("indexing-expr", "slice?")
+            ) {
+                let _dotdot = consume_token!(
+                    tokens,
+                    Token::DotDot,
+                    parser_state!("slice-expr", "dotdot")
+                );
+                let end = production!(
+                    piped_expr(tokens, &[Delimiter::RBracket]),
+                    parser_state!("slice-expr", "end")
+                );
+                let (end, _) = end.to_data();
+                let (rspan, _rbracket) = consume_token!(
+                    tokens,
+                    Token::RBracket,
+                    parser_state!("slice-expr", "rbracket")
+                );
+
+                let binding =
+                    Expr::Binding(AstNode::new(base_ident, base_span.clone()));
+                let slice = Slice {
+                    array: Box::new(binding),
+                    start: Box::new(indexer),
+                    end: Box::new(end),
+                };
+
+                let span = Span::combine(base_span, rspan);
+                return Ok(AstNode::new(
+                    Expr::Slice(AstNode::new(slice, span.clone())),
+                    span,
+                ));
+            }
+
             let (rspan, _rbracket) = consume_token!(
                 tokens,
                 Token::RBracket,
@@ -519,9 +642,13 @@ fn parse_ident_leaf(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
                     AstNode::new(base_ident, span),
                     Box::new(indexer),
                 );
-                Ok(production!(
+                let path = production!(
                     access_path(tokens, root),
                     parser_state!("access-path")
+                );
+                Ok(production!(
+                    maybe_method_call(tokens, path),
+                    parser_state!("indexing-expr", "method-call?")
                 ))
             } else {
                 // Single indexing
@@ -595,6 +722,169 @@ pub fn access_path(
     ))
 }
 
+fn maybe_method_call(
+    tokens: &mut BufferedTokenizer,
+    path: AstNode<Expr>,
+) -> ParseErr<AstNode<Expr>> {
+    if tokens.has_next()
+        && peek_token!(
+            tokens,
+            |tok| match tok {
+                Token::LParen => true,
+                _ => false,
+            },
+            parser_state!("maybe-method-call", "lparen?")
+        )
+    {
+        method_call(tokens, path)
+    } else {
+        Ok(path)
+    }
+}
+
+///
+/// Parses the arguments of `receiver.method(args)` and any chained method calls after it.
+///
+/// `path` is the access path up to and including the method name.
+///
+pub fn method_call(
+    tokens: &mut BufferedTokenizer,
+    path: AstNode<Expr>,
+) -> ParseErr<AstNode<Expr>> {
+    let (path, path_span) = path.to_data();
+    let mut segments = match path {
+        Expr::FieldAccess(path) => path.to_data().0 .0,
+        _ => unreachable!(),
+    };
+
+    let method = match segments.pop() {
+        Some(PathSegment::Ident(method)) if !segments.is_empty() => method,
+        _ => {
+            return Err(parser_error!(
+                ParserErrorKind::UnexpectedToken(Token::LParen),
+                parser_state!("method-call", "method-name")
+            ));
+        }
+    };
+
+    let receiver = if segments.len() == 1 {
+        match segments.pop().unwrap() {
+            PathSegment::Ident(root) => Expr::Binding(root),
+            PathSegment::Indexing(root, indexer) => {
+                let span = root.span();
+                let indexing = Indexing {
+                    array: Box::new(Expr::Binding(root)),
+                    indexer: indexer,
+                };
+                Expr::Indexing(AstNode::new(indexing, span))
+            }
+        }
+    } else {
+        Expr::FieldAccess(AstNode::new(Path(segments), path_span.clone()))
+    };
+    let call = production!(
+        method_call_args(tokens, AstNode::new(receiver, path_span), method),
+        parser_state!("method-call", "args")
+    );
+
+    chained_method_calls(tokens, call)
+}
+
+///
+/// Parses any `.method(args)` calls following `receiver`.
+///
+/// Method calls may follow any primary expression, e.g. `foo(a).show()` or `(x).show()`.
+/// Fields can only be accessed through a variable.
+///
+pub fn chained_method_calls(
+    tokens: &mut BufferedTokenizer,
+    receiver: AstNode<Expr>,
+) -> ParseErr<AstNode<Expr>> {
+    let mut receiver = receiver;
+
+    while tokens.has_next()
+        && peek_token!(
+            tokens,
+            |tok| match tok {
+                Token::Dot => true,
+                _ => false,
+            },
+            parser_state!("method-call", "chained-dot?")
+        )
+    {
+        let _dot = consume_token!(
+            tokens,
+            Token::Dot,
+            parser_state!("method-call", "chained-dot")
+        );
+        let (mspan, name) = consume_token!(tokens,
+                                           Token::Identifier(i) => Ident(i),
+                                           parser_state!("method-call", "chained-method"));
+
+        if !peek_token!(
+            tokens,
+            |tok| match tok {
+                Token::LParen => true,
+                _ => false,
+            },
+            parser_state!("method-call", "chained-lparen?")
+        ) {
+            return Err(parser_error!(
+                ParserErrorKind::MisplacedFieldAccess,
+                parser_state!("method-call", "chained-lparen"),
+                Some(mspan)
+            ));
+        }
+
+        receiver = production!(
+            method_call_args(tokens, receiver, AstNode::new(name, mspan)),
+            parser_state!("method-call", "chained-args")
+        );
+    }
+
+    Ok(receiver)
+}
+
+fn method_call_args(
+    tokens: &mut BufferedTokenizer,
+    receiver: AstNode<Expr>,
+    method: AstNode<Ident>,
+) -> ParseErr<AstNode<Expr>> {
+    let (receiver, receiver_span) = receiver.to_data();
+
+    let (args, args_span) = production!(
+        fn_args(tokens),
+        parser_state!("method-call", "args")
+    )
+    .to_data();
+    let args: Option<Vec<_>> =
+        args.map(|v| v.into_iter().map(|a| a.to_data().0).collect());
+
+    if args
+        .iter()
+        .flatten()
+        .any(|arg| matches!(arg, Expr::Placeholder(_)))
+    {
+        return Err(parser_error!(
+            ParserErrorKind::MisplacedPlaceholder,
+            parser_state!("method-call", "args"),
+            Some(args_span)
+        ));
+    }
+
+    let span = Span::combine(receiver_span, args_span);
+    let call = MethodCall {
+        receiver: Box::new(receiver),
+        method,
+        args,
+    };
+
+    Ok(AstNode::new(
+        Expr::MethodCall(AstNode::new(call, span.clone())),
+        span,
+    ))
+}
+
 // At end of path_segment, next token should be DOT or end of path
 fn path_segment(tokens: &mut BufferedTokenizer) -> ParseErr<PathSegment> {
     enum SegmentDec {
@@ -603,9 +893,25 @@ fn path_segment(tokens: &mut BufferedTokenizer) -> ParseErr<PathSegment> {
         End,
     }
 
-    let (ispan, ident) = consume_token!(tokens, 
-                                        Token::Identifier(i) => Ident(i),
-                                        parser_state!("path-segment", "name"));
+    // Tuple fields are accessed by position: `tuple.0`
+    let field_index = peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::IntLiteral(_) => true,
+            _ => false,
+        },
+        parser_state!("path-segment", "tuple-index?")
+    );
+
+    let (ispan, ident) = if field_index {
+        consume_token!(tokens, 
+                       Token::IntLiteral(i) => Ident(i.to_string()),
+                       parser_state!("path-segment", "tuple-index"))
+    } else {
+        consume_token!(tokens, 
+                       Token::Identifier(i) => Ident(i),
+                       parser_state!("path-segment", "name"))
+    };
 
     match peek_token!(
         tokens,
@@ -679,10 +985,26 @@ pub fn fn_args_post_lparen(
         },
         parser_state!("fn-args", "rparen?")
     ) {
-        let arg = production!(
-            piped_expr(tokens, &[Delimiter::RParen, Delimiter::Comma]),
-            parser_state!("fn-args", "value")
-        );
+        let arg = if peek_token!(
+            tokens,
+            |tok| match tok {
+                Token::Underscore => true,
+                _ => false,
+            },
+            parser_state!("fn-args", "placeholder?")
+        ) {
+            let (span, _) = consume_token!(
+                tokens,
+                Token::Underscore,
+                parser_state!("fn-args", "placeholder")
+            );
+            AstNode::new(Expr::Placeholder(AstNode::new((), span.clone())), span)
+        } else {
+            production!(
+                piped_expr(tokens, &[Delimiter::RParen, Delimiter::Comma]),
+                parser_state!("fn-args", "value")
+            )
+        };
 
         match args {
             Some(mut a) => {
@@ -902,7 +1224,7 @@ fn struct_init(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
     if peek_token!(
         tokens,
         |tok| match tok {
-            Token::RBrace => false,
+            Token::RBrace | Token::DotDot => false,
             _ => true,
         },
         parser_state!("struct-init", "rbrace?")
@@ -913,6 +1235,31 @@ fn struct_init(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
         );
     }
 
+    // Only named struct inits copy the remaining fields from a base struct
+    let base = if path.is_some()
+        && peek_token!(
+            tokens,
+            |tok| match tok {
+                Token::DotDot => true,
+                _ => false,
+            },
+            parser_state!("struct-init", "base?")
+        ) {
+        let _dotdot = consume_token!(
+            tokens,
+            Token::DotDot,
+            parser_state!("struct-init", "base ..")
+        );
+
+        let base = production!(
+            piped_expr(tokens, &[]),
+            parser_state!("struct-init", "base")
+        );
+        Some(Box::new(base.to_data().0))
+    } else {
+        None
+    };
+
     let (lroc, _rbrace) = consume_token!(
         tokens,
         Token::RBrace,
@@ -932,6 +1279,7 @@ fn struct_init(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
         let struct_init = StructInit {
             struct_name: struct_path,
             field_init: init,
+            base: base,
         };
 
         let struct_init = AstNode::new(struct_init, span.clone());
@@ -971,7 +1319,7 @@ fn struct_field_init_list(
             if peek_token!(
                 tokens,
                 |tok| match tok {
-                    Token::RBrace => false,
+                    Token::RBrace | Token::DotDot => false,
                     _ => true,
                 },
                 parser_state!("struct-field-init-list", "rbrace?")
@@ -1035,6 +1383,28 @@ fn array_init(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
         parser_state!("array-init", "lbracket")
     );
 
+    // Empty array literal
+    if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::RBracket => true,
+            _ => false,
+        },
+        parser_state!("array-init", "empty?")
+    ) {
+        let (rloc, _) = consume_token!(
+            tokens,
+            Token::RBracket,
+            parser_state!("array-init", "rbracket")
+        );
+
+        let span = LocationSpan::combine(lloc, rloc);
+        return Ok(AstNode::new(Expr::ArrayInit(AstNode::new(
+            ArrayInit::InitList(Vec::new()),
+            span.clone(),
+        )), span));
+    }
+
     let base_expr = production!(
         parse_primary(tokens),
         parser_state!("array-init", "base-primary")
@@ -1108,6 +1478,66 @@ fn array_init(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
     Ok(AstNode::new(Expr::ArrayInit(array_init), span))
 }
 
+/// Parses the rest of a tuple literal after the first element.
+/// A single element tuple requires a trailing comma: `(1,)`.
+fn tuple_init(
+    tokens: &mut BufferedTokenizer,
+    lspan: LocationSpan,
+    first: AstNode<Expr>,
+) -> ParseErr<AstNode<Expr>> {
+    let mut elements = vec![first.to_data().0];
+
+    loop {
+        if peek_token!(
+            tokens,
+            |tok| match tok {
+                Token::Comma => true,
+                _ => false,
+            },
+            parser_state!("tuple-init", "comma separator?")
+        ) {
+            let _comma = consume_token!(
+                tokens,
+                Token::Comma,
+                parser_state!("tuple-init", "comma separator")
+            );
+            if peek_token!(
+                tokens,
+                |tok| match tok {
+                    Token::RParen => false,
+                    _ => true,
+                },
+                parser_state!("tuple-init", "rparen")
+            ) {
+                let element = production!(
+                    piped_expr(tokens, &[Delimiter::RParen, Delimiter::Comma]),
+                    parser_state!("tuple-init", "element")
+                )
+                .to_data();
+
+                elements.push(element.0);
+                continue;
+            }
+        }
+
+        break;
+    }
+
+    let (rspan, _) = consume_token!(
+        tokens,
+        Token::RParen,
+        parser_state!("tuple-init", "rparen")
+    );
+
+    let span = LocationSpan::combine(lspan, rspan);
+    let init = TupleInit { elements };
+
+    Ok(AstNode::new(
+        Expr::TupleInit(AstNode::new(init, span.clone())),
+        span,
+    ))
+}
+
 fn array_init_list(tokens: &mut BufferedTokenizer) -> ParseErr<Vec<Expr>> {
     // First element already consumed, check for rest of list
     let mut list = Vec::new();
@@ -1161,6 +1591,52 @@ fn array_init_list(tokens: &mut BufferedTokenizer) -> ParseErr<Vec<Expr>> {
     Ok(list)
 }
 
+///
+/// `"text {expr} text"`. The tokenizer splits the string around each interpolated expression.
+///
+fn format_string(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
+    let (start, text) = consume_token!(tokens,
+                                       Token::FormatStart(text) => text,
+                                       parser_state!("format-string", "start"));
+
+    let mut parts = Vec::new();
+    if !text.is_empty() {
+        parts.push(FormatPart::Text(text));
+    }
+
+    loop {
+        let value = production!(
+            piped_expr(tokens, &[Delimiter::FormatHole]),
+            parser_state!("format-string", "value")
+        );
+        parts.push(FormatPart::Value(value.to_data().0));
+
+        let (span, next) = consume_token!(tokens, parser_state!("format-string", "text"));
+        let (text, end) = match next {
+            Token::FormatMid(text) => (text, false),
+            Token::FormatEnd(text) => (text, true),
+
+            token => {
+                return Err(parser_error!(
+                    ParserErrorKind::UnexpectedToken(token),
+                    parser_state!("format-string", "text"),
+                    Some(span)
+                ));
+            }
+        };
+
+        if !text.is_empty() {
+            parts.push(FormatPart::Text(text));
+        }
+
+        if end {
+            let span = LocationSpan::combine(start, span);
+            let format = AstNode::new(FormatString { parts }, span.clone());
+            return Ok(AstNode::new(Expr::Format(format), span));
+        }
+    }
+}
+
 fn anonymous_fn(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
     let (fnloc, _) =
         consume_token!(tokens, Token::Fn, parser_state!("anonymous-fn", "fn"));
@@ -1236,7 +1712,9 @@ fn is_delim(token: &Token, delim: &[Delimiter]) -> bool {
         Token::Comma => Delimiter::Comma,
         Token::Semi => Delimiter::Semi,
         Token::LBrace => Delimiter::LBrace,
+        Token::DotDot => Delimiter::DotDot,
         Token::Pipe => Delimiter::Pipe,
+        Token::FormatMid(_) | Token::FormatEnd(_) => Delimiter::FormatHole,
 
         _ => return false,
     };
@@ -1264,6 +1742,13 @@ fn get_op(token: &Token) -> Option<BinOp> {
         Eq => Some(BinOp::Eq),
         NEq => Some(BinOp::InEq),
 
+        // '&' is a reference in prefix position
+        Ref => Some(BinOp::BitAnd),
+        Bar => Some(BinOp::BitOr),
+        Caret => Some(BinOp::BitXor),
+        Shl => Some(BinOp::Shl),
+        Shr => Some(BinOp::Shr),
+
         _ => None,
     }
 }
@@ -1287,6 +1772,12 @@ fn bin_op_precedence(op: &BinOp) -> u64 {
         Lesser => 10,
         Eq => 9,
         InEq => 9,
+
+        Shl => 12,
+        Shr => 12,
+        BitAnd => 8,
+        BitXor => 7,
+        BitOr => 6,
     }
 }
 
@@ -1307,5 +1798,11 @@ fn is_left_associative(op: &BinOp) -> bool {
         Lesser => true,
         Eq => true,
         InEq => true,
+
+        BitAnd => true,
+        BitOr => true,
+        BitXor => true,
+        Shl => true,
+        Shr => true,
     }
 }
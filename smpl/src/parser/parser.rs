@@ -56,11 +56,32 @@ pub fn module(tokens: &mut BufferedTokenizer) -> ParseErr<Module> {
         Opaque,
         Annotation,
         Function(bool),
+        Impl,
+        Trait,
+        Global(bool),
         Use,
+        Pub,
+        TypeAlias,
         Err,
     }
 
+    let mut anno = Vec::new();
+    if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::Pound => true,
+            _ => false,
+        },
+        parser_state!("module", "annotation?")
+    ) {
+        anno = production!(
+            annotations(tokens),
+            parser_state!("module", "annotation")
+        );
+    }
+
     let mut name = None;
+    let mut mod_anno = Vec::new();
     if peek_token!(
         tokens,
         |tok| match tok {
@@ -69,15 +90,16 @@ pub fn module(tokens: &mut BufferedTokenizer) -> ParseErr<Module> {
         },
         parser_state!("module", "mod-decl")
     ) {
-        // Found mod declaration
+        // Found mod declaration. Leading annotations belong to it instead of the first item.
         name = Some(production!(
             module_decl(tokens),
             parser_state!("module", "mod-decl")
         ));
+        mod_anno = std::mem::take(&mut anno);
     }
 
     let mut decls = Vec::new();
-    let mut anno = Vec::new();
+    let mut public = false;
 
     while tokens.has_next() {
         match peek_token!(
@@ -88,25 +110,33 @@ pub fn module(tokens: &mut BufferedTokenizer) -> ParseErr<Module> {
                 Token::Fn => ModDec::Function(false),
                 Token::Builtin => ModDec::Function(true),
                 Token::Opaque => ModDec::Opaque,
+                Token::Impl => ModDec::Impl,
+                Token::Trait => ModDec::Trait,
+                Token::Const => ModDec::Global(true),
+                Token::Static => ModDec::Global(false),
                 Token::Use => ModDec::Use,
+                Token::Pub => ModDec::Pub,
+                Token::Type => ModDec::TypeAlias,
                 _ => ModDec::Err,
             },
             parser_state!("module", "decl-kind")
         ) {
             ModDec::Opaque => {
                 decls.push(DeclStmt::Opaque(production!(
-                    opaque_decl(tokens, anno),
+                    opaque_decl(tokens, anno, public),
                     parser_state!("module", "opaque-decl")
                 )));
                 anno = Vec::new();
+                public = false;
             }
 
             ModDec::Struct => {
                 decls.push(DeclStmt::Struct(production!(
-                    struct_decl(tokens, anno),
+                    struct_decl(tokens, anno, public),
                     parser_state!("module", "struct-decl")
                 )));
                 anno = Vec::new();
+                public = false;
             }
 
             ModDec::Annotation => {
@@ -118,13 +148,58 @@ pub fn module(tokens: &mut BufferedTokenizer) -> ParseErr<Module> {
 
             ModDec::Function(is_builtin) => {
                 decls.push(production!(
-                    fn_decl(tokens, anno, is_builtin),
+                    fn_decl(tokens, anno, public, is_builtin, None),
                     parser_state!("module", "fn-decl")
                 ));
                 anno = Vec::new();
+                public = false;
+            }
+
+            ModDec::Impl => {
+                if public {
+                    return Err(parser_error!(
+                        ParserErrorKind::MisplacedPub,
+                        parser_state!("module", "impl-decl")
+                    ));
+                }
+                decls.push(DeclStmt::Impl(production!(
+                    impl_decl(tokens),
+                    parser_state!("module", "impl-decl")
+                )));
+                anno = Vec::new();
+            }
+
+            ModDec::Trait => {
+                decls.push(DeclStmt::Trait(production!(
+                    trait_decl(tokens, public),
+                    parser_state!("module", "trait-decl")
+                )));
+                anno = Vec::new();
+                public = false;
+            }
+
+            ModDec::Global(is_const) => {
+                if public && !is_const {
+                    return Err(parser_error!(
+                        ParserErrorKind::MisplacedPub,
+                        parser_state!("module", "global-decl")
+                    ));
+                }
+                decls.push(production!(
+                    global_decl(tokens, is_const, public),
+                    parser_state!("module", "global-decl")
+                ));
+                anno = Vec::new();
+                public = false;
             }
 
             ModDec::Use => {
+                if public {
+                    return Err(parser_error!(
+                        ParserErrorKind::MisplacedPub,
+                        parser_state!("module", "use-decl")
+                    ));
+                }
                 decls.push(production!(
                     use_decl(tokens),
                     parser_state!("module", "use-decl")
@@ -132,13 +207,31 @@ pub fn module(tokens: &mut BufferedTokenizer) -> ParseErr<Module> {
                 anno = Vec::new();
             }
 
+            ModDec::TypeAlias => {
+                decls.push(DeclStmt::TypeAlias(production!(
+                    type_alias_decl(tokens, public),
+                    parser_state!("module", "type-alias-decl")
+                )));
+                anno = Vec::new();
+                public = false;
+            }
+
+            ModDec::Pub => {
+                let _pub = consume_token!(
+                    tokens,
+                    Token::Pub,
+                    parser_state!("module", "pub")
+                );
+                public = true;
+            }
+
             ModDec::Err => {
                 unimplemented!("Unexpected token: {:?}", tokens.next().unwrap())
             }
         }
     }
 
-    let module = Module(name, decls);
+    let module = Module(name, decls, mod_anno);
 
     Ok(module)
 }
@@ -253,24 +346,148 @@ fn kv_pair(
 fn use_decl(tokens: &mut BufferedTokenizer) -> ParseErr<DeclStmt> {
     let (uspan, _) =
         consume_token!(tokens, Token::Use, parser_state!("use-decl", "use"));
-    let (mspan, module) = consume_token!(tokens, 
-                                         Token::Identifier(i) => Ident(i),
-                                         parser_state!("use-decl", "name"));
-    let _semi = consume_token!(
+    let (mspan, first) = consume_token!(tokens, 
+                                        Token::Identifier(i) => Ident(i),
+                                        parser_state!("use-decl", "name"));
+
+    let mut path = vec![AstNode::new(first, mspan.clone())];
+    let mut module_span = mspan;
+    let mut items = None;
+    while peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::ColonColon => true,
+            _ => false,
+        },
+        parser_state!("use-decl", "coloncolon?")
+    ) {
+        let _coloncolon = consume_token!(
+            tokens,
+            Token::ColonColon,
+            parser_state!("use-decl", "coloncolon")
+        );
+
+        let (next_span, next) = consume_token!(tokens, parser_state!("use-decl", "segment"));
+        match next {
+            Token::Identifier(segment) => {
+                module_span = LocationSpan::combine(module_span, next_span.clone());
+                path.push(AstNode::new(Ident(segment), next_span));
+            }
+
+            Token::LBrace => {
+                items = Some(production!(
+                    import_items(tokens),
+                    parser_state!("use-decl", "items")
+                ));
+                break;
+            }
+
+            token => {
+                return Err(parser_error!(
+                    ParserErrorKind::UnexpectedToken(token),
+                    parser_state!("use-decl", "segment"),
+                    Some(next_span)
+                ));
+            }
+        }
+    }
+
+    let import = match items {
+        Some(items) => Import::Items(items),
+        None => Import::Module(production!(
+            import_alias(tokens),
+            parser_state!("use-decl", "alias")
+        )),
+    };
+
+    let (semi_span, _) = consume_token!(
         tokens,
         Token::Semi,
         parser_state!("use-decl", "semicolon")
     );
 
-    let span = LocationSpan::combine(uspan, mspan.clone());
+    let span = LocationSpan::combine(uspan, semi_span);
 
-    let use_decl = UseDecl(AstNode::new(module, mspan));
+    let use_decl = UseDecl {
+        module: AstNode::new(ModulePath(path), module_span),
+        import,
+    };
 
     let use_decl = DeclStmt::Use(AstNode::new(use_decl, span));
 
     Ok(use_decl)
 }
 
+///
+/// `{x, y as z}` after the opening brace
+///
+fn import_items(tokens: &mut BufferedTokenizer) -> ParseErr<Vec<ImportItem>> {
+    let mut items = Vec::new();
+    loop {
+        if peek_token!(
+            tokens,
+            |tok| match tok {
+                Token::RBrace => true,
+                _ => false,
+            },
+            parser_state!("import-items", "rbrace?")
+        ) {
+            let _rbrace = consume_token!(
+                tokens,
+                Token::RBrace,
+                parser_state!("import-items", "rbrace")
+            );
+            return Ok(items);
+        }
+
+        let (name_span, name) = consume_token!(tokens,
+                                               Token::Identifier(i) => Ident(i),
+                                               parser_state!("import-items", "name"));
+        let alias = production!(
+            import_alias(tokens),
+            parser_state!("import-items", "alias")
+        );
+
+        items.push(ImportItem {
+            name: AstNode::new(name, name_span),
+            alias,
+        });
+
+        let (span, next) = consume_token!(tokens, parser_state!("import-items", "separator"));
+        match next {
+            Token::Comma => (),
+            Token::RBrace => return Ok(items),
+
+            token => {
+                return Err(parser_error!(
+                    ParserErrorKind::UnexpectedToken(token),
+                    parser_state!("import-items", "separator"),
+                    Some(span)
+                ));
+            }
+        }
+    }
+}
+
+fn import_alias(tokens: &mut BufferedTokenizer) -> ParseErr<Option<AstNode<Ident>>> {
+    if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::As => true,
+            _ => false,
+        },
+        parser_state!("import-alias", "as?")
+    ) {
+        let _as = consume_token!(tokens, Token::As, parser_state!("import-alias", "as"));
+        let (span, alias) = consume_token!(tokens,
+                                           Token::Identifier(i) => Ident(i),
+                                           parser_state!("import-alias", "alias"));
+        Ok(Some(AstNode::new(alias, span)))
+    } else {
+        Ok(None)
+    }
+}
+
 fn where_clause(tokens: &mut BufferedTokenizer) -> ParseErr<WhereClause> {
     let _where = consume_token!(
         tokens,
@@ -290,15 +507,35 @@ fn where_clause(tokens: &mut BufferedTokenizer) -> ParseErr<WhereClause> {
             Token::Colon,
             parser_state!("where-clause-constraints", "colon")
         );
-        let annotation = production!(
+        let mut annotations = vec![production!(
             type_annotation(tokens),
             parser_state!("where-clause-constraints", "annotation")
-        );
+        )];
+
+        // Multiple bounds on one parameter (i.e. 'T: Show + Eq')
+        while peek_token!(
+            tokens,
+            |tok| match tok {
+                Token::Plus => true,
+                _ => false,
+            },
+            parser_state!("where-clause-constraints", "plus?")
+        ) {
+            let _plus = consume_token!(
+                tokens,
+                Token::Plus,
+                parser_state!("where-clause-constraints", "plus")
+            );
+            annotations.push(production!(
+                type_annotation(tokens),
+                parser_state!("where-clause-constraints", "annotation")
+            ));
+        }
 
         parameter_constraints
             .entry(AstNode::new(parameter, param_span))
             .or_insert(Vec::new())
-            .push(annotation);
+            .extend(annotations);
 
         if peek_token!(
             tokens,
@@ -326,17 +563,424 @@ fn where_clause(tokens: &mut BufferedTokenizer) -> ParseErr<WhereClause> {
 
 #[cfg(test)]
 pub fn testfn_decl(tokens: &mut BufferedTokenizer) -> ParseErr<Function> {
-    let decl = fn_decl(tokens, vec![], false)?;
+    let decl = fn_decl(tokens, vec![], false, false, None)?;
     match decl {
         DeclStmt::Function(f) => Ok(f.to_data().0),
         _ => unreachable!(),
     }
 }
 
+fn impl_decl(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Impl>> {
+    enum ImplDec {
+        Annotation,
+        Function(bool),
+        End,
+    }
+
+    let (impl_loc, _) = consume_token!(
+        tokens,
+        Token::Impl,
+        parser_state!("impl-decl", "impl")
+    );
+
+    let type_params = if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::LParen => true,
+            _ => false,
+        },
+        parser_state!("impl-decl", "type-parameters?")
+    ) {
+        Some(type_param_list(tokens)?)
+    } else {
+        None
+    };
+
+    let target = production!(
+        type_annotation(tokens),
+        parser_state!("impl-decl", "target")
+    );
+
+    // 'impl Trait for Target'
+    let (trait_name, target) = if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::For => true,
+            _ => false,
+        },
+        parser_state!("impl-decl", "for?")
+    ) {
+        let (for_span, _) = consume_token!(
+            tokens,
+            Token::For,
+            parser_state!("impl-decl", "for")
+        );
+
+        let (trait_ann, trait_span) = target.to_data();
+        let trait_name = match trait_ann {
+            TypeAnnotation::Path(TypedPath::NillArity(path)) => path,
+            _ => {
+                return Err(parser_error!(
+                    ParserErrorKind::UnexpectedToken(Token::For),
+                    parser_state!("impl-decl", "trait-name"),
+                    Some(for_span)
+                ));
+            }
+        };
+
+        let target = production!(
+            type_annotation(tokens),
+            parser_state!("impl-decl", "target")
+        );
+
+        (Some(AstNode::new(trait_name, trait_span)), target)
+    } else {
+        (None, target)
+    };
+
+    let where_clause = if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::Where => true,
+            _ => false,
+        },
+        parser_state!("impl-decl", "where-clause?")
+    ) {
+        Some(production!(
+            where_clause(tokens),
+            parser_state!("impl-decl", "where-clause")
+        ))
+    } else {
+        None
+    };
+
+    let _lbrace = consume_token!(
+        tokens,
+        Token::LBrace,
+        parser_state!("impl-decl", "lbrace")
+    );
+
+    let mut methods = Vec::new();
+    let mut anno = Vec::new();
+    loop {
+        match peek_token!(
+            tokens,
+            |tok| match tok {
+                Token::Pound => ImplDec::Annotation,
+                Token::Fn => ImplDec::Function(false),
+                Token::Builtin => ImplDec::Function(true),
+                _ => ImplDec::End,
+            },
+            parser_state!("impl-decl", "method-kind")
+        ) {
+            ImplDec::Annotation => {
+                anno = production!(
+                    annotations(tokens),
+                    parser_state!("impl-decl", "annotation")
+                );
+            }
+
+            ImplDec::Function(is_builtin) => {
+                let method = production!(
+                    fn_decl(tokens, anno, true, is_builtin, Some(&target)),
+                    parser_state!("impl-decl", "method")
+                );
+                anno = Vec::new();
+
+                methods.push(match method {
+                    DeclStmt::Function(f) => Method::Function(f),
+                    DeclStmt::BuiltinFunction(f) => Method::Builtin(f),
+                    _ => unreachable!(),
+                });
+            }
+
+            ImplDec::End => break,
+        }
+    }
+
+    let (rloc, _) = consume_token!(
+        tokens,
+        Token::RBrace,
+        parser_state!("impl-decl", "rbrace")
+    );
+
+    Ok(AstNode::new(
+        Impl {
+            target,
+            trait_name,
+            type_params,
+            where_clause,
+            methods,
+        },
+        LocationSpan::combine(impl_loc, rloc),
+    ))
+}
+
+fn global_decl(
+    tokens: &mut BufferedTokenizer,
+    is_const: bool,
+    public: bool,
+) -> ParseErr<DeclStmt> {
+    // Already checked to be 'const' or 'static'
+    let (global_loc, _) = consume_token!(
+        tokens,
+        parser_state!("global-decl", "const or static")
+    );
+
+    let (name_loc, name) = consume_token!(tokens,
+                                          Token::Identifier(i) => Ident(i),
+                                          parser_state!("global-decl", "name"));
+
+    let _colon = consume_token!(
+        tokens,
+        Token::Colon,
+        parser_state!("global-decl", "type colon")
+    );
+
+    let global_type = production!(
+        type_annotation(tokens),
+        parser_state!("global-decl", "type annotation")
+    );
+
+    let _assign = consume_token!(
+        tokens,
+        Token::Assign,
+        parser_state!("global-decl", "=")
+    );
+
+    let value = production!(
+        piped_expr(tokens, &[Delimiter::Semi]),
+        parser_state!("global-decl", "value")
+    );
+
+    let (semi_loc, _) = consume_token!(
+        tokens,
+        Token::Semi,
+        parser_state!("global-decl", "semicolon")
+    );
+
+    let global = AstNode::new(
+        Global {
+            name: AstNode::new(name, name_loc),
+            global_type,
+            value,
+            public,
+        },
+        LocationSpan::combine(global_loc, semi_loc),
+    );
+
+    if is_const {
+        Ok(DeclStmt::Const(global))
+    } else {
+        Ok(DeclStmt::Static(global))
+    }
+}
+
+fn type_alias_decl(
+    tokens: &mut BufferedTokenizer,
+    public: bool,
+) -> ParseErr<AstNode<TypeAlias>> {
+    let (type_loc, _) = consume_token!(
+        tokens,
+        Token::Type,
+        parser_state!("type-alias-decl", "type")
+    );
+
+    let (name_loc, name) = consume_token!(tokens,
+                                          Token::Identifier(i) => Ident(i),
+                                          parser_state!("type-alias-decl", "name"));
+
+    let type_params = if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::LParen => true,
+            _ => false,
+        },
+        parser_state!("type-alias-decl", "type-parameters?")
+    ) {
+        Some(production!(
+            type_param_list(tokens),
+            parser_state!("type-alias-decl", "type-parameters")
+        ))
+    } else {
+        None
+    };
+
+    let _assign = consume_token!(
+        tokens,
+        Token::Assign,
+        parser_state!("type-alias-decl", "=")
+    );
+
+    let aliased = production!(
+        type_annotation(tokens),
+        parser_state!("type-alias-decl", "aliased type")
+    );
+
+    let (semi_loc, _) = consume_token!(
+        tokens,
+        Token::Semi,
+        parser_state!("type-alias-decl", "semicolon")
+    );
+
+    Ok(AstNode::new(
+        TypeAlias {
+            name: AstNode::new(name, name_loc),
+            type_params,
+            aliased,
+            public,
+        },
+        LocationSpan::combine(type_loc, semi_loc),
+    ))
+}
+
+fn trait_decl(tokens: &mut BufferedTokenizer, public: bool) -> ParseErr<AstNode<Trait>> {
+    let (trait_loc, _) = consume_token!(
+        tokens,
+        Token::Trait,
+        parser_state!("trait-decl", "trait")
+    );
+
+    let (name_loc, name) = consume_token!(tokens,
+                                          Token::Identifier(i) => Ident(i),
+                                          parser_state!("trait-decl", "name"));
+
+    let _lbrace = consume_token!(
+        tokens,
+        Token::LBrace,
+        parser_state!("trait-decl", "lbrace")
+    );
+
+    let mut methods = Vec::new();
+    while peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::Fn => true,
+            _ => false,
+        },
+        parser_state!("trait-decl", "method?")
+    ) {
+        methods.push(production!(
+            trait_method(tokens),
+            parser_state!("trait-decl", "method")
+        ));
+    }
+
+    let (rloc, _) = consume_token!(
+        tokens,
+        Token::RBrace,
+        parser_state!("trait-decl", "rbrace")
+    );
+
+    Ok(AstNode::new(
+        Trait {
+            name: AstNode::new(name, name_loc),
+            methods,
+            public,
+        },
+        LocationSpan::combine(trait_loc, rloc),
+    ))
+}
+
+///
+/// A method signature within a trait. `self` is typed as `Self`.
+///
+fn trait_method(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<TraitMethod>> {
+    let (fn_loc, _) = consume_token!(
+        tokens,
+        Token::Fn,
+        parser_state!("trait-method", "fn")
+    );
+
+    let (name_loc, name) = consume_token!(tokens,
+                                          Token::Identifier(i) => Ident(i),
+                                          parser_state!("trait-method", "name"));
+
+    let _lparen = consume_token!(
+        tokens,
+        Token::LParen,
+        parser_state!("trait-method", "parameter lparen")
+    );
+
+    let self_type = AstNode::new(
+        TypeAnnotation::Path(TypedPath::NillArity(ModulePath(vec![AstNode::new(
+            Ident(TRAIT_SELF.to_string()),
+            name_loc.clone(),
+        )]))),
+        name_loc.clone(),
+    );
+
+    let mut params = vec![production!(
+        self_param(tokens, &self_type),
+        parser_state!("trait-method", "self")
+    )];
+
+    if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::RParen => false,
+            _ => true,
+        },
+        parser_state!("trait-method", "rparen")
+    ) {
+        params.extend(production!(
+            fn_param_list(tokens),
+            parser_state!("trait-method", "fn-param-list")
+        ));
+    }
+
+    let _rparen = consume_token!(
+        tokens,
+        Token::RParen,
+        parser_state!("trait-method", "parameter rparen")
+    );
+
+    let return_type = if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::Arrow => true,
+            _ => false,
+        },
+        parser_state!("trait-method", "return type arrow?")
+    ) {
+        let _arrow = consume_token!(
+            tokens,
+            Token::Arrow,
+            parser_state!("trait-method", "return type arrow")
+        );
+        Some(production!(
+            type_annotation(tokens),
+            parser_state!("trait-method", "return type")
+        ))
+    } else {
+        None
+    };
+
+    let (semi_loc, _) = consume_token!(
+        tokens,
+        Token::Semi,
+        parser_state!("trait-method", "semicolon")
+    );
+
+    Ok(AstNode::new(
+        TraitMethod {
+            name: AstNode::new(name, name_loc),
+            params,
+            return_type,
+        },
+        LocationSpan::combine(fn_loc, semi_loc),
+    ))
+}
+
+///
+/// If `self_type` is provided, the function is a method and its first parameter must be `self`.
+///
 fn fn_decl(
     tokens: &mut BufferedTokenizer,
     annotations: Vec<Annotation>,
+    public: bool,
     is_builtin: bool,
+    self_type: Option<&AstNode<TypeAnnotation>>,
 ) -> ParseErr<DeclStmt> {
     let mut span = Span::dummy();
     if is_builtin {
@@ -385,6 +1029,14 @@ fn fn_decl(
         None
     };
 
+    let self_param = match self_type {
+        Some(self_type) => Some(production!(
+            self_param(tokens, self_type),
+            parser_state!("fn-decl", "self")
+        )),
+        None => None,
+    };
+
     let params = if peek_token!(
         tokens,
         |tok| match tok {
@@ -417,6 +1069,23 @@ fn fn_decl(
         }
     };
 
+    let params = match (self_param, params) {
+        (None, params) => params,
+
+        (Some(self_param), BuiltinFnParams::Checked(params)) => {
+            let mut all = vec![self_param];
+            all.extend(params.into_iter().flatten());
+            BuiltinFnParams::Checked(Some(all))
+        }
+
+        (Some(_), BuiltinFnParams::Unchecked) => {
+            return Err(parser_error!(
+                ParserErrorKind::UnexpectedToken(Token::Unchecked),
+                parser_state!("fn-decl", "method-params")
+            ));
+        }
+    };
+
     let (rloc, _) = consume_token!(
         tokens,
         Token::RParen,
@@ -483,6 +1152,7 @@ fn fn_decl(
                 annotations: annotations,
                 type_params: type_params,
                 where_clause: where_clause,
+                public: public,
             },
             span,
         )))
@@ -516,12 +1186,52 @@ fn fn_decl(
                 annotations: annotations,
                 type_params: type_params,
                 where_clause: where_clause,
+                public: public,
             },
             span,
         )))
     }
 }
 
+fn self_param(
+    tokens: &mut BufferedTokenizer,
+    self_type: &AstNode<TypeAnnotation>,
+) -> ParseErr<AstNode<FnParameter>> {
+    // 'self' is not reserved
+    let (self_span, self_ident) = consume_token!(tokens,
+                                                 Token::Identifier(ident) => ident,
+                                                 parser_state!("self-param", "self"));
+    if self_ident != "self" {
+        return Err(parser_error!(
+            ParserErrorKind::UnexpectedToken(Token::Identifier(self_ident)),
+            parser_state!("self-param", "self"),
+            Some(self_span)
+        ));
+    }
+
+    if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::Comma => true,
+            _ => false,
+        },
+        parser_state!("self-param", "comma?")
+    ) {
+        let _comma = consume_token!(
+            tokens,
+            Token::Comma,
+            parser_state!("self-param", "comma")
+        );
+    }
+
+    let param = FnParameter {
+        name: AstNode::new(Ident(self_ident), self_span.clone()),
+        param_type: self_type.clone(),
+    };
+
+    Ok(AstNode::new(param, self_span))
+}
+
 pub fn fn_param_list(
     tokens: &mut BufferedTokenizer,
 ) -> ParseErr<Vec<AstNode<FnParameter>>> {
@@ -591,13 +1301,14 @@ fn fn_param(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<FnParameter>> {
 
 #[cfg(test)]
 pub fn teststruct_decl(tokens: &mut BufferedTokenizer) -> ParseErr<Struct> {
-    let decl = struct_decl(tokens, vec![])?.to_data().0;
+    let decl = struct_decl(tokens, vec![], false)?.to_data().0;
     Ok(decl)
 }
 
 fn opaque_decl(
     tokens: &mut BufferedTokenizer,
     anns: Vec<Annotation>,
+    public: bool,
 ) -> ParseErr<AstNode<Opaque>> {
     let (opaque_loc, _) = consume_token!(
         tokens,
@@ -652,6 +1363,7 @@ fn opaque_decl(
             annotations: anns,
             type_params: type_params,
             where_clause: where_clause,
+            public: public,
         },
         overall_span,
     ))
@@ -660,6 +1372,7 @@ fn opaque_decl(
 fn struct_decl(
     tokens: &mut BufferedTokenizer,
     anns: Vec<Annotation>,
+    public: bool,
 ) -> ParseErr<AstNode<Struct>> {
     let (struct_loc, _) = consume_token!(
         tokens,
@@ -741,6 +1454,7 @@ fn struct_decl(
             annotations: anns,
             type_params: type_params,
             where_clause: where_clause,
+            public: public,
         },
         overall_span,
     ))
@@ -804,9 +1518,32 @@ fn struct_field(tokens: &mut BufferedTokenizer) -> ParseErr<StructField> {
         parser_state!("struct-field", "type annotation")
     );
 
+    let default = if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::Assign => true,
+            _ => false,
+        },
+        parser_state!("struct-field", "default?")
+    ) {
+        let _assign = consume_token!(
+            tokens,
+            Token::Assign,
+            parser_state!("struct-field", "default =")
+        );
+
+        Some(production!(
+            piped_expr(tokens, &[Delimiter::Comma]),
+            parser_state!("struct-field", "default")
+        ))
+    } else {
+        None
+    };
+
     Ok(StructField {
         name: AstNode::new(ident, idloc),
         field_type: ann,
+        default: default,
     })
 }
 
@@ -815,8 +1552,32 @@ fn module_decl(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Ident>> {
     let (modloc, _) =
         consume_token!(tokens, Token::Mod, parser_state!("mod-decl", "mod"));
     let (_idloc, ident) = consume_token!(tokens, 
-                                         Token::Identifier(i) => Ident(i),
+                                         Token::Identifier(i) => i,
                                          parser_state!("mod-decl", "name"));
+
+    // Nested module names are joined by '::'
+    let mut name = ident;
+    while peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::ColonColon => true,
+            _ => false,
+        },
+        parser_state!("mod-decl", "coloncolon?")
+    ) {
+        let _coloncolon = consume_token!(
+            tokens,
+            Token::ColonColon,
+            parser_state!("mod-decl", "coloncolon")
+        );
+        let (_segloc, segment) = consume_token!(tokens,
+                                                Token::Identifier(i) => i,
+                                                parser_state!("mod-decl", "segment"));
+        name.push_str("::");
+        name.push_str(&segment);
+    }
+
+    let ident = Ident(name);
     let (semiloc, _) = consume_token!(
         tokens,
         Token::Semi,
@@ -834,6 +1595,8 @@ pub fn type_annotation(
         Module,
         FnType,
         ArrayType,
+        RefType,
+        TupleType,
         WidthConstraint,
         Err,
     }
@@ -842,8 +1605,10 @@ pub fn type_annotation(
         tokens,
         |tok| match tok {
             Token::Fn => TypeAnnDec::FnType,
+            Token::Ref => TypeAnnDec::RefType,
             Token::Identifier(_) => TypeAnnDec::Module,
             Token::LBracket => TypeAnnDec::ArrayType,
+            Token::LParen => TypeAnnDec::TupleType,
             Token::Base => TypeAnnDec::WidthConstraint,
             Token::LBrace => TypeAnnDec::WidthConstraint,
 
@@ -889,6 +1654,16 @@ pub fn type_annotation(
             parser_state!("type-annotation", "array-type")
         )),
 
+        TypeAnnDec::RefType => Ok(production!(
+            ref_type(tokens),
+            parser_state!("type-annotation", "ref-type")
+        )),
+
+        TypeAnnDec::TupleType => Ok(production!(
+            tuple_type(tokens),
+            parser_state!("type-annotation", "tuple-type")
+        )),
+
         TypeAnnDec::WidthConstraint => {
             let constraints = production!(
                 width_constraint_list(tokens),
@@ -1056,6 +1831,28 @@ pub fn module_binding(
     Ok(AstNode::new(ModulePath(path), binding_span))
 }
 
+fn ref_type(
+    tokens: &mut BufferedTokenizer,
+) -> ParseErr<AstNode<TypeAnnotation>> {
+    let (refloc, _) = consume_token!(
+        tokens,
+        Token::Ref,
+        parser_state!("ref-type", "ref")
+    );
+    let (base_type, base_span) = production!(
+        type_annotation(tokens),
+        parser_state!("ref-type", "base-type")
+    )
+    .to_data();
+
+    let span = LocationSpan::combine(refloc, base_span.clone());
+
+    Ok(AstNode::new(
+        TypeAnnotation::Ref(Box::new(AstNode::new(base_type, base_span))),
+        span,
+    ))
+}
+
 fn array_type(
     tokens: &mut BufferedTokenizer,
 ) -> ParseErr<AstNode<TypeAnnotation>> {
@@ -1068,14 +1865,36 @@ fn array_type(
         type_annotation(tokens),
         parser_state!("array-type", "base-type")
     ));
-    let _semi = consume_token!(
+    // `[T]` is a growable array; `[T; N]` has a fixed size
+    let size = if peek_token!(
         tokens,
-        Token::Semi,
-        parser_state!("array-type", "semicolon")
-    );
-    let (_, number) = consume_token!(tokens, 
-                                     Token::IntLiteral(i) => i,
-                                     parser_state!("array-type", "array size"));
+        |tok| match tok {
+            Token::Semi => true,
+            _ => false,
+        },
+        parser_state!("array-type", "fixed-size?")
+    ) {
+        let _semi = consume_token!(
+            tokens,
+            Token::Semi,
+            parser_state!("array-type", "semicolon")
+        );
+        let (_, number) = consume_token!(tokens, 
+                                         Token::IntLiteral(i) => i,
+                                         parser_state!("array-type", "array size"));
+
+        if number <= 0 {
+            unimplemented!(
+                "Parser error: number of elements must be greater than 0. Found {}",
+                number
+            );
+        }
+
+        Some(number as u64)
+    } else {
+        None
+    };
+
     let (rloc, _) = consume_token!(
         tokens,
         Token::RBracket,
@@ -1084,16 +1903,66 @@ fn array_type(
 
     let array_type_span = LocationSpan::combine(lloc, rloc);
 
-    if number <= 0 {
-        unimplemented!(
-            "Parser error: number of elements must be greater than 0. Found {}",
-            number
-        );
+    Ok(AstNode::new(
+        TypeAnnotation::Array(base_type, size),
+        array_type_span,
+    ))
+}
+
+fn tuple_type(
+    tokens: &mut BufferedTokenizer,
+) -> ParseErr<AstNode<TypeAnnotation>> {
+    let (lloc, _) = consume_token!(
+        tokens,
+        Token::LParen,
+        parser_state!("tuple-type", "lparen")
+    );
+
+    let mut elements = Vec::new();
+    loop {
+        elements.push(production!(
+            type_annotation(tokens),
+            parser_state!("tuple-type", "element-type")
+        ));
+
+        if peek_token!(
+            tokens,
+            |tok| match tok {
+                Token::Comma => true,
+                _ => false,
+            },
+            parser_state!("tuple-type", "comma separator?")
+        ) {
+            let _comma = consume_token!(
+                tokens,
+                Token::Comma,
+                parser_state!("tuple-type", "comma separator")
+            );
+
+            if peek_token!(
+                tokens,
+                |tok| match tok {
+                    Token::RParen => false,
+                    _ => true,
+                },
+                parser_state!("tuple-type", "rparen?")
+            ) {
+                continue;
+            }
+        }
+
+        break;
     }
 
+    let (rloc, _) = consume_token!(
+        tokens,
+        Token::RParen,
+        parser_state!("tuple-type", "rparen")
+    );
+
     Ok(AstNode::new(
-        TypeAnnotation::Array(base_type, number as u64),
-        array_type_span,
+        TypeAnnotation::Tuple(elements),
+        LocationSpan::combine(lloc, rloc),
     ))
 }
 
@@ -1257,22 +2126,256 @@ pub fn block(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Block>> {
     Ok(AstNode::new(block, span))
 }
 
+///
+/// Contents of a block, including an optional trailing value
+///
+struct BlockBody {
+    stmts: Vec<Stmt>,
+    value: Option<AstNode<Expr>>,
+    span: LocationSpan,
+}
+
+impl BlockBody {
+    fn into_block(self, state: ParserState) -> ParseErr<AstNode<Block>> {
+        match self.value {
+            Some(value) => Err(parser_error!(
+                ParserErrorKind::UnexpectedValue,
+                state,
+                Some(value.span())
+            )),
+
+            None => Ok(AstNode::new(Block(self.stmts), self.span)),
+        }
+    }
+
+    fn into_block_expr(self, state: ParserState) -> ParseErr<AstNode<BlockExpr>> {
+        match self.value {
+            Some(value) => {
+                let block = BlockExpr {
+                    stmts: self.stmts,
+                    value: Box::new(value),
+                };
+
+                Ok(AstNode::new(block, self.span))
+            }
+
+            None => {
+                // Every branch assigns the hidden result local, so a diverging branch has
+                //   nothing to assign
+                let diverges = match self.stmts.last() {
+                    Some(Stmt::ExprStmt(stmt)) => match *stmt.data() {
+                        ExprStmt::Return(..) | ExprStmt::Break(..) | ExprStmt::Continue(..) => {
+                            true
+                        }
+                        _ => false,
+                    },
+                    _ => false,
+                };
+
+                let kind = if diverges {
+                    ParserErrorKind::DivergingValueBlock
+                } else {
+                    ParserErrorKind::MissingValue
+                };
+
+                Err(parser_error!(kind, state, Some(self.span)))
+            }
+        }
+    }
+}
+
+///
+/// `{ stmt; ... value }` where the value is optional
+///
+/// The value is an expression statement directly followed by '}'.
+///
+fn block_body(tokens: &mut BufferedTokenizer) -> ParseErr<BlockBody> {
+    let (lloc, _) = consume_token!(
+        tokens,
+        Token::LBrace,
+        parser_state!("block-body", "lbrace")
+    );
+
+    let mut stmts = Vec::new();
+    let mut value = None;
+    while peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::RBrace => false,
+            _ => true,
+        },
+        parser_state!("block-body", "rbrace")
+    ) {
+        match production!(block_item(tokens), parser_state!("block-body", "stmt")) {
+            BlockItem::Stmt(stmt) => stmts.push(stmt),
+            BlockItem::Value(expr) => {
+                value = Some(expr);
+                break;
+            }
+        }
+    }
+
+    let (rloc, _) = consume_token!(
+        tokens,
+        Token::RBrace,
+        parser_state!("block-body", "rbrace")
+    );
+
+    Ok(BlockBody {
+        stmts,
+        value,
+        span: LocationSpan::combine(lloc, rloc),
+    })
+}
+
+///
+/// `{ stmt; ... value }`
+///
+fn block_expr(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<BlockExpr>> {
+    let body =
+        production!(block_body(tokens), parser_state!("block-expr", "body"));
+
+    body.into_block_expr(parser_state!("block-expr", "value"))
+}
+
+///
+/// An expression in a value position (i.e. a `let` initializer). Also accepts `if` and block
+/// expressions.
+///
+fn value_expr(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Expr>> {
+    enum ValueDec {
+        If,
+        Block,
+        Loop,
+        Expr,
+    }
+
+    match peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::If => ValueDec::If,
+            Token::LBrace => ValueDec::Block,
+            Token::Loop | Token::Label(_) => ValueDec::Loop,
+            _ => ValueDec::Expr,
+        },
+        parser_state!("value-expr", "kind")
+    ) {
+        ValueDec::If => {
+            let if_expr =
+                production!(if_expr(tokens), parser_state!("value-expr", "if"));
+            let span = if_expr.span();
+
+            Ok(AstNode::new(Expr::If(if_expr), span))
+        }
+
+        ValueDec::Block => {
+            let block = production!(
+                block_expr(tokens),
+                parser_state!("value-expr", "block")
+            );
+            let span = block.span();
+
+            Ok(AstNode::new(Expr::Block(block), span))
+        }
+
+        ValueDec::Loop => {
+            let label = if peek_token!(
+                tokens,
+                |tok| match tok {
+                    Token::Label(_) => true,
+                    _ => false,
+                },
+                parser_state!("value-expr", "label?")
+            ) {
+                Some(production!(
+                    loop_label(tokens),
+                    parser_state!("value-expr", "label")
+                ))
+            } else {
+                None
+            };
+
+            let loop_expr = production!(
+                loop_expr(tokens, label),
+                parser_state!("value-expr", "loop")
+            );
+            let span = loop_expr.span();
+
+            Ok(AstNode::new(Expr::Loop(loop_expr), span))
+        }
+
+        ValueDec::Expr => Ok(production!(
+            piped_expr(tokens, &[Delimiter::Semi]),
+            parser_state!("value-expr", "expr")
+        )),
+    }
+}
+
+/// A statement in a block, or the trailing value of a block expression
+enum BlockItem {
+    Stmt(Stmt),
+    Value(AstNode<Expr>),
+}
+
+///
+/// Ends an expression statement with ';'. An expression directly followed by '}' is the value
+/// of a block expression instead.
+///
+fn expr_stmt_end(
+    tokens: &mut BufferedTokenizer,
+    expr: AstNode<Expr>,
+    state: &str,
+) -> ParseErr<BlockItem> {
+    if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::RBrace => true,
+            _ => false,
+        },
+        parser_state!(state, "value?")
+    ) {
+        return Ok(BlockItem::Value(expr));
+    }
+
+    let _semi =
+        consume_token!(tokens, Token::Semi, parser_state!(state, "semicolon"));
+
+    Ok(BlockItem::Stmt(Stmt::Expr(expr)))
+}
+
 #[cfg(test)]
 pub fn teststmt(tokens: &mut BufferedTokenizer) -> ParseErr<Stmt> {
     stmt(tokens)
 }
 
 fn stmt(tokens: &mut BufferedTokenizer) -> ParseErr<Stmt> {
+    match block_item(tokens)? {
+        BlockItem::Stmt(stmt) => Ok(stmt),
+
+        // Only block expressions end with a value
+        BlockItem::Value(value) => Err(parser_error!(
+            ParserErrorKind::UnexpectedValue,
+            parser_state!("stmt", "value"),
+            Some(value.span())
+        )),
+    }
+}
+
+fn block_item(tokens: &mut BufferedTokenizer) -> ParseErr<BlockItem> {
     enum StmtDec {
         Continue,
         Break,
         Return,
 
         While,
+        Loop,
+        For,
         If,
+        Labeled,
 
         LocalVar,
         PotentialAssign,
+        DerefAssign,
 
         Expr,
     }
@@ -1285,71 +2388,227 @@ fn stmt(tokens: &mut BufferedTokenizer) -> ParseErr<Stmt> {
             Token::Return => StmtDec::Return,
 
             Token::While => StmtDec::While,
+            Token::Loop => StmtDec::Loop,
+            Token::For => StmtDec::For,
             Token::If => StmtDec::If,
+            Token::Label(_) => StmtDec::Labeled,
 
             Token::Let => StmtDec::LocalVar,
 
             Token::Identifier(_) => StmtDec::PotentialAssign,
+            Token::Star => StmtDec::DerefAssign,
 
             _ => StmtDec::Expr,
         },
         parser_state!("stmt", "stmt kind")
     ) {
-        StmtDec::Continue => Stmt::ExprStmt(production!(
+        StmtDec::Continue => BlockItem::Stmt(Stmt::ExprStmt(production!(
             continue_stmt(tokens),
             parser_state!("stmt", "continue")
-        )),
+        ))),
 
-        StmtDec::Break => Stmt::ExprStmt(production!(
+        StmtDec::Break => BlockItem::Stmt(Stmt::ExprStmt(production!(
             break_stmt(tokens),
             parser_state!("stmt", "break")
-        )),
+        ))),
 
-        StmtDec::Return => Stmt::ExprStmt(production!(
+        StmtDec::Return => BlockItem::Stmt(Stmt::ExprStmt(production!(
             return_stmt(tokens),
             parser_state!("stmt", "return")
-        )),
+        ))),
 
-        StmtDec::While => Stmt::ExprStmt(production!(
-            while_stmt(tokens),
+        StmtDec::While => BlockItem::Stmt(Stmt::ExprStmt(production!(
+            while_stmt(tokens, None),
             parser_state!("stmt", "while")
-        )),
+        ))),
 
-        StmtDec::If => Stmt::ExprStmt(production!(
-            if_stmt(tokens),
+        StmtDec::Loop => BlockItem::Stmt(Stmt::ExprStmt(production!(
+            loop_stmt(tokens, None),
+            parser_state!("stmt", "loop")
+        ))),
+
+        StmtDec::For => BlockItem::Stmt(Stmt::ExprStmt(production!(
+            for_stmt(tokens, None),
+            parser_state!("stmt", "for")
+        ))),
+
+        StmtDec::Labeled => BlockItem::Stmt(Stmt::ExprStmt(production!(
+            labeled_loop_stmt(tokens),
+            parser_state!("stmt", "labeled-loop")
+        ))),
+
+        StmtDec::If => production!(
+            if_item(tokens),
             parser_state!("stmt", "if")
-        )),
+        ),
 
-        StmtDec::LocalVar => Stmt::ExprStmt(production!(
+        StmtDec::LocalVar => BlockItem::Stmt(Stmt::ExprStmt(production!(
             local_var_decl(tokens),
             parser_state!("stmt", "local-var-decl")
-        )),
+        ))),
 
         StmtDec::PotentialAssign => production!(
             potential_assign(tokens),
             parser_state!("stmt", "potential-assign")
         ),
 
+        StmtDec::DerefAssign => BlockItem::Stmt(production!(
+            deref_assign(tokens),
+            parser_state!("stmt", "deref-assign")
+        )),
+
         StmtDec::Expr => {
             let expr = production!(
                 piped_expr(tokens, &[Delimiter::Semi]),
                 parser_state!("stmt", "stmt-expr")
             );
 
-            let _semi = consume_token!(
-                tokens,
-                Token::Semi,
-                parser_state!("stmt-expr", "semicolon")
-            );
+            production!(
+                expr_stmt_end(tokens, expr, "stmt-expr"),
+                parser_state!("stmt", "stmt-expr")
+            )
+        }
+    };
+
+    Ok(stmt)
+}
+
+///
+/// `*ident = expr;` or `*ident.field = expr;`
+///
+/// Also accepts compound assignment operators (i.e. `*ident += expr;`).
+///
+fn deref_assign(tokens: &mut BufferedTokenizer) -> ParseErr<Stmt> {
+    let (star_span, _) = consume_token!(
+        tokens,
+        Token::Star,
+        parser_state!("deref-assign", "star")
+    );
+
+    let (base_span, base_ident) = consume_token!(tokens,
+                                                 Token::Identifier(ident) => Ident(ident),
+                                                 parser_state!("deref-assign", "root"));
+
+    let root = PathSegment::Ident(AstNode::new(base_ident, base_span.clone()));
+
+    let path = if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::Dot => true,
+            _ => false,
+        },
+        parser_state!("deref-assign", "is-access-path")
+    ) {
+        let (path, _span) = production!(
+            access_path(tokens, root),
+            parser_state!("deref-assign", "access-path")
+        )
+        .to_data();
+
+        match path {
+            Expr::FieldAccess(path) => path,
+            _ => unreachable!(),
+        }
+    } else {
+        AstNode::new(Path(vec![root]), base_span)
+    };
+
+    let op = production!(
+        assign_op(tokens),
+        parser_state!("deref-assign", "assign-op")
+    );
+
+    let (value, value_span) = production!(
+        assign_value(tokens, &op),
+        parser_state!("deref-assign", "value")
+    )
+    .to_data();
+
+    let _semi = consume_token!(
+        tokens,
+        Token::Semi,
+        parser_state!("deref-assign", "semicolon")
+    );
+
+    let span = Span::combine(star_span, value_span);
+
+    Ok(Stmt::ExprStmt(AstNode::new(
+        assignment(path, op, value, true),
+        span,
+    )))
+}
+
+///
+/// `=`, or a compound assignment operator (i.e. `+=`) and its binary operator
+///
+fn assign_op(tokens: &mut BufferedTokenizer) -> ParseErr<Option<BinOp>> {
+    let (span, token) = consume_token!(tokens, parser_state!("assign-op", "op"));
+
+    match token {
+        Token::Assign => Ok(None),
+
+        token => match compound_assign_op(&token) {
+            Some(op) => Ok(Some(op)),
+
+            None => Err(parser_error!(
+                ParserErrorKind::UnexpectedToken(token),
+                parser_state!("assign-op", "op"),
+                Some(span)
+            )),
+        },
+    }
+}
+
+fn is_assign_op(token: &Token) -> bool {
+    match token {
+        Token::Assign => true,
+        token => compound_assign_op(token).is_some(),
+    }
+}
+
+fn compound_assign_op(token: &Token) -> Option<BinOp> {
+    match token {
+        Token::PlusAssign => Some(BinOp::Add),
+        Token::MinusAssign => Some(BinOp::Sub),
+        Token::StarAssign => Some(BinOp::Mul),
+        Token::SlashAssign => Some(BinOp::Div),
+        Token::PercentAssign => Some(BinOp::Mod),
+
+        _ => None,
+    }
+}
 
-            Stmt::Expr(expr)
-        }
-    };
+///
+/// Plain assignments accept `if` and block expressions as values
+///
+fn assign_value(
+    tokens: &mut BufferedTokenizer,
+    op: &Option<BinOp>,
+) -> ParseErr<AstNode<Expr>> {
+    match op {
+        Some(_) => piped_expr(tokens, &[Delimiter::Semi]),
+        None => value_expr(tokens),
+    }
+}
 
-    Ok(stmt)
+fn assignment(name: AstNode<Path>, op: Option<BinOp>, value: Expr, deref: bool) -> ExprStmt {
+    match op {
+        Some(op) => ExprStmt::CompoundAssignment(CompoundAssignment {
+            name,
+            op,
+            value,
+            deref,
+        }),
+
+        None => ExprStmt::Assignment(Assignment {
+            name,
+            value,
+            deref,
+        }),
+    }
 }
 
-fn potential_assign(tokens: &mut BufferedTokenizer) -> ParseErr<Stmt> {
+fn potential_assign(tokens: &mut BufferedTokenizer) -> ParseErr<BlockItem> {
     enum Dec {
         AccessPath,
         ModulePath,
@@ -1379,7 +2638,7 @@ fn potential_assign(tokens: &mut BufferedTokenizer) -> ParseErr<Stmt> {
             Token::ColonColon => Dec::ModulePath,
             Token::LParen => Dec::FnCallOrTypeArgFnCall,
             Token::LBracket => Dec::Indexing,
-            Token::Assign => Dec::DefSingletonAssignment,
+            tok if is_assign_op(tok) => Dec::DefSingletonAssignment,
             _ => Dec::DefSingletonExpr,
         },
         parser_state!("potential-assign", "lhs kind")
@@ -1408,19 +2667,17 @@ fn potential_assign(tokens: &mut BufferedTokenizer) -> ParseErr<Stmt> {
                 expr_module_path(tokens, base_ident, base_span),
                 parser_state!("stmt-expr-module-path", "module-path")
             );
+            let path = production!(
+                chained_method_calls(tokens, path),
+                parser_state!("stmt-expr-module-path", "method-calls")
+            );
 
             let expr = production!(
-                prebase_piped_expr(tokens, path, &[Delimiter::Semi]),
+                prebase_expr(tokens, path, &[Delimiter::Semi]),
                 parser_state!("stmt-expr-module-path", "expr")
             );
 
-            let _semi = consume_token!(
-                tokens,
-                Token::Semi,
-                parser_state!("stmt-expr-module-path", "semicolon")
-            );
-
-            return Ok(Stmt::Expr(expr));
+            return expr_stmt_end(tokens, expr, "stmt-expr-module-path");
         }
 
         Dec::FnCallOrTypeArgFnCall => {
@@ -1482,9 +2739,9 @@ fn potential_assign(tokens: &mut BufferedTokenizer) -> ParseErr<Stmt> {
                             TypedPath::Parameterized(path, type_args);
                         let typed_path =
                             Expr::Path(AstNode::new(typed_path, path_span.clone()));
-                        return Ok(Stmt::Expr(AstNode::new(
+                        return Ok(BlockItem::Stmt(Stmt::Expr(AstNode::new(
                             typed_path, path_span,
-                        )));
+                        ))));
                     }
 
                     LocalDec::FnCall => (),
@@ -1528,19 +2785,17 @@ fn potential_assign(tokens: &mut BufferedTokenizer) -> ParseErr<Stmt> {
             let span = Span::combine(base_span, args_span);
             let expr_base = AstNode::new(fn_call, span.clone());
             let expr_base = AstNode::new(Expr::FnCall(expr_base), span);
+            let expr_base = production!(
+                chained_method_calls(tokens, expr_base),
+                parser_state!("stmt-expr-fn-call", "method-calls")
+            );
 
             let expr = production!(
-                prebase_piped_expr(tokens, expr_base, &[Delimiter::Semi]),
+                prebase_expr(tokens, expr_base, &[Delimiter::Semi]),
                 parser_state!("stmt-expr-fn-call", "expr")
             );
 
-            let _semi = consume_token!(
-                tokens,
-                Token::Semi,
-                parser_state!("stmt-expr-fn-call", "semicolon")
-            );
-
-            return Ok(Stmt::Expr(expr));
+            return expr_stmt_end(tokens, expr, "stmt-expr-fn-call");
         }
 
         Dec::Indexing => {
@@ -1600,14 +2855,13 @@ fn potential_assign(tokens: &mut BufferedTokenizer) -> ParseErr<Stmt> {
         }
 
         Dec::DefSingletonAssignment => {
-            let _assignop = consume_token!(
-                tokens,
-                Token::Assign,
-                parser_state!("assignment", "=")
+            let op = production!(
+                assign_op(tokens),
+                parser_state!("assignment", "assign-op")
             );
 
             let value = production!(
-                piped_expr(tokens, &[Delimiter::Semi]),
+                assign_value(tokens, &op),
                 parser_state!("assignment", "value")
             );
             let (value, value_span) = value.to_data();
@@ -1624,45 +2878,58 @@ fn potential_assign(tokens: &mut BufferedTokenizer) -> ParseErr<Stmt> {
             let path = AstNode::new(Path(path), base_span.clone());
 
             let assignment_span = Span::combine(base_span, value_span);
-            let assignment = Assignment {
-                name: path,
-                value: value,
-            };
+            let assignment = assignment(path, op, value, false);
 
-            let assignment = ExprStmt::Assignment(assignment);
-
-            return Ok(Stmt::ExprStmt(AstNode::new(
+            return Ok(BlockItem::Stmt(Stmt::ExprStmt(AstNode::new(
                 assignment,
                 assignment_span,
-            )));
+            ))));
         }
 
         Dec::DefSingletonExpr => {
             // Expecting: expr ';'
             // 'ident op' is not a lvalue
-            let _span = base_span;
+            let span = base_span;
+            let binding = Expr::Binding(AstNode::new(base_ident, span.clone()));
             let expr = production!(
-                piped_expr(tokens, &[Delimiter::Semi]),
+                prebase_expr(tokens, AstNode::new(binding, span), &[Delimiter::Semi]),
                 parser_state!("expr-stmt-singleton", "expr")
             );
 
-            let _semi = consume_token!(
-                tokens,
-                Token::Semi,
-                parser_state!("expr-stmt-singleton", "semicolon")
-            );
-
-            return Ok(Stmt::Expr(expr));
+            return expr_stmt_end(tokens, expr, "expr-stmt-singleton");
         }
     };
 
+    // Method calls are not lvalues
+    if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::LParen => true,
+            _ => false,
+        },
+        parser_state!("potential-assign", "method-call?")
+    ) {
+        let path_span = path.span();
+        let call = production!(
+            method_call(tokens, AstNode::new(Expr::FieldAccess(path), path_span)),
+            parser_state!("stmt-method-call", "method-call")
+        );
+
+        let expr = production!(
+            prebase_expr(tokens, call, &[Delimiter::Semi]),
+            parser_state!("stmt-method-call", "expr")
+        );
+
+        return expr_stmt_end(tokens, expr, "stmt-method-call");
+    }
+
     // Found a full path
     // Check if it's an assignment or expression
 
     match peek_token!(
         tokens,
         |tok| match tok {
-            Token::Assign => PathDec::Assign,
+            tok if is_assign_op(tok) => PathDec::Assign,
             _ => PathDec::Expr,
         },
         parser_state!("full-path-potential-assign", "=;")
@@ -1670,14 +2937,13 @@ fn potential_assign(tokens: &mut BufferedTokenizer) -> ParseErr<Stmt> {
         PathDec::Assign => {
             let path = path;
 
-            let _assign = consume_token!(
-                tokens,
-                Token::Assign,
-                parser_state!("assignment", "=")
+            let op = production!(
+                assign_op(tokens),
+                parser_state!("assignment", "assign-op")
             );
 
             let (value, value_span) = production!(
-                piped_expr(tokens, &[Delimiter::Semi]),
+                assign_value(tokens, &op),
                 parser_state!("assignment", "expr")
             )
             .to_data();
@@ -1689,33 +2955,23 @@ fn potential_assign(tokens: &mut BufferedTokenizer) -> ParseErr<Stmt> {
             );
 
             let span = Span::combine(path.span(), value_span);
-            let assignment = Assignment {
-                name: path,
-                value: value,
-            };
 
-            Ok(Stmt::ExprStmt(AstNode::new(
-                ExprStmt::Assignment(assignment),
+            Ok(BlockItem::Stmt(Stmt::ExprStmt(AstNode::new(
+                assignment(path, op, value, false),
                 span,
-            )))
+            ))))
         }
 
         PathDec::Expr => {
-            let _span = path.span();
-            let _path = Expr::FieldAccess(path);
+            let span = path.span();
+            let path = AstNode::new(Expr::FieldAccess(path), span);
 
             let expr = production!(
-                piped_expr(tokens, &[Delimiter::Semi]),
+                prebase_expr(tokens, path, &[Delimiter::Semi]),
                 parser_state!("stmt-expr-path", "expr")
             );
 
-            let _semi = consume_token!(
-                tokens,
-                Token::Semi,
-                parser_state!("stmt-expr-path", "semicolon")
-            );
-
-            Ok(Stmt::Expr(expr))
+            expr_stmt_end(tokens, expr, "stmt-expr-path")
         }
     }
 }
@@ -1729,10 +2985,26 @@ fn local_var_decl(
         parser_state!("local-var-decl", "let")
     );
 
-    let (iloc, ident) = consume_token!(tokens, 
-                                       Token::Identifier(i) => Ident(i),
-                                       parser_state!("local-var-decl", "name"));
-    let ident = AstNode::new(ident, iloc);
+    let destructure = peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::LParen => true,
+            _ => false,
+        },
+        parser_state!("local-var-decl", "tuple pattern?")
+    );
+
+    let names = if destructure {
+        production!(
+            tuple_pattern(tokens),
+            parser_state!("local-var-decl", "tuple pattern")
+        )
+    } else {
+        let (iloc, ident) = consume_token!(tokens, 
+                                           Token::Identifier(i) => Ident(i),
+                                           parser_state!("local-var-decl", "name"));
+        vec![AstNode::new(ident, iloc)]
+    };
 
     let mut type_anno = None;
 
@@ -1762,7 +3034,7 @@ fn local_var_decl(
     );
 
     let init_value = production!(
-        piped_expr(tokens, &[Delimiter::Semi]),
+        value_expr(tokens),
         parser_state!("local-var-decl", "value")
     )
     .to_data();
@@ -1775,16 +3047,93 @@ fn local_var_decl(
 
     let span = LocationSpan::combine(letloc, semiloc);
 
+    if destructure {
+        let local_tuple_decl = LocalTupleDecl {
+            var_type: type_anno,
+            var_names: names,
+            var_init: init_value.0,
+        };
+
+        return Ok(AstNode::new(
+            ExprStmt::LocalTupleDecl(local_tuple_decl),
+            span,
+        ));
+    }
+
     let local_var_decl = LocalVarDecl {
         var_type: type_anno,
-        var_name: ident,
-        var_init: init_value.0,
+        var_name: names.into_iter().next().unwrap(),
+        var_init: Some(init_value.0),
+        tuple_arity: None,
     };
 
     Ok(AstNode::new(ExprStmt::LocalVarDecl(local_var_decl), span))
 }
 
-fn if_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
+fn tuple_pattern(
+    tokens: &mut BufferedTokenizer,
+) -> ParseErr<Vec<AstNode<Ident>>> {
+    let _lparen = consume_token!(
+        tokens,
+        Token::LParen,
+        parser_state!("tuple-pattern", "lparen")
+    );
+
+    let mut names = Vec::new();
+    loop {
+        let (iloc, ident) = consume_token!(tokens, 
+                                           Token::Identifier(i) => Ident(i),
+                                           parser_state!("tuple-pattern", "name"));
+        names.push(AstNode::new(ident, iloc));
+
+        if peek_token!(
+            tokens,
+            |tok| match tok {
+                Token::Comma => true,
+                _ => false,
+            },
+            parser_state!("tuple-pattern", "comma separator?")
+        ) {
+            let _comma = consume_token!(
+                tokens,
+                Token::Comma,
+                parser_state!("tuple-pattern", "comma separator")
+            );
+
+            if peek_token!(
+                tokens,
+                |tok| match tok {
+                    Token::RParen => false,
+                    _ => true,
+                },
+                parser_state!("tuple-pattern", "rparen?")
+            ) {
+                continue;
+            }
+        }
+
+        break;
+    }
+
+    let _rparen = consume_token!(
+        tokens,
+        Token::RParen,
+        parser_state!("tuple-pattern", "rparen")
+    );
+
+    Ok(names)
+}
+
+///
+/// `if a { ... } elif b { ... } else { ... }` before deciding if it is a statement or a value
+///
+struct IfBody {
+    branches: Vec<(AstNode<Expr>, BlockBody)>,
+    default_block: Option<BlockBody>,
+    span: LocationSpan,
+}
+
+fn if_body(tokens: &mut BufferedTokenizer) -> ParseErr<IfBody> {
     enum IfDec {
         Elif,
         Else,
@@ -1792,18 +3141,16 @@ fn if_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
     }
 
     let (ifloc, _) =
-        consume_token!(tokens, Token::If, parser_state!("if-stmt", "if"));
-
-    let mut end = ifloc.clone();
+        consume_token!(tokens, Token::If, parser_state!("if", "if"));
 
     let first_branch = production!(
         if_branch(tokens),
-        parser_state!("if-stmt", "first branch")
+        parser_state!("if", "first branch")
     );
-    end = first_branch.block.span();
+    let mut end = first_branch.1.span.clone();
 
     let mut branches = vec![first_branch];
-    let mut default_branch = None;
+    let mut default_block = None;
 
     loop {
         match peek_token!(
@@ -1814,20 +3161,20 @@ fn if_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
 
                 _ => IfDec::End,
             },
-            parser_state!("if-stmt", "branches")
+            parser_state!("if", "branches")
         ) {
             IfDec::Elif => {
                 let _elif = consume_token!(
                     tokens,
                     Token::Elif,
-                    parser_state!("if-stmt", "elif")
+                    parser_state!("if", "elif")
                 );
 
                 let branch = production!(
                     if_branch(tokens),
-                    parser_state!("if-stmt", "elif branch")
+                    parser_state!("if", "elif branch")
                 );
-                end = branch.block.span();
+                end = branch.1.span.clone();
 
                 branches.push(branch);
             }
@@ -1836,15 +3183,15 @@ fn if_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
                 let _else = consume_token!(
                     tokens,
                     Token::Else,
-                    parser_state!("if-stmt", "else")
+                    parser_state!("if", "else")
                 );
                 let block = production!(
-                    block(tokens),
-                    parser_state!("if-stmt", "else-block")
+                    block_body(tokens),
+                    parser_state!("if", "else-block")
                 );
 
-                end = block.span();
-                default_branch = Some(block);
+                end = block.span.clone();
+                default_block = Some(block);
 
                 break;
             }
@@ -1853,30 +3200,219 @@ fn if_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
         }
     }
 
-    let span = Span::combine(ifloc, end);
+    Ok(IfBody {
+        branches,
+        default_block,
+        span: LocationSpan::combine(ifloc, end),
+    })
+}
+
+fn if_branch(
+    tokens: &mut BufferedTokenizer,
+) -> ParseErr<(AstNode<Expr>, BlockBody)> {
+    let conditional = piped_expr(tokens, &[Delimiter::LBrace])?;
+
+    let block =
+        production!(block_body(tokens), parser_state!("if-branch", "block"));
+
+    Ok((conditional, block))
+}
+
+///
+/// `if` as a statement. If its blocks end with values, it is the value of a block expression
+/// instead.
+///
+fn if_item(tokens: &mut BufferedTokenizer) -> ParseErr<BlockItem> {
+    let body = production!(if_body(tokens), parser_state!("if-stmt", "if"));
+
+    let has_value = body.branches.iter().any(|(_, block)| block.value.is_some())
+        || body
+            .default_block
+            .as_ref()
+            .is_some_and(|block| block.value.is_some());
+
+    if has_value {
+        let if_expr = production!(
+            if_value(body),
+            parser_state!("if-stmt", "if-expr")
+        );
+        let span = if_expr.span();
+
+        return Ok(BlockItem::Value(AstNode::new(Expr::If(if_expr), span)));
+    }
+
+    let mut branches = Vec::new();
+    for (conditional, block) in body.branches {
+        branches.push(Branch {
+            conditional,
+            block: block.into_block(parser_state!("if-stmt", "branch"))?,
+        });
+    }
+
+    let default_block = match body.default_block {
+        Some(block) => {
+            Some(block.into_block(parser_state!("if-stmt", "else-block"))?)
+        }
+        None => None,
+    };
 
     let if_stmt = If {
-        branches: branches,
-        default_block: default_branch,
+        branches,
+        default_block,
     };
 
-    Ok(AstNode::new(ExprStmt::If(if_stmt), span))
+    Ok(BlockItem::Stmt(Stmt::ExprStmt(AstNode::new(
+        ExprStmt::If(if_stmt),
+        body.span,
+    ))))
 }
 
-fn if_branch(tokens: &mut BufferedTokenizer) -> ParseErr<Branch> {
-    let conditional = piped_expr(tokens, &[Delimiter::LBrace])?;
+///
+/// `if` in a value position
+///
+fn if_expr(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<IfExpr>> {
+    let body = production!(if_body(tokens), parser_state!("if-expr", "if"));
 
-    let block = production!(block(tokens), parser_state!("if-branch", "block"));
+    if_value(body)
+}
 
-    let branch = Branch {
-        conditional: conditional,
-        block: block,
+///
+/// Every block of an `if` expression ends with a value and the 'else' branch is required.
+///
+fn if_value(body: IfBody) -> ParseErr<AstNode<IfExpr>> {
+    let default_block = match body.default_block {
+        Some(block) => {
+            block.into_block_expr(parser_state!("if-expr", "else-block"))?
+        }
+
+        None => {
+            return Err(parser_error!(
+                ParserErrorKind::MissingElse,
+                parser_state!("if-expr", "else"),
+                Some(body.span)
+            ));
+        }
+    };
+
+    let mut branches = Vec::new();
+    for (conditional, block) in body.branches {
+        branches.push(ValueBranch {
+            conditional,
+            block: block.into_block_expr(parser_state!("if-expr", "branch"))?,
+        });
+    }
+
+    let if_expr = IfExpr {
+        branches,
+        default_block,
+    };
+
+    Ok(AstNode::new(if_expr, body.span))
+}
+
+///
+/// `'label:` before a loop
+///
+fn loop_label(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<Ident>> {
+    let (span, label) = consume_token!(tokens,
+                                       Token::Label(label) => Ident(label),
+                                       parser_state!("loop-label", "label"));
+
+    let _colon = consume_token!(
+        tokens,
+        Token::Colon,
+        parser_state!("loop-label", "colon")
+    );
+
+    Ok(AstNode::new(label, span))
+}
+
+fn labeled_loop_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
+    enum LoopDec {
+        While,
+        For,
+        Loop,
+    }
+
+    let label = production!(
+        loop_label(tokens),
+        parser_state!("labeled-loop", "label")
+    );
+
+    match peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::While => LoopDec::While,
+            Token::For => LoopDec::For,
+            _ => LoopDec::Loop,
+        },
+        parser_state!("labeled-loop", "loop kind")
+    ) {
+        LoopDec::While => Ok(production!(
+            while_stmt(tokens, Some(label)),
+            parser_state!("labeled-loop", "while")
+        )),
+
+        LoopDec::For => Ok(production!(
+            for_stmt(tokens, Some(label)),
+            parser_state!("labeled-loop", "for")
+        )),
+
+        LoopDec::Loop => Ok(production!(
+            loop_stmt(tokens, Some(label)),
+            parser_state!("labeled-loop", "loop")
+        )),
+    }
+}
+
+/// The span of a labeled loop starts at its label
+fn loop_span(label: &Option<AstNode<Ident>>, start: Span, end: Span) -> Span {
+    match label {
+        Some(label) => Span::combine(label.span(), end),
+        None => Span::combine(start, end),
+    }
+}
+
+fn loop_stmt(
+    tokens: &mut BufferedTokenizer,
+    label: Option<AstNode<Ident>>,
+) -> ParseErr<AstNode<ExprStmt>> {
+    let (loop_expr, span) = production!(
+        loop_expr(tokens, label),
+        parser_state!("loop-stmt", "loop")
+    )
+    .to_data();
+
+    Ok(AstNode::new(ExprStmt::Loop(loop_expr), span))
+}
+
+fn loop_expr(
+    tokens: &mut BufferedTokenizer,
+    label: Option<AstNode<Ident>>,
+) -> ParseErr<AstNode<Loop>> {
+    let (looploc, _) = consume_token!(
+        tokens,
+        Token::Loop,
+        parser_state!("loop", "loop")
+    );
+
+    let block = production!(block(tokens), parser_state!("loop", "block"));
+
+    let span = loop_span(&label, looploc, block.span());
+
+    let loop_expr = Loop {
+        label,
+        block,
+        value: None,
     };
 
-    Ok(branch)
+    Ok(AstNode::new(loop_expr, span))
 }
 
-fn while_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
+fn while_stmt(
+    tokens: &mut BufferedTokenizer,
+    label: Option<AstNode<Ident>>,
+) -> ParseErr<AstNode<ExprStmt>> {
     let (whileloc, _) = consume_token!(
         tokens,
         Token::While,
@@ -1891,9 +3427,10 @@ fn while_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
     let block =
         production!(block(tokens), parser_state!("while-stmt", "block"));
 
-    let span = Span::combine(whileloc, block.span());
+    let span = loop_span(&label, whileloc, block.span());
 
     let while_stmt = While {
+        label,
         conditional: conditional,
         block: block,
     };
@@ -1901,6 +3438,76 @@ fn while_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
     Ok(AstNode::new(ExprStmt::While(while_stmt), span))
 }
 
+fn for_stmt(
+    tokens: &mut BufferedTokenizer,
+    label: Option<AstNode<Ident>>,
+) -> ParseErr<AstNode<ExprStmt>> {
+    let (forloc, _) = consume_token!(
+        tokens,
+        Token::For,
+        parser_state!("for-stmt", "for")
+    );
+
+    let (var_span, var_name) = consume_token!(tokens,
+                                              Token::Identifier(ident) => Ident(ident),
+                                              parser_state!("for-stmt", "var-name"));
+
+    // 'in' is not reserved
+    let (in_span, in_ident) = consume_token!(tokens,
+                                             Token::Identifier(ident) => ident,
+                                             parser_state!("for-stmt", "in"));
+    if in_ident != "in" {
+        return Err(parser_error!(
+            ParserErrorKind::UnexpectedToken(Token::Identifier(in_ident)),
+            parser_state!("for-stmt", "in"),
+            Some(in_span)
+        ));
+    }
+
+    let iterable = production!(
+        piped_expr(tokens, &[Delimiter::LBrace, Delimiter::DotDot]),
+        parser_state!("for-stmt", "iterable")
+    );
+
+    let iterable = if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::DotDot => true,
+            _ => false,
+        },
+        parser_state!("for-stmt", "range?")
+    ) {
+        let _dotdot = consume_token!(
+            tokens,
+            Token::DotDot,
+            parser_state!("for-stmt", "..")
+        );
+
+        let end = production!(
+            piped_expr(tokens, &[Delimiter::LBrace]),
+            parser_state!("for-stmt", "range-end")
+        );
+
+        ForIterable::Range(iterable, end)
+    } else {
+        ForIterable::Collection(iterable)
+    };
+
+    let block =
+        production!(block(tokens), parser_state!("for-stmt", "block"));
+
+    let span = loop_span(&label, forloc, block.span());
+
+    let for_stmt = For {
+        label,
+        var_name: AstNode::new(var_name, var_span),
+        iterable,
+        block,
+    };
+
+    Ok(AstNode::new(ExprStmt::For(for_stmt), span))
+}
+
 fn return_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
     let (returnloc, _) = consume_token!(
         tokens,
@@ -1930,7 +3537,7 @@ fn return_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
     } else {
         // Expression
         let expr = production!(
-            piped_expr(tokens, &[Delimiter::Semi]),
+            value_expr(tokens),
             parser_state!("return-stmt", "expr")
         );
 
@@ -1952,6 +3559,29 @@ fn return_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
     ))
 }
 
+///
+/// `'label` after `break` or `continue`
+///
+fn target_label(
+    tokens: &mut BufferedTokenizer,
+) -> ParseErr<Option<AstNode<Ident>>> {
+    if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::Label(_) => true,
+            _ => false,
+        },
+        parser_state!("target-label", "label?")
+    ) {
+        let (span, label) = consume_token!(tokens,
+                                           Token::Label(label) => Ident(label),
+                                           parser_state!("target-label", "label"));
+        Ok(Some(AstNode::new(label, span)))
+    } else {
+        Ok(None)
+    }
+}
+
 fn continue_stmt(
     tokens: &mut BufferedTokenizer,
 ) -> ParseErr<AstNode<ExprStmt>> {
@@ -1960,6 +3590,10 @@ fn continue_stmt(
         Token::Continue,
         parser_state!("continue-stmt", "continue")
     );
+    let label = production!(
+        target_label(tokens),
+        parser_state!("continue-stmt", "label")
+    );
     let (semiloc, _) = consume_token!(
         tokens,
         Token::Semi,
@@ -1967,7 +3601,7 @@ fn continue_stmt(
     );
 
     let span = LocationSpan::combine(contloc, semiloc);
-    Ok(AstNode::new(ExprStmt::Continue(span.clone()), span))
+    Ok(AstNode::new(ExprStmt::Continue(span.clone(), label), span))
 }
 
 fn break_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
@@ -1976,6 +3610,27 @@ fn break_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
         Token::Break,
         parser_state!("break-stmt", "break")
     );
+    let label = production!(
+        target_label(tokens),
+        parser_state!("break-stmt", "label")
+    );
+
+    let value = if peek_token!(
+        tokens,
+        |tok| match tok {
+            Token::Semi => false,
+            _ => true,
+        },
+        parser_state!("break-stmt", "value?")
+    ) {
+        Some(production!(
+            value_expr(tokens),
+            parser_state!("break-stmt", "value")
+        ))
+    } else {
+        None
+    };
+
     let (semiloc, _) = consume_token!(
         tokens,
         Token::Semi,
@@ -1983,7 +3638,10 @@ fn break_stmt(tokens: &mut BufferedTokenizer) -> ParseErr<AstNode<ExprStmt>> {
     );
 
     let span = LocationSpan::combine(contloc, semiloc);
-    Ok(AstNode::new(ExprStmt::Break(span.clone()), span))
+    Ok(AstNode::new(
+        ExprStmt::Break(span.clone(), label, value.map(|v| v.to_data().0)),
+        span,
+    ))
 }
 
 fn type_param_list(tokens: &mut BufferedTokenizer) -> ParseErr<TypeParams> {
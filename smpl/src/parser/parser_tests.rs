@@ -109,6 +109,7 @@ init NAME {
             let expected = Expr::StructInit(dummy_node!(StructInit {
                 struct_name: type_path!("NAME"),
                 field_init: Vec::new(),
+                base: None,
             }));
 
             assert_eq!(init_1, expected);
@@ -127,6 +128,7 @@ init NAME {
                     (dummy_node!(ident!("field1")), field_init),
                     (dummy_node!(ident!("field2")), field2_init)
                 ],
+                base: None,
             }));
 
             assert_eq!(init_2, expected);
@@ -806,4 +808,820 @@ opaque Vec(type T, B)
 
         let _mod1 = parse_module(wrap_input!(mod1)).unwrap();
     }
+
+    #[test]
+    fn parse_references() {
+        let mod1 =
+"mod mod1;
+
+fn increment(counter: &int, point: &Point) {
+    *counter = *counter + 1;
+    *point.x = 2 * *counter;
+    let array: &[int; 3] = &a[1];
+}";
+
+        let _mod1 = parse_module(wrap_input!(mod1)).unwrap();
+
+        match parse_stmt_quick("*a = 1;") {
+            Stmt::ExprStmt(stmt) => match stmt.to_data().0 {
+                ExprStmt::Assignment(assignment) => assert!(assignment.deref),
+                s => panic!("Expected an assignment. Found {:?}", s),
+            },
+
+            s => panic!("Expected an assignment. Found {:?}", s),
+        }
+
+        match parse_expr_quick("&a;") {
+            Expr::Uni(uni) => assert_eq!(uni.data().op, UniOp::Ref),
+            e => panic!("Expected a uni expr. Found {:?}", e),
+        }
+    }
+
+    #[test]
+    fn parse_bitwise_expr() {
+        {
+            let input = "1 | 2 ^ 3 & 4 << 5";
+            let e = parse_expr_quick(input);
+            let root = {
+                let _1 = int!(1 => BoxExpr);
+                let _2 = int!(2 => BoxExpr);
+                let _3 = int!(3 => BoxExpr);
+                let _4 = int!(4 => BoxExpr);
+                let _5 = int!(5 => BoxExpr);
+
+                let shift = bin_expr!((_4, BinOp::Shl, _5) => BoxExpr);
+                let and = bin_expr!((_3, BinOp::BitAnd, shift) => BoxExpr);
+                let xor = bin_expr!((_2, BinOp::BitXor, and) => BoxExpr);
+
+                let parent = bin_expr!((_1, BinOp::BitOr, xor) => Expr);
+                parent
+            };
+            assert_eq!(e, root);
+        }
+
+        {
+            // Shifts bind tighter than comparisons, bitwise operators looser
+            let input = "1 >> 2 < 3 & 4";
+            let e = parse_expr_quick(input);
+            let root = {
+                let _1 = int!(1 => BoxExpr);
+                let _2 = int!(2 => BoxExpr);
+                let _3 = int!(3 => BoxExpr);
+                let _4 = int!(4 => BoxExpr);
+
+                let shift = bin_expr!((_1, BinOp::Shr, _2) => BoxExpr);
+                let cmp = bin_expr!((shift, BinOp::Lesser, _3) => BoxExpr);
+
+                let parent = bin_expr!((cmp, BinOp::BitAnd, _4) => Expr);
+                parent
+            };
+            assert_eq!(e, root);
+        }
+
+        match parse_expr_quick("~a & &b;") {
+            Expr::Bin(bin) => {
+                let bin = bin.to_data().0;
+                assert_eq!(bin.op, BinOp::BitAnd);
+                match *bin.lhs {
+                    Expr::Uni(ref uni) => assert_eq!(uni.data().op, UniOp::BitNot),
+                    ref e => panic!("Expected a uni expr. Found {:?}", e),
+                }
+                match *bin.rhs {
+                    Expr::Uni(ref uni) => assert_eq!(uni.data().op, UniOp::Ref),
+                    ref e => panic!("Expected a uni expr. Found {:?}", e),
+                }
+            }
+            e => panic!("Expected a bin expr. Found {:?}", e),
+        }
+    }
+
+    #[test]
+    fn parse_compound_assignment() {
+        let compound = |input: &str| match parse_stmt_quick(input) {
+            Stmt::ExprStmt(stmt) => match stmt.to_data().0 {
+                ExprStmt::CompoundAssignment(assignment) => assignment,
+                s => panic!("Expected a compound assignment. Found {:?}", s),
+            },
+
+            s => panic!("Expected a compound assignment. Found {:?}", s),
+        };
+
+        let assignment = compound("count += 1;");
+        assert_eq!(assignment.op, BinOp::Add);
+        assert_eq!(assignment.name.data().0.len(), 1);
+        assert!(!assignment.deref);
+
+        let assignment = compound("a[i].total -= 2 * b;");
+        assert_eq!(assignment.op, BinOp::Sub);
+        assert_eq!(assignment.name.data().0.len(), 2);
+        match assignment.name.data().0[0] {
+            PathSegment::Indexing(..) => (),
+            ref s => panic!("Expected an indexing segment. Found {:?}", s),
+        }
+        match assignment.value {
+            Expr::Bin(ref bin) => assert_eq!(bin.data().op, BinOp::Mul),
+            ref e => panic!("Expected a bin expr. Found {:?}", e),
+        }
+
+        assert_eq!(compound("values[0] *= 3;").op, BinOp::Mul);
+        assert_eq!(compound("p.x /= 4;").op, BinOp::Div);
+
+        let assignment = compound("*counter %= 5;");
+        assert_eq!(assignment.op, BinOp::Mod);
+        assert!(assignment.deref);
+
+        // Plain assignments are unchanged
+        match parse_stmt_quick("count = count + 1;") {
+            Stmt::ExprStmt(stmt) => match stmt.to_data().0 {
+                ExprStmt::Assignment(..) => (),
+                s => panic!("Expected an assignment. Found {:?}", s),
+            },
+
+            s => panic!("Expected an assignment. Found {:?}", s),
+        }
+    }
+
+    #[test]
+    fn parse_for_loops() {
+        match parse_stmt_quick("for i in 0..len { }") {
+            Stmt::ExprStmt(stmt) => match stmt.to_data().0 {
+                ExprStmt::For(for_data) => {
+                    assert_eq!(for_data.var_name.data().as_str(), "i");
+                    match for_data.iterable {
+                        ForIterable::Range(..) => (),
+                        i => panic!("Expected a range. Found {:?}", i),
+                    }
+                }
+                s => panic!("Expected a for loop. Found {:?}", s),
+            },
+
+            s => panic!("Expected a for loop. Found {:?}", s),
+        }
+
+        match parse_stmt_quick("for x in foo.bar { baz(x); }") {
+            Stmt::ExprStmt(stmt) => match stmt.to_data().0 {
+                ExprStmt::For(for_data) => match for_data.iterable {
+                    ForIterable::Collection(..) => (),
+                    i => panic!("Expected a collection. Found {:?}", i),
+                },
+                s => panic!("Expected a for loop. Found {:?}", s),
+            },
+
+            s => panic!("Expected a for loop. Found {:?}", s),
+        }
+
+        // 'in' remains usable as an identifier
+        let _mod1 = parse_module(wrap_input!("mod mod1; fn foo(in: int) { for x in in..10 { } }"))
+            .unwrap();
+    }
+
+    #[test]
+    fn parse_tuples() {
+        match parse_stmt_quick("let (a, b): (int, String) = (1, \"foo\");") {
+            Stmt::ExprStmt(stmt) => match stmt.to_data().0 {
+                ExprStmt::LocalTupleDecl(decl) => {
+                    let names = decl.var_names
+                        .iter()
+                        .map(|n| n.data().as_str())
+                        .collect::<Vec<_>>();
+                    assert_eq!(names, vec!["a", "b"]);
+
+                    match decl.var_type.unwrap().data() {
+                        TypeAnnotation::Tuple(ref elements) => assert_eq!(elements.len(), 2),
+                        t => panic!("Expected a tuple type. Found {:?}", t),
+                    }
+
+                    match decl.var_init {
+                        Expr::TupleInit(init) => assert_eq!(init.data().elements.len(), 2),
+                        e => panic!("Expected a tuple. Found {:?}", e),
+                    }
+                }
+                s => panic!("Expected a tuple declaration. Found {:?}", s),
+            },
+
+            s => panic!("Expected a tuple declaration. Found {:?}", s),
+        }
+
+        // Parenthesized expressions are not tuples
+        match parse_stmt_quick("(1 + 2);") {
+            Stmt::Expr(expr) => match expr.to_data().0 {
+                Expr::Bin(..) => (),
+                e => panic!("Expected a binary expression. Found {:?}", e),
+            },
+            s => panic!("Expected an expression. Found {:?}", s),
+        }
+
+        match parse_stmt_quick("(1,);") {
+            Stmt::Expr(expr) => match expr.to_data().0 {
+                Expr::TupleInit(init) => assert_eq!(init.data().elements.len(), 1),
+                e => panic!("Expected a tuple. Found {:?}", e),
+            },
+            s => panic!("Expected an expression. Found {:?}", s),
+        }
+
+        match parse_expr_quick("foo.0.1;") {
+            Expr::FieldAccess(path) => {
+                let segments = path
+                    .data()
+                    .0
+                    .iter()
+                    .map(|s| match s {
+                        PathSegment::Ident(i) => i.data().as_str().to_string(),
+                        s => panic!("Expected a field. Found {:?}", s),
+                    })
+                    .collect::<Vec<_>>();
+                assert_eq!(segments, vec!["foo", "0", "1"]);
+            }
+            e => panic!("Expected a field access. Found {:?}", e),
+        }
+    }
+
+    #[test]
+    fn parse_methods() {
+        let input =
+"mod mod1;
+
+impl(type T) Wrapper(type T) {
+    fn get(self) -> T {
+        return self.value;
+    }
+
+    builtin fn set(self, value: T) -> Wrapper(type T);
+}";
+        let ast = parse_module(wrap_input!(input)).unwrap().module;
+        match ast.1[0] {
+            DeclStmt::Impl(ref impl_decl) => {
+                let impl_decl = impl_decl.data();
+                assert!(impl_decl.type_params.is_some());
+                assert_eq!(impl_decl.methods.len(), 2);
+
+                match impl_decl.methods[0] {
+                    Method::Function(ref f) => {
+                        let params = f.data().params.as_ref().unwrap();
+                        assert_eq!(params.len(), 1);
+                        assert_eq!(params[0].data().name.data().as_str(), "self");
+                        assert_eq!(params[0].data().param_type, impl_decl.target);
+                    }
+                    ref m => panic!("Expected a method. Found {:?}", m),
+                }
+
+                match impl_decl.methods[1] {
+                    Method::Builtin(ref f) => match f.data().params {
+                        BuiltinFnParams::Checked(Some(ref params)) => {
+                            assert_eq!(params.len(), 2)
+                        }
+                        ref p => panic!("Expected checked parameters. Found {:?}", p),
+                    },
+                    ref m => panic!("Expected a builtin method. Found {:?}", m),
+                }
+            }
+            _ => panic!("Expected an impl block"),
+        }
+
+        match parse_stmt_quick("p.scale(2).sum();") {
+            Stmt::Expr(expr) => match expr.to_data().0 {
+                Expr::MethodCall(call) => {
+                    let call = call.to_data().0;
+                    assert_eq!(call.method.data().as_str(), "sum");
+                    assert!(call.args.map(|a| a.is_empty()).unwrap_or(true));
+
+                    match *call.receiver {
+                        Expr::MethodCall(inner) => {
+                            let inner = inner.to_data().0;
+                            assert_eq!(inner.method.data().as_str(), "scale");
+                            assert_eq!(inner.args.unwrap().len(), 1);
+                        }
+                        e => panic!("Expected a method call. Found {:?}", e),
+                    }
+                }
+                e => panic!("Expected a method call. Found {:?}", e),
+            },
+            s => panic!("Expected an expression. Found {:?}", s),
+        }
+
+        // Methods can be called on any primary expression
+        for (input, receiver_is_call) in &[
+            ("id(a).show();", true),
+            ("m::id(a).show();", true),
+            ("(x).show();", false),
+            ("let y = id(a).show();", true),
+        ] {
+            let receiver = match parse_stmt_quick(input) {
+                Stmt::Expr(expr) => expr.to_data().0,
+                Stmt::ExprStmt(stmt) => match stmt.to_data().0 {
+                    ExprStmt::LocalVarDecl(decl) => decl.var_init.unwrap(),
+                    s => panic!("Expected a local variable. Found {:?}", s),
+                },
+                s => panic!("Expected an expression. Found {:?}", s),
+            };
+
+            match receiver {
+                Expr::MethodCall(call) => {
+                    let call = call.to_data().0;
+                    assert_eq!(call.method.data().as_str(), "show");
+                    match *call.receiver {
+                        Expr::FnCall(_) => assert!(receiver_is_call, "{}", input),
+                        Expr::Binding(_) => assert!(!receiver_is_call, "{}", input),
+                        e => panic!("Unexpected receiver {:?}", e),
+                    }
+                }
+                e => panic!("Expected a method call. Found {:?}", e),
+            }
+        }
+
+        let source = ModuleSource::Anonymous(None);
+        let mut tokens = buffer_input(&source, "let y = id(a).x;");
+        match teststmt(&mut tokens) {
+            Err(e) => assert!(e.to_string().contains("Fields can only be accessed"), "{}", e),
+            Ok(s) => panic!("Expected a parser error. Found {:?}", s),
+        }
+    }
+
+    #[test]
+    fn parse_traits() {
+        let input =
+"mod mod1;
+
+trait Same {
+    fn same(self, other: Self) -> bool;
+    fn reset(self);
+}
+
+impl Same for Point {
+    fn same(self, other: Point) -> bool {
+        return true;
+    }
+}
+
+fn foo(type T)(a: T) -> bool
+    where T: Same + Score {
+    return a.same(a);
+}";
+        let ast = parse_module(wrap_input!(input)).unwrap().module;
+        match ast.1[0] {
+            DeclStmt::Trait(ref trait_decl) => {
+                let trait_decl = trait_decl.data();
+                assert_eq!(trait_decl.name.data().as_str(), "Same");
+                assert_eq!(trait_decl.methods.len(), 2);
+
+                let same = trait_decl.methods[0].data();
+                assert_eq!(same.params.len(), 2);
+                assert_eq!(same.params[0].data().name.data().as_str(), "self");
+                assert!(same.return_type.is_some());
+                assert!(trait_decl.methods[1].data().return_type.is_none());
+            }
+            _ => panic!("Expected a trait"),
+        }
+
+        match ast.1[1] {
+            DeclStmt::Impl(ref impl_decl) => {
+                let impl_decl = impl_decl.data();
+                let trait_name = impl_decl.trait_name.as_ref().unwrap();
+                assert_eq!(trait_name.data().0[0].data().as_str(), "Same");
+                assert_eq!(impl_decl.methods.len(), 1);
+            }
+            _ => panic!("Expected an impl block"),
+        }
+
+        match ast.1[2] {
+            DeclStmt::Function(ref func) => {
+                let where_clause = func.data().where_clause.as_ref().unwrap();
+                let bounds = where_clause.0.values().next().unwrap();
+                assert_eq!(bounds.len(), 2);
+            }
+            _ => panic!("Expected a function"),
+        }
+    }
+
+    #[test]
+    fn parse_globals() {
+        let input =
+"mod mod1;
+
+const MAX: int = 10 * 2 + 1;
+static COUNTER: int = MAX;";
+        let ast = parse_module(wrap_input!(input)).unwrap().module;
+        match ast.1[0] {
+            DeclStmt::Const(ref global) => {
+                let global = global.data();
+                assert_eq!(global.name.data().as_str(), "MAX");
+                match *global.value.data() {
+                    Expr::Bin(_) => (),
+                    ref e => panic!("Expected a binary expression. Found {:?}", e),
+                }
+            }
+            _ => panic!("Expected a const"),
+        }
+
+        match ast.1[1] {
+            DeclStmt::Static(ref global) => {
+                assert_eq!(global.data().name.data().as_str(), "COUNTER");
+            }
+            _ => panic!("Expected a static"),
+        }
+    }
+
+    #[test]
+    fn parse_use_decls() {
+        let input = "mod game::ai;
+use game::ai::pathing;
+use option as opt;
+use vec::{Vec, push as vec_push,};";
+
+        let source = ModuleSource::Anonymous(None);
+        let mut input = buffer_input(&source, input);
+        let module = module(&mut input).unwrap();
+
+        assert_eq!(module.name(), Some(&ident!("game::ai")));
+
+        let uses: Vec<_> = module
+            .1
+            .into_iter()
+            .map(|decl| match decl {
+                DeclStmt::Use(use_decl) => use_decl.to_data().0,
+                _ => panic!("Expected a use declaration"),
+            })
+            .collect();
+
+        assert_eq!(uses[0].module.data().to_string(), "game::ai::pathing");
+        assert_eq!(uses[0].import, Import::Module(None));
+        assert_eq!(uses[0].prefix().unwrap().data(), &ident!("pathing"));
+
+        assert_eq!(uses[1].module.data().to_string(), "option");
+        assert_eq!(uses[1].prefix().unwrap().data(), &ident!("opt"));
+
+        assert_eq!(uses[2].module.data().to_string(), "vec");
+        match uses[2].import {
+            Import::Items(ref items) => {
+                assert_eq!(items.len(), 2);
+                assert_eq!(items[0].bound_name().data(), &ident!("Vec"));
+                assert_eq!(items[1].name.data(), &ident!("push"));
+                assert_eq!(items[1].bound_name().data(), &ident!("vec_push"));
+            }
+
+            ref i => panic!("Expected selective imports. Found {:?}", i),
+        }
+    }
+
+    #[test]
+    fn parse_pub_decls() {
+        let input = "mod mod1;
+pub struct A { }
+opaque B;
+pub fn foo() { }
+#[test]
+pub builtin fn bar();
+pub trait T { }
+pub const C: int = 1;
+static S: int = 2;";
+
+        let source = ModuleSource::Anonymous(None);
+        let mut input = buffer_input(&source, input);
+        let decls = module(&mut input).unwrap().1;
+
+        let public: Vec<bool> = decls
+            .into_iter()
+            .map(|decl| match decl {
+                DeclStmt::Struct(d) => d.data().public,
+                DeclStmt::Opaque(d) => d.data().public,
+                DeclStmt::Function(d) => d.data().public,
+                DeclStmt::BuiltinFunction(d) => d.data().public,
+                DeclStmt::Trait(d) => d.data().public,
+                DeclStmt::Const(d) | DeclStmt::Static(d) => d.data().public,
+                _ => panic!("Unexpected declaration"),
+            })
+            .collect();
+        assert_eq!(public, vec![true, false, true, true, true, true, false]);
+
+        for input in vec!["pub use mod2;", "pub static S: int = 1;", "pub impl A { }"] {
+            let mut input = buffer_input(&source, input);
+            assert!(module(&mut input).is_err());
+        }
+    }
+
+    #[test]
+    fn parse_type_alias() {
+        let input = "pub type Pair(type T) = (T, T);";
+
+        let source = ModuleSource::Anonymous(None);
+        let mut input = buffer_input(&source, input);
+        let mut decls = module(&mut input).unwrap().1;
+
+        let alias = match decls.remove(0) {
+            DeclStmt::TypeAlias(alias) => alias.to_data().0,
+            _ => panic!("Expected a type alias"),
+        };
+
+        assert_eq!(alias.name.data(), &ident!("Pair"));
+        assert_eq!(alias.type_params.unwrap().params.len(), 1);
+        assert!(alias.public);
+        match alias.aliased.data() {
+            TypeAnnotation::Tuple(elements) => assert_eq!(elements.len(), 2),
+            ann => panic!("Expected a tuple type. Found {:?}", ann),
+        }
+    }
+
+    #[test]
+    fn parse_format_string() {
+        let input = r#"let s = "a{x + 1}b{f(y)}";"#;
+        let init = match parse_stmt_quick(input) {
+            Stmt::ExprStmt(stmt) => match stmt.to_data().0 {
+                ExprStmt::LocalVarDecl(decl) => decl.var_init.unwrap(),
+                s => panic!("Expected a local variable declaration. Found {:?}", s),
+            },
+
+            s => panic!("Expected a local variable declaration. Found {:?}", s),
+        };
+
+        let parts = match init {
+            Expr::Format(format) => format.to_data().0.parts,
+            e => panic!("Expected a format string. Found {:?}", e),
+        };
+
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[0], FormatPart::Text("a".to_string()));
+        assert_eq!(parts[2], FormatPart::Text("b".to_string()));
+        match parts[1] {
+            FormatPart::Value(Expr::Bin(ref bin)) => assert_eq!(bin.data().op, BinOp::Add),
+            ref p => panic!("Expected a bin expr. Found {:?}", p),
+        }
+        match parts[3] {
+            FormatPart::Value(Expr::FnCall(..)) => (),
+            ref p => panic!("Expected a fn call. Found {:?}", p),
+        }
+    }
+
+    #[test]
+    fn parse_if_expr() {
+        let init = |input: &str| match parse_stmt_quick(input) {
+            Stmt::ExprStmt(stmt) => match stmt.to_data().0 {
+                ExprStmt::LocalVarDecl(decl) => decl.var_init.unwrap(),
+                s => panic!("Expected a local variable declaration. Found {:?}", s),
+            },
+
+            s => panic!("Expected a local variable declaration. Found {:?}", s),
+        };
+
+        match init("let x = if a { 1 } elif b { let c = 2; c } else { 3 };") {
+            Expr::If(if_expr) => {
+                let if_expr = if_expr.to_data().0;
+                assert_eq!(if_expr.branches.len(), 2);
+                assert_eq!(if_expr.branches[1].block.data().stmts.len(), 1);
+                assert_eq!(*if_expr.branches[0].block.data().value.data(), int!(1 => Expr));
+                assert_eq!(*if_expr.default_block.data().value.data(), int!(3 => Expr));
+            }
+
+            e => panic!("Expected an if expression. Found {:?}", e),
+        }
+
+        match init("let x = { let y = 1; y + 2 };") {
+            Expr::Block(block) => {
+                let block = block.to_data().0;
+                assert_eq!(block.stmts.len(), 1);
+                match block.value.data() {
+                    Expr::Bin(bin) => assert_eq!(bin.data().op, BinOp::Add),
+                    e => panic!("Expected a bin expr. Found {:?}", e),
+                }
+            }
+
+            e => panic!("Expected a block expression. Found {:?}", e),
+        }
+
+        // Nested 'if' expressions are the value of their block
+        match init("let x = { if a { 1 } else { 2 } };") {
+            Expr::Block(block) => match block.data().value.data() {
+                Expr::If(..) => (),
+                e => panic!("Expected an if expression. Found {:?}", e),
+            },
+
+            e => panic!("Expected a block expression. Found {:?}", e),
+        }
+
+        // 'if' statements are unchanged
+        match parse_stmt_quick("if a { foo(); } else { bar(); }") {
+            Stmt::ExprStmt(stmt) => match stmt.to_data().0 {
+                ExprStmt::If(..) => (),
+                s => panic!("Expected an if statement. Found {:?}", s),
+            },
+
+            s => panic!("Expected an if statement. Found {:?}", s),
+        }
+
+        let source = ModuleSource::Anonymous(None);
+        let mut tokens = buffer_input(&source, "let x = if a { 1 };");
+        assert!(teststmt(&mut tokens).is_err());
+
+        let mut tokens = buffer_input(&source, "let x = { foo(); };");
+        assert!(teststmt(&mut tokens).is_err());
+
+        // Value expressions nested in other expressions are rejected
+        for input in &[
+            "foo(if a { 1 } else { 2 });",
+            "let x = 1 + if a { 1 } else { 2 };",
+            "x += if a { 1 } else { 2 };",
+            "let x = [{ 1 }];",
+        ] {
+            let mut tokens = buffer_input(&source, input);
+            match teststmt(&mut tokens) {
+                Err(e) => assert!(e.to_string().contains("only allowed"), "{}", e),
+                Ok(s) => panic!("Expected a misplaced value expression error. Found {:?}", s),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_value_expr_scope() {
+        let source = ModuleSource::Anonymous(None);
+        let parse = |input: &str| {
+            let mut tokens = buffer_input(&source, input);
+            teststmt(&mut tokens)
+        };
+
+        // Accepted value positions
+        for input in &[
+            "let x = if a { 1 } else { 2 };",
+            "x = { 1 };",
+            "*x = if a { 1 } else { 2 };",
+            "let (x, y) = { (1, 2) };",
+            "return if a { 1 } else { 2 };",
+            "break loop { break 1; };",
+        ] {
+            if let Err(e) = parse(input) {
+                panic!("Failed to parse {:?}: {}", input, e);
+            }
+        }
+
+        // Any other position is rejected
+        for input in &[
+            "foo(1, { 2 });",
+            "a.foo(if a { 1 } else { 2 });",
+            "let x = -if a { 1 } else { 2 };",
+            "let x = (if a { 1 } else { 2 }) * 2;",
+            "let x = a[{ 0 }];",
+            "let x = init Foo { a: { 1 } };",
+            "let x = foo(loop { break 1; });",
+        ] {
+            match parse(input) {
+                Err(e) => assert!(e.to_string().contains("only allowed"), "{}: {}", input, e),
+                Ok(s) => panic!("Expected a misplaced value expression error. Found {:?}", s),
+            }
+        }
+
+        // Every block used as a value ends with a value, even when the block diverges
+        for input in &[
+            "let x = if a { 1 } else { return 2; };",
+            "let x = if a { continue; } else { 1 };",
+            "let x = { foo(); break; };",
+            "let x = loop { let y = if a { break 1; } else { 2 }; };",
+        ] {
+            match parse(input) {
+                Err(e) => assert!(e.to_string().contains("not supported"), "{}: {}", input, e),
+                Ok(s) => panic!("Expected a diverging value block error. Found {:?}", s),
+            }
+        }
+
+        // Locals without an initializer only come from lowering value expressions
+        assert!(parse("let x;").is_err());
+        assert!(parse("let x: int;").is_err());
+    }
+
+    #[test]
+    fn parse_partial_app() {
+        let call = match parse_expr_quick("add(1, _, x)") {
+            Expr::FnCall(call) => call.to_data().0,
+            e => panic!("Expected a fn call. Found {:?}", e),
+        };
+
+        assert!(call.is_partial());
+        match call.args.unwrap()[1] {
+            Expr::Placeholder(_) => (),
+            ref e => panic!("Expected a placeholder. Found {:?}", e),
+        }
+
+        let source = ModuleSource::Anonymous(None);
+        let mut tokens = buffer_input(&source, "x.add(_)");
+        assert!(piped_expr(&mut tokens, &[]).is_err());
+
+        let mut tokens = buffer_input(&source, "1 |> add(_, 2)");
+        assert!(piped_expr(&mut tokens, &[]).is_err());
+    }
+
+    #[test]
+    fn parse_labeled_loops() {
+        let labeled = match parse_stmt_quick("'outer: for i in 0..3 { continue 'outer; }") {
+            Stmt::ExprStmt(stmt) => match stmt.to_data().0 {
+                ExprStmt::For(for_data) => for_data,
+                s => panic!("Expected a for loop. Found {:?}", s),
+            },
+            s => panic!("Expected a for loop. Found {:?}", s),
+        };
+
+        assert_eq!(labeled.label.unwrap().data(), &ident!("outer"));
+        match labeled.block.data().0[0] {
+            Stmt::ExprStmt(ref stmt) => match stmt.data() {
+                ExprStmt::Continue(_, Some(label)) => assert_eq!(label.data(), &ident!("outer")),
+                s => panic!("Expected a labeled continue. Found {:?}", s),
+            },
+            ref s => panic!("Expected a labeled continue. Found {:?}", s),
+        }
+
+        let init = match parse_stmt_quick("let x = 'l: loop { break 'l 5; };") {
+            Stmt::ExprStmt(stmt) => match stmt.to_data().0 {
+                ExprStmt::LocalVarDecl(decl) => decl.var_init.unwrap(),
+                s => panic!("Expected a local variable declaration. Found {:?}", s),
+            },
+            s => panic!("Expected a local variable declaration. Found {:?}", s),
+        };
+
+        let loop_expr = match init {
+            Expr::Loop(loop_expr) => loop_expr.to_data().0,
+            e => panic!("Expected a loop expression. Found {:?}", e),
+        };
+        assert_eq!(loop_expr.label.unwrap().data(), &ident!("l"));
+        match loop_expr.block.data().0[0] {
+            Stmt::ExprStmt(ref stmt) => match stmt.data() {
+                ExprStmt::Break(_, Some(_), Some(Expr::Literal(_))) => (),
+                s => panic!("Expected a labeled break with a value. Found {:?}", s),
+            },
+            ref s => panic!("Expected a labeled break with a value. Found {:?}", s),
+        }
+
+        // Only loops are labeled
+        let source = ModuleSource::Anonymous(None);
+        let mut tokens = buffer_input(&source, "'l: if true { }");
+        assert!(teststmt(&mut tokens).is_err());
+    }
+
+    #[test]
+    fn parse_struct_defaults_and_update() {
+        let input = "struct Player {
+    name: String,
+    hp: int = MAX_HP - 10,
+    alive: bool = true,
+}";
+        let source = ModuleSource::Anonymous(None);
+        let mut input = buffer_input(&source, input);
+        let struct_def = match module(&mut input).unwrap().1.remove(0) {
+            DeclStmt::Struct(struct_def) => struct_def.to_data().0,
+            _ => panic!("Expected a struct declaration"),
+        };
+        let fields = struct_def.body.0.unwrap();
+        assert!(fields[0].default.is_none());
+        match fields[1].default.as_ref().map(|default| default.data()) {
+            Some(Expr::Bin(_)) => (),
+            d => panic!("Expected a binary expression default. Found {:?}", d),
+        }
+        assert_eq!(fields[2].default.as_ref().map(|default| default.data()), Some(&boolean!(true => Expr)));
+
+        let init = parse_expr_quick("init Player { name: \"x\", ..other }");
+        match init {
+            Expr::StructInit(init) => {
+                let init = init.to_data().0;
+                assert_eq!(init.field_init.len(), 1);
+                assert_eq!(init.base, Some(Box::new(Expr::Binding(dummy_node!(ident!("other"))))));
+            }
+            e => panic!("Expected a struct init. Found {:?}", e),
+        }
+
+        // Anonymous struct inits have no base struct
+        let source = ModuleSource::Anonymous(None);
+        let mut tokens = buffer_input(&source, "init { name: \"x\", ..other }");
+        assert!(piped_expr(&mut tokens, &[]).is_err());
+    }
+
+    #[test]
+    fn parse_growable_arrays() {
+        let source = ModuleSource::Anonymous(None);
+        let mut tokens = buffer_input(&source, "[int]");
+        match type_annotation(&mut tokens).unwrap().to_data().0 {
+            TypeAnnotation::Array(_, None) => (),
+            t => panic!("Expected a growable array type. Found {:?}", t),
+        }
+
+        let mut tokens = buffer_input(&source, "[int; 3]");
+        match type_annotation(&mut tokens).unwrap().to_data().0 {
+            TypeAnnotation::Array(_, Some(3)) => (),
+            t => panic!("Expected a fixed-size array type. Found {:?}", t),
+        }
+
+        match parse_expr_quick("[]") {
+            Expr::ArrayInit(init) => {
+                assert_eq!(init.to_data().0, ArrayInit::InitList(Vec::new()))
+            }
+            e => panic!("Expected an empty array init. Found {:?}", e),
+        }
+
+        match parse_expr_quick("a[1..n + 1]") {
+            Expr::Slice(slice) => {
+                let slice = slice.to_data().0;
+                assert_eq!(*slice.array, Expr::Binding(dummy_node!(ident!("a"))));
+                assert_eq!(*slice.start, int!(1 => Expr));
+                match *slice.end {
+                    Expr::Bin(_) => (),
+                    ref e => panic!("Expected a binary expression end. Found {:?}", e),
+                }
+            }
+            e => panic!("Expected a slice. Found {:?}", e),
+        }
+    }
 }
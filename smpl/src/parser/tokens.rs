@@ -1,4 +1,5 @@
 use std::iter::{Enumerate, Iterator, Peekable};
+use std::num::{IntErrorKind, ParseIntError};
 /// Inspired by Gluon, specifically gluon/parser/src/token.rs
 /// https://github.com/gluon-lang/gluon/blob/master/parser/src/token.rs
 use std::str::CharIndices;
@@ -44,20 +45,36 @@ impl SpannedToken {
 pub enum Token {
     Identifier(String),
     StringLiteral(String),
+    /// Text of an interpolated string before its first interpolation (`"a{`)
+    FormatStart(String),
+    /// Text of an interpolated string between two interpolations (`}b{`)
+    FormatMid(String),
+    /// Text of an interpolated string after its last interpolation (`}c"`)
+    FormatEnd(String),
     IntLiteral(i64),
     FloatLiteral(f64),
     BoolLiteral(bool),
+    /// Loop label (`'outer`), without the quote
+    Label(String),
 
     Base,
     Fn,
     Struct,
     Opaque,
+    Impl,
+    Trait,
+    Const,
+    Static,
     Mod,
     Use,
+    As,
+    Pub,
     Builtin,
     Unchecked,
     Type,
     Where,
+    /// `_` argument placeholder of a partial application
+    Underscore,
 
     Init,
 
@@ -70,10 +87,17 @@ pub enum Token {
     Elif,
 
     While,
+    Loop,
+    For,
 
     Let,
 
     Assign,
+    PlusAssign,
+    MinusAssign,
+    StarAssign,
+    SlashAssign,
+    PercentAssign,
 
     Eq,
     NEq,
@@ -91,6 +115,12 @@ pub enum Token {
     Slash,
     Percent,
 
+    Bar,
+    Caret,
+    Tilde,
+    Shl,
+    Shr,
+
     Pipe,
 
     Ref,
@@ -107,6 +137,7 @@ pub enum Token {
 
     Comma,
     Dot,
+    DotDot,
 
     Arrow,
 
@@ -123,21 +154,32 @@ impl std::fmt::Display for Token {
         match *self {
             Identifier(ref s) => write!(f, "{}", s),
             StringLiteral(ref s) => write!(f, "\"{}\"", s),
+            FormatStart(ref s) => write!(f, "\"{}{{", s),
+            FormatMid(ref s) => write!(f, "}}{}{{", s),
+            FormatEnd(ref s) => write!(f, "}}{}\"", s),
             IntLiteral(i) => write!(f, "{}", i),
             FloatLiteral(fl) => write!(f, "{}", fl),
             BoolLiteral(b) => write!(f, "{}", b),
+            Label(ref s) => write!(f, "'{}", s),
 
             Base => write!(f, "base"),
             Fn => write!(f, "fn"),
             Struct => write!(f, "struct"),
             Opaque => write!(f, "opaque"),
+            Impl => write!(f, "impl"),
+            Trait => write!(f, "trait"),
+            Const => write!(f, "const"),
+            Static => write!(f, "static"),
             Mod => write!(f, "mod"),
             Use => write!(f, "use"),
+            As => write!(f, "as"),
+            Pub => write!(f, "pub"),
             Builtin => write!(f, "builtin"),
             Unchecked => write!(f, "UNCHECKED"),
 
             Type => write!(f, "type"),
             Where => write!(f, "where"),
+            Underscore => write!(f, "_"),
 
             Init => write!(f, "init"),
 
@@ -150,10 +192,17 @@ impl std::fmt::Display for Token {
             Elif => write!(f, "elif"),
 
             While => write!(f, "while"),
+            Loop => write!(f, "loop"),
+            For => write!(f, "for"),
 
             Let => write!(f, "let"),
 
             Assign => write!(f, "="),
+            PlusAssign => write!(f, "+="),
+            MinusAssign => write!(f, "-="),
+            StarAssign => write!(f, "*="),
+            SlashAssign => write!(f, "/="),
+            PercentAssign => write!(f, "%="),
 
             Eq => write!(f, "=="),
             NEq => write!(f, "!="),
@@ -171,6 +220,12 @@ impl std::fmt::Display for Token {
             Slash => write!(f, "/"),
             Percent => write!(f, "%"),
 
+            Bar => write!(f, "|"),
+            Caret => write!(f, "^"),
+            Tilde => write!(f, "~"),
+            Shl => write!(f, "<<"),
+            Shr => write!(f, ">>"),
+
             Pipe => write!(f, "|>"),
 
             Ref => write!(f, "&"),
@@ -187,6 +242,7 @@ impl std::fmt::Display for Token {
 
             Comma => write!(f, ","),
             Dot => write!(f, "."),
+            DotDot => write!(f, ".."),
 
             Arrow => write!(f, "->"),
 
@@ -217,8 +273,23 @@ pub enum TokenizerError {
     #[fail(display = "Unterminated string literal.")]
     UnterminatedStringLiteral,
 
-    #[fail(display = "Incomplete token: '{}'.", _0)]
-    IncompleteToken(Token),
+    #[fail(display = "Unmatched '}}' in string literal. Use '}}}}' for a literal '}}'.")]
+    UnmatchedFormatBrace,
+
+    #[fail(display = "Invalid numeric literal: '{}'.", _0)]
+    InvalidNumber(String),
+
+    #[fail(display = "Integer literal out of range for int: '{}'.", _0)]
+    IntOutOfRange(String),
+
+    #[fail(display = "Float literal out of range for float: '{}'.", _0)]
+    FloatOutOfRange(String),
+
+    #[fail(display = "Unknown escape sequence: '\\{}'.", _0)]
+    UnknownEscape(char),
+
+    #[fail(display = "Invalid unicode escape. Expected '\\u{{..}}' with 1 to 6 hex digits.")]
+    InvalidUnicodeEscape,
 }
 
 #[derive(Debug)]
@@ -272,6 +343,11 @@ impl<'src_str, 'input> CharInput<'src_str, 'input> {
     fn peek(&self) -> Option<(Location, char)> {
         self.lookahead
     }
+
+    /// The character after the lookahead
+    fn peek_second(&self) -> Option<char> {
+        self.chars.clone().nth(1).map(|(_, (_, c))| c)
+    }
 }
 
 impl<'src_str, 'input> Iterator for CharInput<'src_str, 'input> {
@@ -323,6 +399,9 @@ pub struct Tokenizer<'src_str, 'input> {
     source: &'src_str ModuleSource,
     input: &'input str,
     chars: CharInput<'src_str, 'input>,
+    after_dot: bool,
+    /// Number of unclosed '{' within each enclosing string interpolation
+    format_holes: Vec<usize>,
 }
 
 impl<'src_str, 'input> Tokenizer<'src_str, 'input> {
@@ -333,6 +412,8 @@ impl<'src_str, 'input> Tokenizer<'src_str, 'input> {
             source: source,
             input: input,
             chars: CharInput::new(source, input),
+            after_dot: false,
+            format_holes: Vec::new(),
         }
     }
 
@@ -404,36 +485,45 @@ impl<'src_str, 'input> Tokenizer<'src_str, 'input> {
         self.take_until(start, |c| c == '\n')
     }
 
+    /// Arithmetic operator, or its compound assignment form when followed by '='
+    fn maybe_assign_op(
+        &mut self,
+        start: Location,
+        op: Token,
+        assign_op: Token,
+    ) -> Result<SpannedToken, SpannedError> {
+        if self.test_lookahead(|c| c == '=') {
+            let (end, _) = self.chars.next().ok_or(SpannedError {
+                error: TokenizerError::UnexpectedEndOfInput,
+                location: start,
+            })?;
+            Ok(SpannedToken::new(
+                assign_op,
+                LocationSpan::new(self.source.to_string(), start, end),
+            ))
+        } else {
+            Ok(SpannedToken::new(
+                op,
+                LocationSpan::span_1(self.source.to_string(), start, 1),
+            ))
+        }
+    }
+
     fn op(
         &mut self,
         start: Location,
         c: char,
     ) -> Result<SpannedToken, SpannedError> {
         match c {
-            '+' => Ok(SpannedToken::new(
-                Token::Plus,
-                LocationSpan::span_1(self.source.to_string(), start, 1),
-            )),
+            '+' => self.maybe_assign_op(start, Token::Plus, Token::PlusAssign),
 
-            '-' => Ok(SpannedToken::new(
-                Token::Minus,
-                LocationSpan::span_1(self.source.to_string(), start, 1),
-            )),
+            '-' => self.maybe_assign_op(start, Token::Minus, Token::MinusAssign),
 
-            '*' => Ok(SpannedToken::new(
-                Token::Star,
-                LocationSpan::span_1(self.source.to_string(), start, 1),
-            )),
+            '*' => self.maybe_assign_op(start, Token::Star, Token::StarAssign),
 
-            '/' => Ok(SpannedToken::new(
-                Token::Slash,
-                LocationSpan::span_1(self.source.to_string(), start, 1),
-            )),
+            '/' => self.maybe_assign_op(start, Token::Slash, Token::SlashAssign),
 
-            '%' => Ok(SpannedToken::new(
-                Token::Percent,
-                LocationSpan::span_1(self.source.to_string(), start, 1),
-            )),
+            '%' => self.maybe_assign_op(start, Token::Percent, Token::PercentAssign),
 
             '&' => {
                 if self.test_lookahead(|c| c == '&') {
@@ -473,13 +563,23 @@ impl<'src_str, 'input> Tokenizer<'src_str, 'input> {
                         LocationSpan::new(self.source.to_string(), start, end),
                     ))
                 } else {
-                    Err(SpannedError {
-                        error: TokenizerError::IncompleteToken(Token::LOr),
-                        location: start,
-                    })
+                    Ok(SpannedToken::new(
+                        Token::Bar,
+                        LocationSpan::span_1(self.source.to_string(), start, 1),
+                    ))
                 }
             }
 
+            '^' => Ok(SpannedToken::new(
+                Token::Caret,
+                LocationSpan::span_1(self.source.to_string(), start, 1),
+            )),
+
+            '~' => Ok(SpannedToken::new(
+                Token::Tilde,
+                LocationSpan::span_1(self.source.to_string(), start, 1),
+            )),
+
             '=' => {
                 if self.test_lookahead(|c| c == '=') {
                     let (end, _) = self.chars.next().ok_or(SpannedError {
@@ -526,6 +626,15 @@ impl<'src_str, 'input> Tokenizer<'src_str, 'input> {
                         Token::Lte,
                         LocationSpan::new(self.source.to_string(), start, end),
                     ))
+                } else if self.test_lookahead(|c| c == '<') {
+                    let (end, _) = self.chars.next().ok_or(SpannedError {
+                        error: TokenizerError::UnexpectedEndOfInput,
+                        location: start,
+                    })?;
+                    Ok(SpannedToken::new(
+                        Token::Shl,
+                        LocationSpan::new(self.source.to_string(), start, end),
+                    ))
                 } else {
                     Ok(SpannedToken::new(
                         Token::Lt,
@@ -544,6 +653,15 @@ impl<'src_str, 'input> Tokenizer<'src_str, 'input> {
                         Token::Gte,
                         LocationSpan::new(self.source.to_string(), start, end),
                     ))
+                } else if self.test_lookahead(|c| c == '>') {
+                    let (end, _) = self.chars.next().ok_or(SpannedError {
+                        error: TokenizerError::UnexpectedEndOfInput,
+                        location: start,
+                    })?;
+                    Ok(SpannedToken::new(
+                        Token::Shr,
+                        LocationSpan::new(self.source.to_string(), start, end),
+                    ))
                 } else {
                     Ok(SpannedToken::new(
                         Token::Gt,
@@ -565,16 +683,25 @@ impl<'src_str, 'input> Tokenizer<'src_str, 'input> {
             "mod" => Token::Mod,
             "struct" => Token::Struct,
             "opaque" => Token::Opaque,
+            "impl" => Token::Impl,
+            "trait" => Token::Trait,
+            "const" => Token::Const,
+            "static" => Token::Static,
             "use" => Token::Use,
+            "as" => Token::As,
+            "pub" => Token::Pub,
             "if" => Token::If,
             "else" => Token::Else,
             "elif" => Token::Elif,
             "while" => Token::While,
+            "loop" => Token::Loop,
+            "for" => Token::For,
             "let" => Token::Let,
             "builtin" => Token::Builtin,
             "UNCHECKED" => Token::Unchecked,
             "type" => Token::Type,
             "where" => Token::Where,
+            "_" => Token::Underscore,
             "continue" => Token::Continue,
             "break" => Token::Break,
             "return" => Token::Return,
@@ -588,57 +715,193 @@ impl<'src_str, 'input> Tokenizer<'src_str, 'input> {
         SpannedToken::new(token, LocationSpan::new(self.source.to_string(), start, end))
     }
 
+    /// `'name` labels a loop
+    fn label(&mut self, start: Location) -> SpannedToken {
+        let (end, label) = self.take_while(start, is_ident_continue);
+
+        SpannedToken::new(
+            Token::Label(label[1..].to_string()),
+            LocationSpan::new(self.source.to_string(), start, end),
+        )
+    }
+
+    ///
+    /// Decimal (`1_000`), hexadecimal (`0xFF`), binary (`0b1010`) and octal (`0o17`) ints, and
+    /// decimal floats with optional exponents (`1.5`, `2.`, `1e-3`). '_' separates digits.
+    ///
+    /// Literals which do not fit into an `int` or `float` are errors located at their start.
+    ///
     fn numeric_literal(
         &mut self,
         start: Location,
+        allow_float: bool,
     ) -> Result<SpannedToken, SpannedError> {
-        let (end, int) = self.take_while(start, is_digit);
+        let (end, int) = self.take_while(start, is_digit_or_separator);
+
+        let radix = match self.chars.peek() {
+            Some((_, 'x')) => Some(16),
+            Some((_, 'o')) => Some(8),
+            Some((_, 'b')) => Some(2),
+            _ => None,
+        }
+        .filter(|_| int.trim_start_matches('-') == "0");
+
+        if let Some(radix) = radix {
+            self.chars.next(); // Skip the radix prefix
+            let (end, literal) =
+                self.take_while(start, |c| c.is_digit(radix) || c == '_');
+            self.end_of_number()?;
 
-        if let Some((_loc, ch)) = self.chars.peek() {
-            if ch == '.' {
+            let (sign, digits) = match literal.strip_prefix('-') {
+                Some(digits) => ("-", digits),
+                None => ("", literal),
+            };
+            let digits = format!("{}{}", sign, digits[2..].replace('_', ""));
+
+            let i = i64::from_str_radix(&digits, radix)
+                .map_err(|e| int_error(start, literal, e))?;
+
+            return Ok(SpannedToken::new(
+                Token::IntLiteral(i),
+                LocationSpan::new(self.source.to_string(), start, end),
+            ));
+        }
+
+        let mut end = end;
+        let mut literal = int;
+        let mut is_float = false;
+        if allow_float {
+            // '..' is a range, not a decimal point
+            if self.test_lookahead(|c| c == '.') && self.chars.peek_second() != Some('.') {
                 self.chars.next(); // Skip '.'
-                let (end, float) = self.take_while(start, is_digit);
-
-                if let Some((next, ch)) = self.chars.peek() {
-                    if is_ident_continue(ch) {
-                        return Err(SpannedError {
-                            error: TokenizerError::UnexpectedChar(ch),
-                            location: next,
-                        });
-                    }
+                let (float_end, float) = self.take_while(start, is_digit_or_separator);
+                end = float_end;
+                literal = float;
+                is_float = true;
+            }
+
+            if self.test_lookahead(|c| c == 'e' || c == 'E') {
+                self.chars.next(); // Skip 'e'
+                if self.test_lookahead(|c| c == '+' || c == '-') {
+                    self.chars.next();
                 }
 
-                let f = float.parse::<f64>().unwrap();
+                let (exp_end, exp) = self.take_while(start, is_digit_or_separator);
+                if !exp.ends_with(is_digit) {
+                    return Err(SpannedError {
+                        error: TokenizerError::InvalidNumber(exp.to_string()),
+                        location: start,
+                    });
+                }
 
-                return Ok(SpannedToken::new(
-                    Token::FloatLiteral(f),
-                    LocationSpan::new(self.source.to_string(), start, end),
-                ));
+                end = exp_end;
+                literal = exp;
+                is_float = true;
             }
         }
 
-        let i = int.parse::<i64>().unwrap();
+        self.end_of_number()?;
+
+        let digits = literal.replace('_', "");
+        let token = if is_float {
+            let f = digits.parse::<f64>().map_err(|_| SpannedError {
+                error: TokenizerError::InvalidNumber(literal.to_string()),
+                location: start,
+            })?;
+
+            if !f.is_finite() {
+                return Err(SpannedError {
+                    error: TokenizerError::FloatOutOfRange(literal.to_string()),
+                    location: start,
+                });
+            }
+
+            Token::FloatLiteral(f)
+        } else {
+            let i = digits
+                .parse::<i64>()
+                .map_err(|e| int_error(start, literal, e))?;
+            Token::IntLiteral(i)
+        };
+
         Ok(SpannedToken::new(
-            Token::IntLiteral(i),
+            token,
             LocationSpan::new(self.source.to_string(), start, end),
         ))
     }
 
-    fn string_literal(
+    /// Numbers may not be directly followed by identifier characters (i.e. `12abc`)
+    fn end_of_number(&mut self) -> Result<(), SpannedError> {
+        match self.chars.peek() {
+            Some((next, ch)) if is_ident_continue(ch) => Err(SpannedError {
+                error: TokenizerError::UnexpectedChar(ch),
+                location: next,
+            }),
+
+            _ => Ok(()),
+        }
+    }
+
+    ///
+    /// String literals may span multiple lines. A '\' at the end of a line skips the line break
+    /// and the indentation of the next line.
+    ///
+    /// '{' starts an interpolated expression, which the tokenizer returns to after its closing
+    /// '}' (`continued`). '{{' and '}}' are literal braces.
+    ///
+    fn string_contents(
         &mut self,
         start: Location,
+        continued: bool,
     ) -> Result<SpannedToken, SpannedError> {
         let mut literal = String::new();
         while let Some((e, ch)) = self.chars.next() {
             match ch {
                 '\"' => {
+                    let token = if continued {
+                        Token::FormatEnd(literal)
+                    } else {
+                        Token::StringLiteral(literal)
+                    };
+
+                    return Ok(SpannedToken::new(
+                        token,
+                        LocationSpan::new(self.source.to_string(), start, e),
+                    ));
+                }
+
+                '{' | '}' if self.test_lookahead(|c| c == ch) => {
+                    self.chars.next();
+                    literal.push(ch);
+                }
+
+                '{' => {
+                    self.format_holes.push(0);
+                    let token = if continued {
+                        Token::FormatMid(literal)
+                    } else {
+                        Token::FormatStart(literal)
+                    };
+
                     return Ok(SpannedToken::new(
-                        Token::StringLiteral(literal),
+                        token,
                         LocationSpan::new(self.source.to_string(), start, e),
                     ));
                 }
 
-                // TODO: Track escape characters
+                '}' => {
+                    return Err(SpannedError {
+                        error: TokenizerError::UnmatchedFormatBrace,
+                        location: e,
+                    });
+                }
+
+                '\\' => {
+                    if let Some(escaped) = self.escape(e)? {
+                        literal.push(escaped);
+                    }
+                }
+
                 ch => literal.push(ch),
             }
         }
@@ -648,6 +911,139 @@ impl<'src_str, 'input> Tokenizer<'src_str, 'input> {
             location: start,
         })
     }
+
+    ///
+    /// The character escaped by the '\' at `start`. `None` for a line continuation.
+    ///
+    fn escape(&mut self, start: Location) -> Result<Option<char>, SpannedError> {
+        let (_, ch) = self.chars.next().ok_or(SpannedError {
+            error: TokenizerError::UnterminatedStringLiteral,
+            location: start,
+        })?;
+
+        let escaped = match ch {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            '\\' => '\\',
+            '\"' => '\"',
+            '\'' => '\'',
+            'u' => self.unicode_escape(start)?,
+
+            '\n' | '\r' => {
+                while self.test_lookahead(char::is_whitespace) {
+                    self.chars.next();
+                }
+
+                return Ok(None);
+            }
+
+            ch => {
+                return Err(SpannedError {
+                    error: TokenizerError::UnknownEscape(ch),
+                    location: start,
+                });
+            }
+        };
+
+        Ok(Some(escaped))
+    }
+
+    ///
+    /// `\u{1F600}`
+    ///
+    fn unicode_escape(&mut self, start: Location) -> Result<char, SpannedError> {
+        let invalid = SpannedError {
+            error: TokenizerError::InvalidUnicodeEscape,
+            location: start,
+        };
+
+        match self.chars.next() {
+            Some((_, '{')) => (),
+            _ => return Err(invalid),
+        }
+
+        let mut digits = String::new();
+        loop {
+            match self.chars.next() {
+                Some((_, '}')) => break,
+                Some((_, c)) if c.is_ascii_hexdigit() && digits.len() < 6 => digits.push(c),
+                _ => return Err(invalid),
+            }
+        }
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(std::char::from_u32)
+            .ok_or(invalid)
+    }
+
+    ///
+    /// `r"..."` or `r#"..."#`. Characters are not escaped. The literal ends at a '"' followed by
+    /// as many '#' as the opening.
+    ///
+    fn raw_string_literal(
+        &mut self,
+        start: Location,
+    ) -> Result<SpannedToken, SpannedError> {
+        let mut hashes = 0;
+        while self.test_lookahead(|c| c == '#') {
+            self.chars.next();
+            hashes += 1;
+        }
+
+        match self.chars.next() {
+            Some((_, '\"')) => (),
+
+            Some((location, ch)) => {
+                return Err(SpannedError {
+                    error: TokenizerError::UnexpectedChar(ch),
+                    location,
+                });
+            }
+
+            None => {
+                return Err(SpannedError {
+                    error: TokenizerError::UnexpectedEndOfInput,
+                    location: start,
+                });
+            }
+        }
+
+        let mut literal = String::new();
+        while let Some((e, ch)) = self.chars.next() {
+            if ch != '\"' {
+                literal.push(ch);
+                continue;
+            }
+
+            let mut end = e;
+            let mut closing = 0;
+            while closing < hashes && self.test_lookahead(|c| c == '#') {
+                end = self.chars.next().unwrap().0;
+                closing += 1;
+            }
+
+            if closing == hashes {
+                return Ok(SpannedToken::new(
+                    Token::StringLiteral(literal),
+                    LocationSpan::new(self.source.to_string(), start, end),
+                ));
+            }
+
+            // Not enough '#' to close the literal
+            literal.push('\"');
+            // `repeat_n` would require Rust 1.82
+            #[allow(clippy::manual_repeat_n)]
+            literal.extend(std::iter::repeat('#').take(closing));
+        }
+
+        Err(SpannedError {
+            error: TokenizerError::UnterminatedStringLiteral,
+            location: start,
+        })
+    }
 }
 
 impl<'src_str, 'input> Iterator for Tokenizer<'src_str, 'input> {
@@ -655,6 +1051,9 @@ impl<'src_str, 'input> Iterator for Tokenizer<'src_str, 'input> {
 
     fn next(&mut self) -> Option<Self::Item> {
         while let Some((start, c)) = self.chars.next() {
+            // Numbers following a '.' are tuple fields (i.e. 't.0.1'), not floats
+            let after_dot = std::mem::replace(&mut self.after_dot, false);
+
             return match c {
                 '/' if self.test_lookahead(is_slash) => {
                     self.line_comment(start);
@@ -673,10 +1072,21 @@ impl<'src_str, 'input> Iterator for Tokenizer<'src_str, 'input> {
                     Token::Comma,
                     LocationSpan::span_1(self.source.to_string(), start, 1),
                 ))),
-                '.' => Some(Ok(SpannedToken::new(
-                    Token::Dot,
-                    LocationSpan::span_1(self.source.to_string(), start, 1),
-                ))),
+                '.' if self.test_lookahead(|c| c == '.') => {
+                    let (end, _) = self.chars.next().unwrap();
+                    Some(Ok(SpannedToken::new(
+                        Token::DotDot,
+                        LocationSpan::new(self.source.to_string(), start, end),
+                    )))
+                }
+
+                '.' => {
+                    self.after_dot = true;
+                    Some(Ok(SpannedToken::new(
+                        Token::Dot,
+                        LocationSpan::span_1(self.source.to_string(), start, 1),
+                    )))
+                }
                 ';' => Some(Ok(SpannedToken::new(
                     Token::Semi,
                     LocationSpan::span_1(self.source.to_string(), start, 1),
@@ -715,27 +1125,51 @@ impl<'src_str, 'input> Iterator for Tokenizer<'src_str, 'input> {
                     LocationSpan::span_1(self.source.to_string(), start, 1),
                 ))),
 
-                '{' => Some(Ok(SpannedToken::new(
-                    Token::LBrace,
-                    LocationSpan::span_1(self.source.to_string(), start, 1),
-                ))),
-                '}' => Some(Ok(SpannedToken::new(
-                    Token::RBrace,
-                    LocationSpan::span_1(self.source.to_string(), start, 1),
-                ))),
+                '{' => {
+                    if let Some(depth) = self.format_holes.last_mut() {
+                        *depth += 1;
+                    }
+
+                    Some(Ok(SpannedToken::new(
+                        Token::LBrace,
+                        LocationSpan::span_1(self.source.to_string(), start, 1),
+                    )))
+                }
+
+                // Closes a string interpolation and continues the string
+                '}' if self.format_holes.last() == Some(&0) => {
+                    self.format_holes.pop();
+                    Some(self.string_contents(start, true))
+                }
+
+                '}' => {
+                    if let Some(depth) = self.format_holes.last_mut() {
+                        *depth -= 1;
+                    }
+
+                    Some(Ok(SpannedToken::new(
+                        Token::RBrace,
+                        LocationSpan::span_1(self.source.to_string(), start, 1),
+                    )))
+                }
 
                 '#' => Some(Ok(SpannedToken::new(
                     Token::Pound,
                     LocationSpan::span_1(self.source.to_string(), start, 1),
                 ))),
 
-                '\"' => Some(self.string_literal(start)),
+                '\"' => Some(self.string_contents(start, false)),
+                'r' if self.test_lookahead(|c| c == '\"' || c == '#') => {
+                    Some(self.raw_string_literal(start))
+                }
+
+                '\'' if self.test_lookahead(is_ident_start) => Some(Ok(self.label(start))),
 
                 ch if is_ident_start(ch) => Some(Ok(self.identifier(start))),
                 ch if is_digit(ch)
                     || (ch == '-' && self.test_lookahead(is_digit)) =>
                 {
-                    Some(self.numeric_literal(start))
+                    Some(self.numeric_literal(start, !after_dot))
                 }
 
                 ch if is_op(ch) => Some(self.op(start, ch)),
@@ -811,6 +1245,8 @@ fn is_op(c: char) -> bool {
         || c == '%'
         || c == '&'
         || c == '|'
+        || c == '^'
+        || c == '~'
         || c == '!'
         || c == '='
         || c == '>'
@@ -829,6 +1265,23 @@ fn is_digit(c: char) -> bool {
     c.is_digit(10)
 }
 
+fn is_digit_or_separator(c: char) -> bool {
+    is_digit(c) || c == '_'
+}
+
+fn int_error(location: Location, literal: &str, e: ParseIntError) -> SpannedError {
+    let literal = literal.to_string();
+    let error = match e.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+            TokenizerError::IntOutOfRange(literal)
+        }
+
+        _ => TokenizerError::InvalidNumber(literal),
+    };
+
+    SpannedError { error, location }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -939,6 +1392,111 @@ false
         assert_eq!(None, tok.next());
     }
 
+    #[test]
+    fn tokenize_string_escapes() {
+        let input = r#""line\n\ttab \\ \"quoted\" \u{48}\u{1F600}" "first \
+    second"
+"multi
+line""#;
+        let source = ModuleSource::Anonymous(None);
+        let mut tok = Tokenizer::new(&source, input);
+
+        assert_eq!(
+            Token::StringLiteral("line\n\ttab \\ \"quoted\" H\u{1F600}".to_string()),
+            unwrap(tok.next())
+        );
+        assert_eq!(
+            Token::StringLiteral("first second".to_string()),
+            unwrap(tok.next())
+        );
+        assert_eq!(
+            Token::StringLiteral("multi\nline".to_string()),
+            unwrap(tok.next())
+        );
+
+        assert_eq!(None, tok.next());
+
+        let error = |input: &str| {
+            let mut tok = Tokenizer::new(&source, input);
+            tok.next().unwrap().unwrap_err()
+        };
+
+        let e = error(r#"  "ab\q""#);
+        assert_eq!(e.error, TokenizerError::UnknownEscape('q'));
+        assert_eq!(e.location.byte_index, 5);
+
+        assert_eq!(error(r#""\u{110000}""#).error, TokenizerError::InvalidUnicodeEscape);
+        assert_eq!(error(r#""\u{}""#).error, TokenizerError::InvalidUnicodeEscape);
+        assert_eq!(error(r#""\u48""#).error, TokenizerError::InvalidUnicodeEscape);
+    }
+
+    #[test]
+    fn tokenize_raw_strings() {
+        let input = r###"r"C:\path\n" r#"say "hi""# r##"a"#b"## rvalue"###;
+        let source = ModuleSource::Anonymous(None);
+        let mut tok = Tokenizer::new(&source, input);
+
+        assert_eq!(
+            Token::StringLiteral(r"C:\path\n".to_string()),
+            unwrap(tok.next())
+        );
+
+        let (span, literal) = tok.next().unwrap().unwrap().to_data();
+        assert_eq!(Token::StringLiteral(r#"say "hi""#.to_string()), literal);
+        assert_eq!(span.start().byte_index, 13);
+        assert_eq!(span.end().byte_index, 25);
+
+        assert_eq!(
+            Token::StringLiteral(r##"a"#b"##.to_string()),
+            unwrap(tok.next())
+        );
+        assert_eq!(
+            Token::Identifier("rvalue".to_string()),
+            unwrap(tok.next())
+        );
+
+        assert_eq!(None, tok.next());
+    }
+
+    #[test]
+    fn tokenize_format_strings() {
+        let input = r#""HP: {hp}/{max} {{}}" "{ init P { x: 1 }.x }{"a{b}"}""#;
+        let source = ModuleSource::Anonymous(None);
+        let mut tok = Tokenizer::new(&source, input);
+
+        assert_eq!(Token::FormatStart("HP: ".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::Identifier("hp".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::FormatMid("/".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::Identifier("max".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::FormatEnd(" {}".to_string()), unwrap(tok.next()));
+
+        // Braces within an interpolation are tokens
+        assert_eq!(Token::FormatStart("".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::Init, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("P".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::LBrace, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("x".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::Colon, unwrap(tok.next()));
+        assert_eq!(Token::IntLiteral(1), unwrap(tok.next()));
+        assert_eq!(Token::RBrace, unwrap(tok.next()));
+        assert_eq!(Token::Dot, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("x".to_string()), unwrap(tok.next()));
+
+        // Nested interpolated strings
+        assert_eq!(Token::FormatMid("".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::FormatStart("a".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::Identifier("b".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::FormatEnd("".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::FormatEnd("".to_string()), unwrap(tok.next()));
+
+        assert_eq!(None, tok.next());
+
+        let mut tok = Tokenizer::new(&source, r#""a } b""#);
+        let error = tok.next().unwrap().unwrap_err();
+        assert_eq!(error.error, TokenizerError::UnmatchedFormatBrace);
+        assert_eq!(error.location.byte_index, 3);
+    }
+
     #[test]
     fn tokenize_idents() {
         let input = "
@@ -984,6 +1542,68 @@ A1b2
         assert_eq!(Token::IntLiteral(3), unwrap(tok.next()));
     }
 
+    #[test]
+    fn tokenize_compound_assign() {
+        let input = "a += 1; b -= c *= d /= e %= f";
+        let source = ModuleSource::Anonymous(None);
+        let mut tok = Tokenizer::new(&source, input);
+
+        assert_eq!(Token::Identifier("a".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::PlusAssign, unwrap(tok.next()));
+        assert_eq!(Token::IntLiteral(1), unwrap(tok.next()));
+        assert_eq!(Token::Semi, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("b".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::MinusAssign, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("c".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::StarAssign, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("d".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::SlashAssign, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("e".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::PercentAssign, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("f".to_string()), unwrap(tok.next()));
+    }
+
+    #[test]
+    fn tokenize_bitwise() {
+        let input = "a & b | c ^ ~d << 2 >> e || f && g |> h";
+        let source = ModuleSource::Anonymous(None);
+        let mut tok = Tokenizer::new(&source, input);
+
+        assert_eq!(Token::Identifier("a".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::Ref, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("b".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::Bar, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("c".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::Caret, unwrap(tok.next()));
+        assert_eq!(Token::Tilde, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("d".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::Shl, unwrap(tok.next()));
+        assert_eq!(Token::IntLiteral(2), unwrap(tok.next()));
+        assert_eq!(Token::Shr, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("e".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::LOr, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("f".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::LAnd, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("g".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::Pipe, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("h".to_string()), unwrap(tok.next()));
+    }
+
+    #[test]
+    fn tokenize_labels() {
+        let input = "'outer: loop { break 'outer; }";
+        let source = ModuleSource::Anonymous(None);
+        let mut tok = Tokenizer::new(&source, input);
+
+        assert_eq!(Token::Label("outer".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::Colon, unwrap(tok.next()));
+        assert_eq!(Token::Loop, unwrap(tok.next()));
+        assert_eq!(Token::LBrace, unwrap(tok.next()));
+        assert_eq!(Token::Break, unwrap(tok.next()));
+        assert_eq!(Token::Label("outer".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::Semi, unwrap(tok.next()));
+    }
+
     #[test]
     fn tokenize_keywords() {
         let input = "if struct while fn mod use else elif let";
@@ -1037,6 +1657,37 @@ A1b2
         assert_eq!(Token::Identifier("baz".to_string()), unwrap(tok.next()));
     }
 
+    #[test]
+    fn tokenize_tuple_field_access() {
+        let input = "foo.0.1 1.5";
+        let source = ModuleSource::Anonymous(None);
+        let mut tok = Tokenizer::new(&source, input);
+
+        assert_eq!(Token::Identifier("foo".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::Dot, unwrap(tok.next()));
+        assert_eq!(Token::IntLiteral(0), unwrap(tok.next()));
+        assert_eq!(Token::Dot, unwrap(tok.next()));
+        assert_eq!(Token::IntLiteral(1), unwrap(tok.next()));
+        assert_eq!(Token::FloatLiteral(1.5), unwrap(tok.next()));
+    }
+
+    #[test]
+    fn tokenize_range() {
+        let input = "for i in 0..10 1.5..a";
+        let source = ModuleSource::Anonymous(None);
+        let mut tok = Tokenizer::new(&source, input);
+
+        assert_eq!(Token::For, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("i".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::Identifier("in".to_string()), unwrap(tok.next()));
+        assert_eq!(Token::IntLiteral(0), unwrap(tok.next()));
+        assert_eq!(Token::DotDot, unwrap(tok.next()));
+        assert_eq!(Token::IntLiteral(10), unwrap(tok.next()));
+        assert_eq!(Token::FloatLiteral(1.5), unwrap(tok.next()));
+        assert_eq!(Token::DotDot, unwrap(tok.next()));
+        assert_eq!(Token::Identifier("a".to_string()), unwrap(tok.next()));
+    }
+
     #[test]
     fn tokenize_comma_list() {
         let input = "foo, bar,baz";
@@ -1066,6 +1717,57 @@ A1b2
         assert_eq!(None, tok.next());
     }
 
+    #[test]
+    fn tokenize_numeric_forms() {
+        let input = "0xFF -0x10 0b1010 0o17 1_000_000 1e3 1.5e-3 2.5E+2 1_0.2_5 \
+                     9223372036854775807 -9223372036854775808";
+        let source = ModuleSource::Anonymous(None);
+        let mut tok = Tokenizer::new(&source, input);
+
+        assert_eq!(Token::IntLiteral(255), unwrap(tok.next()));
+        assert_eq!(Token::IntLiteral(-16), unwrap(tok.next()));
+        assert_eq!(Token::IntLiteral(10), unwrap(tok.next()));
+        assert_eq!(Token::IntLiteral(15), unwrap(tok.next()));
+        assert_eq!(Token::IntLiteral(1_000_000), unwrap(tok.next()));
+        assert_eq!(Token::FloatLiteral(1e3), unwrap(tok.next()));
+        assert_eq!(Token::FloatLiteral(1.5e-3), unwrap(tok.next()));
+        assert_eq!(Token::FloatLiteral(2.5e2), unwrap(tok.next()));
+        assert_eq!(Token::FloatLiteral(10.25), unwrap(tok.next()));
+        assert_eq!(Token::IntLiteral(i64::MAX), unwrap(tok.next()));
+        assert_eq!(Token::IntLiteral(i64::MIN), unwrap(tok.next()));
+
+        assert_eq!(None, tok.next());
+    }
+
+    #[test]
+    fn tokenize_numeric_errors() {
+        let source = ModuleSource::Anonymous(None);
+        let error = |input: &str| {
+            let mut tok = Tokenizer::new(&source, input);
+            tok.next().unwrap().unwrap_err()
+        };
+
+        let e = error("  99999999999999999999;");
+        assert_eq!(
+            e.error,
+            TokenizerError::IntOutOfRange("99999999999999999999".to_string())
+        );
+        assert_eq!(e.location.byte_index, 2);
+
+        assert_eq!(
+            error("0x8000000000000000").error,
+            TokenizerError::IntOutOfRange("0x8000000000000000".to_string())
+        );
+        assert_eq!(
+            error("1e400").error,
+            TokenizerError::FloatOutOfRange("1e400".to_string())
+        );
+        assert_eq!(error("0x").error, TokenizerError::InvalidNumber("0x".to_string()));
+        assert_eq!(error("1e+").error, TokenizerError::InvalidNumber("1e+".to_string()));
+        assert_eq!(error("0b102").error, TokenizerError::UnexpectedChar('2'));
+        assert_eq!(error("0xFG").error, TokenizerError::UnexpectedChar('G'));
+    }
+
     #[test]
     fn tokenize_single_float() {
         let input = "21.";
@@ -3,7 +3,7 @@ use std::rc::Rc;
 
 use crate::analysis::error::AnalysisError;
 use crate::analysis::{
-    check_program, AnonymousFn, FnId, Function,
+    check_program, reload_program, AnonymousFn, FnId, Function,
     Program as AnalyzedProgram, TypingContext, CFG,
     ModuleId, Module as AnalyzedModule,
 };
@@ -47,6 +47,27 @@ impl Program {
         })
     }
 
+    ///
+    /// Re-analyze a `Program` after replacing one of its modules.
+    ///
+    /// * `modules` - Every module of the new program, including the replacement module
+    /// * `previous_module` - The module being replaced
+    /// * `previous` - Metadata of the program being replaced
+    ///
+    /// Functions keep their previous `FnId` if their module and name did not change.
+    /// Functions of the replacement module may not change their signatures.
+    ///
+    pub fn reload<I>(
+        modules: I,
+        previous_module: &ParsedModule,
+        previous: &crate::metadata::Metadata,
+    ) -> Result<Program, AnalysisError>
+    where I: Iterator<Item=ParsedModule> {
+        Ok(Program {
+            program: reload_program(modules.collect(), previous_module, previous)?,
+        })
+    }
+
     ///
     /// Access to this `Program`'s metadata collected during static analysis.
     ///
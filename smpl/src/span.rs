@@ -37,6 +37,10 @@ impl LocationSpan {
         LocationSpan::new("dummy".to_string(), d_loc.clone(), d_loc)
     }
 
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
     pub fn start(&self) -> Location {
         self.start
     }
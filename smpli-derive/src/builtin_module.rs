@@ -0,0 +1,147 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    AttributeArgs, Error, FnArg, Item, ItemFn, ItemMod, Lit, Meta, NestedMeta, Pat,
+    ReturnType, Visibility,
+};
+
+///
+/// Every `pub fn` in the module is declared as a builtin with its Rust name, parameter
+///   names and types (see `smpli::SmplType`). Private functions are left alone.
+///
+/// Generates inside the module:
+///   * `MOD_NAME`: the SMPL module name (the `name` argument or the Rust module name)
+///   * `declaration()`: the SMPL source declaring the builtins and importing the modules their
+///     types refer to (see `smpli::SmplType::smpl_modules()`)
+///   * `vm_module()`: the parsed declarations with each builtin registered with `smpli::typed()`
+///
+pub fn expand(args: AttributeArgs, mut module: ItemMod) -> Result<TokenStream2, Error> {
+    let mut mod_name = module.ident.to_string();
+
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::NameValue(ref nv)) if nv.path.is_ident("name") => {
+                match nv.lit {
+                    Lit::Str(ref s) => mod_name = s.value(),
+                    ref lit => return Err(Error::new_spanned(lit, "Expected a string")),
+                }
+            }
+
+            arg => return Err(Error::new_spanned(arg, "Unknown argument. Expected `name = \"...\"`")),
+        }
+    }
+
+    let items = match module.content {
+        Some((_, ref mut items)) => items,
+        None => {
+            return Err(Error::new_spanned(
+                &module,
+                "smpl_module requires an inline module",
+            ));
+        }
+    };
+
+    let mut declarations = Vec::new();
+    let mut uses = Vec::new();
+    let mut registrations = Vec::new();
+
+    for item in items.iter() {
+        let item = match item {
+            Item::Fn(ref item) => item,
+            _ => continue,
+        };
+
+        match item.vis {
+            Visibility::Public(..) => (),
+            _ => continue,
+        }
+
+        let (declaration, modules, registration) = builtin(item)?;
+        declarations.push(declaration);
+        uses.push(modules);
+        registrations.push(registration);
+    }
+
+    let generated: syn::File = syn::parse2(quote! {
+        pub const MOD_NAME: &str = #mod_name;
+
+        pub fn declaration() -> String {
+            let builtins: Vec<String> = vec![#(#declarations),*];
+            let mut uses: Vec<String> = Vec::new();
+            #(uses.extend(#uses);)*
+            ::smpli::__private::module_declaration(MOD_NAME, &uses, &builtins)
+        }
+
+        pub fn vm_module() -> ::smpli::VmModule {
+            let declaration = declaration();
+            let input = ::smpli::UnparsedModule::anonymous(&declaration);
+            let parsed = ::smpli::parse_module(input)
+                .expect("Generated builtin declarations should parse");
+
+            ::smpli::VmModule::new(parsed)
+                #(#registrations)*
+        }
+    })?;
+
+    items.extend(generated.items);
+
+    Ok(quote!(#module))
+}
+
+fn builtin(item: &ItemFn) -> Result<(TokenStream2, TokenStream2, TokenStream2), Error> {
+    let sig = &item.sig;
+
+    if let Some(ref asyncness) = sig.asyncness {
+        return Err(Error::new_spanned(asyncness, "Builtins must be synchronous"));
+    }
+
+    if !sig.generics.params.is_empty() {
+        return Err(Error::new_spanned(&sig.generics, "Builtins cannot be generic"));
+    }
+
+    let mut params = Vec::new();
+    let mut modules = Vec::new();
+    for input in sig.inputs.iter() {
+        let input = match input {
+            FnArg::Typed(ref input) => input,
+            FnArg::Receiver(ref receiver) => {
+                return Err(Error::new_spanned(receiver, "Builtins cannot take self"));
+            }
+        };
+
+        let name = match *input.pat {
+            Pat::Ident(ref pat) => pat.ident.to_string(),
+            ref pat => {
+                return Err(Error::new_spanned(pat, "Builtin parameters must be identifiers"));
+            }
+        };
+
+        let ty = &input.ty;
+        params.push(quote!((#name, <#ty as ::smpli::SmplType>::smpl_type())));
+        modules.push(quote!(<#ty as ::smpli::SmplType>::smpl_modules()));
+    }
+
+    let output = match sig.output {
+        ReturnType::Default => quote!(()),
+        ReturnType::Type(_, ref ty) => quote! {
+            <#ty as ::smpli::IntoBuiltinResult>::Output
+        },
+    };
+    let return_type = quote!(<#output as ::smpli::SmplType>::smpl_type());
+    modules.push(quote!(<#output as ::smpli::SmplType>::smpl_modules()));
+
+    let ident = &sig.ident;
+    let name = ident.to_string();
+
+    let declaration = quote! {
+        ::smpli::__private::builtin_declaration(#name, &[#(#params),*], #return_type)
+    };
+
+    let registration = quote! {
+        .add_builtin(#name, ::smpli::typed(#ident))
+    };
+
+    let modules = quote!(vec![#(#modules),*].concat());
+
+    Ok((declaration, modules, registration))
+}
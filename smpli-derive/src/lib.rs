@@ -0,0 +1,284 @@
+//!
+//! `#[smpl_module]` generates the `builtin fn` declarations and `VmModule` registrations
+//!   of a module of typed Rust functions (see `smpl_module()`).
+//!
+//! `#[derive(SmplValue)]` implements `smpli::{SmplType, FromSmpl, ToSmpl, SmplDeclaration}`.
+//!
+//! Structs with named fields become `Value::Struct`s with one field per Rust field
+//!   and are declared as SMPL `struct`s with the same name. Field types are declared
+//!   with `SmplType::smpl_type()` (modules declaring `Option` fields must `use option;`).
+//!
+//! Tuple structs use the field names "0", "1", ... like Rust tuples and are declared
+//!   `opaque` (SMPL field names must be identifiers).
+//!
+//! Enums become a `Value::Struct` with the variant index in the field `__TAG` and
+//!   the variant's fields alongside it. They are declared `opaque`.
+//!
+extern crate proc_macro;
+
+mod builtin_module;
+
+use proc_macro::TokenStream;
+use proc_macro2::{ Span, TokenStream as TokenStream2 };
+use quote::quote;
+use syn::{
+    parse_macro_input, AttributeArgs, Data, DeriveInput, Error, Fields, Ident, ItemMod,
+};
+
+const ENUM_TAG_KEY: &str = "__TAG";
+
+///
+/// Declares every `pub fn` of an inline module as a SMPL builtin.
+///
+/// ```ignore
+/// #[smpl_module(name = "geometry")]
+/// mod geometry {
+///     pub fn dist(x: f64, y: f64) -> f64 {
+///         (x * x + y * y).sqrt()
+///     }
+/// }
+///
+/// // mod geometry;
+/// //
+/// // builtin fn dist(x: float, y: float) -> float;
+/// let module: VmModule = geometry::vm_module();
+/// ```
+///
+/// Functions must be convertible with `smpli::typed()`.
+///
+#[proc_macro_attribute]
+pub fn smpl_module(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as AttributeArgs);
+    let module = parse_macro_input!(item as ItemMod);
+
+    builtin_module::expand(args, module)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(SmplValue)]
+pub fn derive_smpl_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    derive(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn derive(input: DeriveInput) -> Result<TokenStream2, Error> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "SmplValue cannot be derived for generic types",
+        ));
+    }
+
+    let name = &input.ident;
+    let smpl_name = name.to_string();
+
+    let (from_body, to_body, declaration) = match input.data {
+        Data::Struct(ref data) => {
+            let (construct, field_names) = from_fields(quote!(#name), &data.fields);
+            let (destruct, set) = to_fields(quote!(#name), &data.fields);
+
+            let from_body = quote! {
+                #[allow(unused_variables)]
+                let smpl_struct = ::smpli::__private::expect_struct::<Self>(value)?;
+                Ok(#construct)
+            };
+
+            let to_body = quote! {
+                let #destruct = self;
+                #[allow(unused_mut)]
+                let mut smpl_struct = ::smpli::Struct::new();
+                #set
+                ::smpli::Value::Struct(smpl_struct)
+            };
+
+            let declaration = match data.fields {
+                Fields::Named(ref fields) => {
+                    let types = fields.named.iter().map(|f| &f.ty);
+                    let header = format!("struct {} {{\n", smpl_name);
+                    quote! {
+                        let mut declaration = String::from(#header);
+                        #(
+                            declaration.push_str(&format!("    {}: {},\n",
+                                #field_names,
+                                <#types as ::smpli::SmplType>::smpl_type()));
+                        )*
+                        declaration.push_str("}\n");
+                        declaration
+                    }
+                }
+
+                Fields::Unit => {
+                    let declaration = format!("struct {} {{ }}\n", smpl_name);
+                    quote!(String::from(#declaration))
+                }
+
+                Fields::Unnamed(..) => opaque_declaration(&smpl_name),
+            };
+
+            (from_body, to_body, declaration)
+        }
+
+        Data::Enum(ref data) => {
+            let mut from_arms = Vec::new();
+            let mut to_arms = Vec::new();
+
+            for (tag, variant) in data.variants.iter().enumerate() {
+                let tag = tag as i64;
+                let variant_name = &variant.ident;
+
+                let (construct, _) = from_fields(quote!(#name::#variant_name), &variant.fields);
+                let (destruct, set) = to_fields(quote!(#name::#variant_name), &variant.fields);
+
+                from_arms.push(quote! {
+                    #tag => Ok(#construct),
+                });
+
+                to_arms.push(quote! {
+                    #destruct => {
+                        smpl_struct.set_field(#ENUM_TAG_KEY.to_string(), ::smpli::Value::Int(#tag));
+                        #set
+                    }
+                });
+            }
+
+            let from_body = quote! {
+                let smpl_struct = ::smpli::__private::expect_struct::<Self>(value)?;
+                let tag: i64 = ::smpli::__private::take_field::<Self, i64>(&smpl_struct, #ENUM_TAG_KEY)?;
+                match tag {
+                    #(#from_arms)*
+                    tag => Err(::smpli::err::ConversionError {
+                        expected: <Self as ::smpli::SmplType>::smpl_type(),
+                        found: format!("unknown variant tag {}", tag),
+                    }),
+                }
+            };
+
+            let to_body = quote! {
+                let mut smpl_struct = ::smpli::Struct::new();
+                match self {
+                    #(#to_arms)*
+                }
+                ::smpli::Value::Struct(smpl_struct)
+            };
+
+            (from_body, to_body, opaque_declaration(&smpl_name))
+        }
+
+        Data::Union(ref data) => {
+            return Err(Error::new_spanned(
+                data.union_token,
+                "SmplValue cannot be derived for unions",
+            ));
+        }
+    };
+
+    Ok(quote! {
+        impl ::smpli::SmplType for #name {
+            fn smpl_type() -> String {
+                String::from(#smpl_name)
+            }
+        }
+
+        impl ::smpli::FromSmpl for #name {
+            fn from_smpl(value: ::smpli::Value)
+                -> Result<Self, ::smpli::err::ConversionError> {
+                #from_body
+            }
+        }
+
+        impl ::smpli::ToSmpl for #name {
+            fn to_smpl(self) -> ::smpli::Value {
+                #to_body
+            }
+        }
+
+        impl ::smpli::SmplDeclaration for #name {
+            fn smpl_declaration() -> String {
+                #declaration
+            }
+        }
+    })
+}
+
+fn opaque_declaration(smpl_name: &str) -> TokenStream2 {
+    let declaration = format!("opaque {};\n", smpl_name);
+    quote!(String::from(#declaration))
+}
+
+///
+/// SMPL field names of `fields`
+///
+fn field_names(fields: &Fields) -> Vec<String> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match field.ident {
+            Some(ref ident) => ident.to_string(),
+            None => index.to_string(),
+        })
+        .collect()
+}
+
+///
+/// Bindings for each field of `fields` when destructuring
+///
+fn field_bindings(fields: &Fields) -> Vec<Ident> {
+    (0..fields.len())
+        .map(|index| Ident::new(&format!("field_{}", index), Span::call_site()))
+        .collect()
+}
+
+///
+/// Expression constructing `path` from the fields of `smpl_struct`
+///
+fn from_fields(path: TokenStream2, fields: &Fields) -> (TokenStream2, Vec<String>) {
+    let names = field_names(fields);
+    let types = fields.iter().map(|f| &f.ty);
+    let take = names.iter().zip(types).map(|(name, ty)| {
+        quote!(::smpli::__private::take_field::<Self, #ty>(&smpl_struct, #name)?)
+    });
+
+    let construct = match fields {
+        Fields::Named(ref named) => {
+            let idents = named.named.iter().map(|f| f.ident.as_ref().unwrap());
+            quote!(#path { #(#idents: #take),* })
+        }
+
+        Fields::Unnamed(..) => quote!(#path ( #(#take),* )),
+
+        Fields::Unit => quote!(#path),
+    };
+
+    (construct, names)
+}
+
+///
+/// Pattern destructuring `path` and statements setting each field on `smpl_struct`
+///
+fn to_fields(path: TokenStream2, fields: &Fields) -> (TokenStream2, TokenStream2) {
+    let names = field_names(fields);
+    let bindings = field_bindings(fields);
+
+    let destruct = match fields {
+        Fields::Named(ref named) => {
+            let idents = named.named.iter().map(|f| f.ident.as_ref().unwrap());
+            quote!(#path { #(#idents: #bindings),* })
+        }
+
+        Fields::Unnamed(..) => quote!(#path ( #(#bindings),* )),
+
+        Fields::Unit => quote!(#path),
+    };
+
+    let set = quote! {
+        #(
+            smpl_struct.set_field(#names.to_string(), ::smpli::ToSmpl::to_smpl(#bindings));
+        )*
+    };
+
+    (destruct, set)
+}
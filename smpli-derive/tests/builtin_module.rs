@@ -0,0 +1,83 @@
+use failure::{ err_msg, Error };
+
+use smpli::*;
+use smpli_derive::smpl_module;
+
+#[smpl_module(name = "text")]
+mod text_builtins {
+    use super::*;
+
+    pub fn repeat(s: String, times: i64) -> Result<String, Error> {
+        if times < 0 {
+            Err(err_msg("Negative length"))?;
+        }
+
+        Ok(s.repeat(times as usize))
+    }
+
+    pub fn first_word(s: String) -> Option<String> {
+        s.split_whitespace().next().map(|w| w.to_string())
+    }
+
+    pub fn nothing() {}
+
+    // Not a builtin
+    #[allow(dead_code)]
+    fn helper() -> i64 {
+        0
+    }
+}
+
+#[test]
+fn builtin_module_declaration() {
+    assert_eq!("text", text_builtins::MOD_NAME);
+    assert_eq!(
+"mod text;
+
+use option;
+
+builtin fn repeat(s: String, times: int) -> String;
+builtin fn first_word(s: String) -> option::Option(type String);
+builtin fn nothing();
+", text_builtins::declaration());
+}
+
+fn run(fn_name: &str) -> Result<Value, Error> {
+    let code =
+"mod mod1;
+
+use option;
+use text;
+
+fn test() -> String {
+    text::nothing();
+    return text::repeat(\"ab\", 3);
+}
+
+fn test_word() -> String {
+    return option::unwrap(type String)(text::first_word(\"hello world\"));
+}
+
+fn test_error() -> String {
+    return text::repeat(\"ab\", -1);
+}";
+
+    let parsed = parse_module(UnparsedModule::anonymous(code)).unwrap();
+    let modules = vec![VmModule::new(parsed), text_builtins::vm_module()];
+
+    let avm = AVM::new(Std::std(), modules).unwrap();
+    let handle = avm.query_module("mod1", fn_name).unwrap().unwrap();
+
+    avm.spawn_executor(handle, vec![], SpawnOptions {
+        type_check: false
+    })
+        .unwrap()
+        .execute_sync()
+}
+
+#[test]
+fn builtin_module_executor() {
+    assert_eq!(Value::String("ababab".to_string()), run("test").unwrap());
+    assert_eq!(Value::String("hello".to_string()), run("test_word").unwrap());
+    assert!(run("test_error").is_err());
+}
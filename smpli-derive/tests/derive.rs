@@ -0,0 +1,137 @@
+use smpli::*;
+use smpli_derive::SmplValue;
+
+#[derive(SmplValue, Debug, Clone, PartialEq)]
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+#[derive(SmplValue, Debug, Clone, PartialEq)]
+struct Labeled {
+    label: String,
+    point: Point,
+    weight: Option<f64>,
+}
+
+#[derive(SmplValue, Debug, Clone, PartialEq)]
+struct Pair(i64, bool);
+
+#[derive(SmplValue, Debug, Clone, PartialEq)]
+struct Marker;
+
+#[derive(SmplValue, Debug, Clone, PartialEq)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect { width: i64, height: i64 },
+}
+
+fn run(code: &str, fn_name: &str, args: Vec<Value>) -> Value {
+    let parsed = parse_module(UnparsedModule::anonymous(code)).unwrap();
+    let module = VmModule::new(parsed);
+
+    let avm = AVM::new(Std::std(), vec![module]).unwrap();
+    let handle = avm.query_module("mod1", fn_name).unwrap().unwrap();
+
+    avm.spawn_executor(handle, args, SpawnOptions {
+        type_check: false
+    })
+        .unwrap()
+        .execute_sync()
+        .unwrap()
+}
+
+#[test]
+fn derive_declaration() {
+    assert_eq!("struct Point {\n    x: int,\n    y: int,\n}\n",
+        Point::smpl_declaration());
+    assert_eq!("struct Labeled {\n    label: String,\n    point: Point,\n    weight: option::Option(type float),\n}\n",
+        Labeled::smpl_declaration());
+    assert_eq!("opaque Pair;\n", Pair::smpl_declaration());
+    assert_eq!("struct Marker { }\n", Marker::smpl_declaration());
+    assert_eq!("opaque Shape;\n", Shape::smpl_declaration());
+}
+
+#[test]
+fn derive_round_trip() {
+    let labeled = Labeled {
+        label: "origin".to_string(),
+        point: Point { x: 0, y: 0 },
+        weight: Some(1.5),
+    };
+
+    assert_eq!(labeled.clone(), Labeled::from_smpl(labeled.to_smpl()).unwrap());
+    assert_eq!(Pair(1, true), Pair::from_smpl(Pair(1, true).to_smpl()).unwrap());
+    assert_eq!(Marker, Marker::from_smpl(Marker.to_smpl()).unwrap());
+
+    for shape in [Shape::Empty, Shape::Circle(2.0), Shape::Rect { width: 1, height: 2 }] {
+        assert_eq!(shape.clone(), Shape::from_smpl(shape.to_smpl()).unwrap());
+    }
+
+    assert!(Point::from_smpl(Value::Int(1)).is_err());
+    assert!(Point::from_smpl(Pair(1, true).to_smpl()).is_err());
+}
+
+#[test]
+fn derive_executor_struct() {
+    let code = format!(
+"mod mod1;
+
+{}
+fn shift(p: Point) -> Point {{
+    return init Point {{ x: p.x + 1, y: p.y * 2 }};
+}}", Point::smpl_declaration());
+
+    let result = run(&code, "shift", vec![Point { x: 1, y: 5 }.to_smpl()]);
+
+    assert_eq!(Point { x: 2, y: 10 }, Point::from_smpl(result).unwrap());
+}
+
+#[test]
+fn derive_executor_nested() {
+    let code = format!(
+"mod mod1;
+
+use option;
+
+{}
+{}
+fn relabel(l: Labeled) -> Labeled {{
+    return init Labeled {{
+        label: \"moved\",
+        point: init Point {{ x: l.point.y, y: l.point.x }},
+        weight: option::none(type float)(),
+    }};
+}}", Point::smpl_declaration(), Labeled::smpl_declaration());
+
+    let input = Labeled {
+        label: "a".to_string(),
+        point: Point { x: 3, y: 4 },
+        weight: Some(1.0),
+    };
+
+    let result = run(&code, "relabel", vec![input.to_smpl()]);
+
+    assert_eq!(Labeled {
+        label: "moved".to_string(),
+        point: Point { x: 4, y: 3 },
+        weight: None,
+    }, Labeled::from_smpl(result).unwrap());
+}
+
+#[test]
+fn derive_executor_enum() {
+    let code = format!(
+"mod mod1;
+
+{}
+fn id(s: Shape) -> Shape {{
+    return s;
+}}", Shape::smpl_declaration());
+
+    let shape = Shape::Rect { width: 3, height: 4 };
+    let result = run(&code, "id", vec![shape.clone().to_smpl()]);
+
+    assert_eq!(shape, Shape::from_smpl(result).unwrap());
+}
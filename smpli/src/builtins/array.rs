@@ -0,0 +1,173 @@
+use failure::Error;
+use smpl::prelude::{UnparsedModule, parse_module};
+
+use crate::*;
+
+pub const ARRAY_LEN: &'static str = "len";
+pub const ARRAY_PUSH: &'static str = "push";
+pub const ARRAY_POP: &'static str = "pop";
+pub const ARRAY_INSERT: &'static str = "insert";
+pub const ARRAY_REMOVE: &'static str = "remove";
+pub const ARRAY_CLEAR: &'static str = "clear";
+
+const ARRAY_DECLARATION: &'static str = include_str!("array.smpl");
+
+pub fn vm_module() -> VmModule {
+    let input = UnparsedModule::anonymous(ARRAY_DECLARATION);
+    let parsed = parse_module(input).unwrap();
+
+    let module = VmModule::new(parsed)
+        .add_builtin(ARRAY_LEN,    super::erase(len))
+        .add_builtin(ARRAY_PUSH,   super::erase(push))
+        .add_builtin(ARRAY_POP,    super::erase(pop))
+        .add_builtin(ARRAY_INSERT, super::erase(insert))
+        .add_builtin(ARRAY_REMOVE, super::erase(remove))
+        .add_builtin(ARRAY_CLEAR,  super::erase(clear));
+
+    module
+}
+
+#[derive(Fail, Debug)]
+pub enum ArrayError {
+    #[fail(display = "Index '{}' out of range ('{}')", _0, _1)]
+    IndexOutOfRange(i64, usize),
+
+    #[fail(display = "Cannot pop from an empty array")]
+    Empty,
+
+    #[fail(display = "Expected a reference to an array")]
+    ExpectedReference,
+
+    #[fail(display = "Expected an array")]
+    ExpectedArray,
+}
+
+fn array_ref(value: Value) -> Result<ReferableValue, ArrayError> {
+    match value {
+        Value::Ref(r) => Ok(r),
+        _ => Err(ArrayError::ExpectedReference),
+    }
+}
+
+fn array_mut(value: &mut Value) -> Result<&mut Array, ArrayError> {
+    match *value {
+        Value::Array(ref mut a) => Ok(a),
+        _ => Err(ArrayError::ExpectedArray),
+    }
+}
+
+async fn len(args: Vec<Value>) -> Result<Value, Error> {
+    let mut args = exact_args!(1, args)?;
+    let array = args.pop().unwrap();
+    let array = match array {
+        Value::Array(a) => a,
+        _ => Err(ArrayError::ExpectedArray)?,
+    };
+
+    Ok(Value::Int(array.len() as i64))
+}
+
+async fn push(args: Vec<Value>) -> Result<Value, Error> {
+    let mut args = exact_args!(2, args)?;
+
+    let to_push = args.pop().unwrap();
+
+    let array = args.pop().unwrap();
+    let array = array_ref(array)?;
+
+    let mut borrow = array.inner_ref_mut();
+    let array = array_mut(&mut *borrow)?;
+    array.push(to_push);
+
+    Ok(Value::Unit)
+}
+
+async fn pop(args: Vec<Value>) -> Result<Value, Error> {
+    let mut args = exact_args!(1, args)?;
+
+    let array = args.pop().unwrap();
+    let array = array_ref(array)?;
+
+    let mut borrow = array.inner_ref_mut();
+    let array = array_mut(&mut *borrow)?;
+
+    let item = array
+        .pop()
+        .map(|rc| rc.clone_value())
+        .ok_or(ArrayError::Empty)?;
+
+    Ok(item)
+}
+
+async fn insert(args: Vec<Value>) -> Result<Value, Error> {
+    let mut args = exact_args!(3, args)?;
+
+    let to_insert = args.pop().unwrap();
+    let index = args.pop().unwrap();
+    let smpl_index = irmatch!(index; Value::Int(i) => i);
+
+    let array = args.pop().unwrap();
+    let array = array_ref(array)?;
+
+    let mut borrow = array.inner_ref_mut();
+    let array = array_mut(&mut *borrow)?;
+
+    // Inserting at the length appends
+    if smpl_index < 0 || smpl_index as usize > array.len() {
+        return Err(ArrayError::IndexOutOfRange(smpl_index, array.len()))?;
+    }
+
+    array.insert(smpl_index as usize, to_insert);
+
+    Ok(Value::Unit)
+}
+
+async fn remove(args: Vec<Value>) -> Result<Value, Error> {
+    let mut args = exact_args!(2, args)?;
+
+    let index = args.pop().unwrap();
+    let smpl_index = irmatch!(index; Value::Int(i) => i);
+
+    let array = args.pop().unwrap();
+    let array = array_ref(array)?;
+
+    let mut borrow = array.inner_ref_mut();
+    let array = array_mut(&mut *borrow)?;
+
+    if smpl_index < 0 || smpl_index as usize >= array.len() {
+        return Err(ArrayError::IndexOutOfRange(smpl_index, array.len()))?;
+    }
+
+    let item = array.remove(smpl_index as usize).clone_value();
+
+    Ok(item)
+}
+
+async fn clear(args: Vec<Value>) -> Result<Value, Error> {
+    let mut args = exact_args!(1, args)?;
+
+    let array = args.pop().unwrap();
+    let array = array_ref(array)?;
+
+    let mut borrow = array.inner_ref_mut();
+    let array = array_mut(&mut *borrow)?;
+    array.clear();
+
+    Ok(Value::Unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn array_builtin_non_reference() {
+        let array = || Value::Array(Array::new_init(vec![Value::Int(1)]));
+
+        let pushed = futures::executor::block_on(push(vec![array(), Value::Int(2)]));
+        assert!(pushed.unwrap_err().downcast::<ArrayError>().is_ok());
+
+        let cleared = futures::executor::block_on(clear(vec![Value::Int(2)]));
+        assert!(cleared.unwrap_err().downcast::<ArrayError>().is_ok());
+    }
+}
@@ -1,36 +1,8 @@
-use failure::Error;
-use smpl::prelude::{UnparsedModule, parse_module};
+use smpli_derive::smpl_module;
 
-use crate::exact_args;
-use crate::*;
+pub use self::functions::vm_module;
 
-pub const MOD_CONVERT: &'static str = "convert";
-
-pub const CONVERT_INT_TO_FLOAT: &'static str = "int_to_float";
-pub const CONVERT_FLOAT_TO_INT: &'static str = "float_to_int";
-
-pub const CONVERT_IS_FLOAT: &'static str = "is_float";
-pub const CONVERT_IS_INT: &'static str = "is_int";
-
-pub const CONVERT_STRING_TO_FLOAT: &'static str = "string_to_float";
-pub const CONVERT_STRING_TO_INT: &'static str = "string_to_int";
-
-pub const CONVERT_DECLARATION: &'static str = include_str!("convert.smpl");
-
-pub fn vm_module() -> VmModule {
-    let input = UnparsedModule::anonymous(CONVERT_DECLARATION);
-    let parsed = parse_module(input).unwrap();
-
-    let module = VmModule::new(parsed)
-        .add_builtin(CONVERT_INT_TO_FLOAT,      super::erase(int_to_float))
-        .add_builtin(CONVERT_FLOAT_TO_INT,      super::erase(float_to_int))
-        .add_builtin(CONVERT_IS_FLOAT,          super::erase(is_float))
-        .add_builtin(CONVERT_IS_INT,            super::erase(is_int))
-        .add_builtin(CONVERT_STRING_TO_FLOAT,   super::erase(string_to_float))
-        .add_builtin(CONVERT_STRING_TO_INT,     super::erase(string_to_int));
-
-    module
-}
+pub const MOD_CONVERT: &str = functions::MOD_NAME;
 
 #[derive(Fail, Debug)]
 #[fail(display = "Error converting '{}' to {}'", _0, _1)]
@@ -46,69 +18,53 @@ pub enum ConversionTarget {
     Float,
 }
 
-async fn int_to_float(args: Vec<Value>) -> Result<Value, Error> {
-    let mut args = exact_args!(1, args)?;
+#[smpl_module(name = "convert")]
+mod functions {
+    use super::{ ConversionError, ConversionTarget };
 
-    let a = args.remove(0);
-    match a {
-        Value::Int(i) => Ok(Value::Float(i as f64)),
-        _ => unreachable!(),
+    pub fn int_to_float(i: i64) -> f64 {
+        i as f64
     }
-}
 
-async fn float_to_int(args: Vec<Value>) -> Result<Value, Error> {
-    let mut args = exact_args!(1, args)?;
-
-    let a = args.remove(0);
-    match a {
-        Value::Float(f) => Ok(Value::Int(f as i64)),
-        _ => unreachable!(),
+    pub fn float_to_int(f: f64) -> i64 {
+        f as i64
     }
-}
 
-async fn is_float(args: Vec<Value>) -> Result<Value, Error> {
-    let mut args = exact_args!(1, args)?;
-
-    let a = args.remove(0);
-    match a {
-        Value::String(s) => Ok(Value::Bool(s.parse::<f32>().is_ok())),
-        _ => unreachable!(),
+    pub fn is_float(s: String) -> bool {
+        s.parse::<f32>().is_ok()
     }
-}
 
-async fn is_int(args: Vec<Value>) -> Result<Value, Error> {
-    let mut args = exact_args!(1, args)?;
-
-    let a = args.remove(0);
-    match a {
-        Value::String(s) => Ok(Value::Bool(s.parse::<i32>().is_ok())),
-        _ => unreachable!(),
+    pub fn is_int(s: String) -> bool {
+        s.parse::<i32>().is_ok()
     }
-}
 
-async fn string_to_float(args: Vec<Value>) -> Result<Value, Error> {
-    let mut args = exact_args!(1, args)?;
+    pub fn string_to_float(s: String) -> Result<f64, ConversionError> {
+        s.parse::<f64>()
+            .map_err(|_| ConversionError(s, ConversionTarget::Float))
+    }
 
-    let a = args.remove(0);
-    match a {
-        Value::String(s) => {
-            Ok(Value::Float(s.parse::<f64>().map_err(|_| {
-                ConversionError(s, ConversionTarget::Float)
-            })?))
-        }
-        _ => unreachable!(),
+    pub fn string_to_int(s: String) -> Result<i64, ConversionError> {
+        s.parse::<i64>()
+            .map_err(|_| ConversionError(s, ConversionTarget::Int))
     }
 }
 
-async fn string_to_int(args: Vec<Value>) -> Result<Value, Error> {
-    let mut args = exact_args!(1, args)?;
-
-    let a = args.remove(0);
-    match a {
-        Value::String(s) => Ok(Value::Int(
-            s.parse::<i64>()
-                .map_err(|_| ConversionError(s, ConversionTarget::Int))?,
-        )),
-        _ => unreachable!(),
+#[cfg(test)]
+#[cfg_attr(rustfmt, rustfmt_skip)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_declaration() {
+        assert_eq!(
+"mod convert;
+
+builtin fn int_to_float(i: int) -> float;
+builtin fn float_to_int(f: float) -> int;
+builtin fn is_float(s: String) -> bool;
+builtin fn is_int(s: String) -> bool;
+builtin fn string_to_float(s: String) -> float;
+builtin fn string_to_int(s: String) -> int;
+", functions::declaration());
     }
 }
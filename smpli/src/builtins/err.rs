@@ -1,27 +1,8 @@
-use failure::Error;
-use smpl::prelude::{UnparsedModule, parse_module};
+use smpli_derive::smpl_module;
 
-use crate::*;
+pub use self::functions::vm_module;
 
-pub const MOD_ERR: &'static str = "err";
-
-pub const ERR_PANIC: &'static str = "panic";
-pub const ERR_PANIC_MSG: &'static str = "panic_msg";
-pub const ERR_ASSERT: &'static str = "assert";
-
-pub const ERR_DECLARATION: &'static str = include_str!("err.smpl");
-
-pub fn vm_module() -> VmModule {
-    let input = UnparsedModule::anonymous(ERR_DECLARATION);
-    let parsed = parse_module(input).unwrap();
-
-    let module = VmModule::new(parsed)
-        .add_builtin(ERR_PANIC,     super::erase(panic))
-        .add_builtin(ERR_PANIC_MSG, super::erase(panic_msg))
-        .add_builtin(ERR_ASSERT,    super::erase(assert));
-
-    module
-}
+pub const MOD_ERR: &str = functions::MOD_NAME;
 
 #[derive(Fail, Debug)]
 pub struct RuntimeError(Option<String>);
@@ -35,27 +16,40 @@ impl std::fmt::Display for RuntimeError {
     }
 }
 
-async fn panic(_args: Vec<Value>) -> Result<Value, Error> {
-    Err(RuntimeError(None))?
-}
+#[smpl_module(name = "err")]
+mod functions {
+    use super::RuntimeError;
 
-async fn panic_msg(mut args: Vec<Value>) -> Result<Value, Error> {
-    let a = args.remove(0);
+    pub fn panic() -> Result<(), RuntimeError> {
+        Err(RuntimeError(None))
+    }
 
-    match a {
-        Value::String(s) => Err(RuntimeError(Some(s)))?,
-        _ => unreachable!(),
+    pub fn panic_msg(msg: String) -> Result<(), RuntimeError> {
+        Err(RuntimeError(Some(msg)))
+    }
+
+    pub fn assert(a: bool) -> Result<(), RuntimeError> {
+        if a {
+            Ok(())
+        } else {
+            Err(RuntimeError(Some("Assertion failed".to_string())))
+        }
     }
 }
 
-async fn assert(mut args: Vec<Value>) -> Result<Value, Error> {
-    let a = args.remove(0);
+#[cfg(test)]
+#[cfg_attr(rustfmt, rustfmt_skip)]
+mod tests {
+    use super::*;
 
-    let a = irmatch!(a; Value::Bool(a) => a);
+    #[test]
+    fn err_declaration() {
+        assert_eq!(
+"mod err;
 
-    if a {
-        Ok(Value::Unit)
-    } else {
-        Err(RuntimeError(Some("Assertion failed".to_string())))?
+builtin fn panic();
+builtin fn panic_msg(msg: String);
+builtin fn assert(a: bool);
+", functions::declaration());
     }
 }
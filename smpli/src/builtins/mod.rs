@@ -1,3 +1,7 @@
+// `convert`, `err` and `math` are declared from their Rust signatures with `#[smpl_module]`.
+//   The other modules keep a hand-written `.smpl` declaration because they need generic
+//   builtins, `UNCHECKED` parameters, opaque types, methods or functions written in SMPL.
+pub mod array;
 pub mod convert;
 pub mod err;
 pub mod log;
@@ -17,6 +17,15 @@ pub const VEC_GET: &'static str = "get";
 pub const VEC_REMOVE: &'static str = "remove";
 pub const VEC_CLEAR: &'static str = "clear";
 
+pub const VEC_METHOD_LEN: &'static str = "Vec::len";
+pub const VEC_METHOD_CONTAINS: &'static str = "Vec::contains";
+pub const VEC_METHOD_PUSH: &'static str = "Vec::push";
+pub const VEC_METHOD_INSERT: &'static str = "Vec::insert";
+pub const VEC_METHOD_GET_VALUE: &'static str = "Vec::get_value";
+pub const VEC_METHOD_GET: &'static str = "Vec::get";
+pub const VEC_METHOD_REMOVE: &'static str = "Vec::remove";
+pub const VEC_METHOD_CLEAR: &'static str = "Vec::clear";
+
 pub const VEC_DATA_KEY: &'static str = "__DATA";
 pub const VEC_LEN_KEY: &'static str = "__LEN";
 
@@ -35,7 +44,15 @@ pub fn vm_module() -> VmModule {
         .add_builtin(VEC_GET_VALUE, super::erase(get_value))
         .add_builtin(VEC_GET,       super::erase(get))
         .add_builtin(VEC_REMOVE,    super::erase(remove))
-        .add_builtin(VEC_CLEAR,     super::erase(clear));
+        .add_builtin(VEC_CLEAR,     super::erase(clear))
+        .add_builtin(VEC_METHOD_LEN,       super::erase(len))
+        .add_builtin(VEC_METHOD_CONTAINS,  super::erase(contains))
+        .add_builtin(VEC_METHOD_PUSH,      super::erase(push))
+        .add_builtin(VEC_METHOD_INSERT,    super::erase(insert))
+        .add_builtin(VEC_METHOD_GET_VALUE, super::erase(get_value))
+        .add_builtin(VEC_METHOD_GET,       super::erase(get))
+        .add_builtin(VEC_METHOD_REMOVE,    super::erase(remove))
+        .add_builtin(VEC_METHOD_CLEAR,     super::erase(clear));
 
     module
 }
@@ -449,6 +466,54 @@ return vec::contains(type int)(v, 20);
     assert_eq!(Value::Bool(false), result);
 }
 
+#[test]
+fn interpreter_vec_for_loop() {
+    let mod1 =
+"
+mod mod1;
+use vec;
+
+fn test() -> int {
+let v = vec::new(type int)();
+v = vec::push(type int)(v, 1);
+v = vec::push(type int)(v, 20);
+v = vec::push(type int)(v, 300);
+
+let sum = 0;
+for x in v {
+    sum = sum + x;
+}
+
+return sum;
+}
+";
+
+    let result = vec_test!(mod1, "mod1", "test", vec![]);
+
+    assert_eq!(Value::Int(321), result);
+}
+
+#[test]
+fn interpreter_vec_inferred_type_args() {
+    let mod1 =
+"
+mod mod1;
+use vec;
+
+fn test() -> int {
+let v = vec::new(type int)();
+v = vec::push(v, 123);
+v = vec::push(v, 456);
+
+return vec::len(v) + vec::get_value(v, 1);
+}
+";
+
+    let result = vec_test!(mod1, "mod1", "test", vec![]);
+
+    assert_eq!(Value::Int(2 + 456), result);
+}
+
 #[test]
 fn interpreter_vec_clear() {
     let mod1 =
@@ -471,4 +536,30 @@ return vec::len(type int)(cleared);
 
     assert_eq!(Value::Int(0), result);
 }
+
+#[test]
+fn interpreter_vec_methods() {
+    let mod1 =
+"
+mod mod1;
+use vec;
+
+fn test() -> int {
+let v = vec::new(type int)();
+v = v.push(123).push(456).insert(0, 789);
+
+let removed = v.remove(1);
+
+if v.contains(123) && removed.contains(123) == false {
+    return removed.len() + removed.get_value(0);
+}
+
+return 0;
+}
+";
+
+    let result = vec_test!(mod1, "mod1", "test", vec![]);
+
+    assert_eq!(Value::Int(2 + 789), result);
+}
 }
@@ -0,0 +1,602 @@
+use failure::Error;
+
+use crate::builtins::option::{
+    self,
+    OPTION_DATA_KEY,
+    OPTION_TAG_KEY,
+};
+use crate::err::{ ConversionError, InternalError };
+use crate::value::{ Array, Struct, Value };
+use crate::vm_i::{ ArgType, BuiltinFn, BuiltinResult };
+
+///
+/// Name of the SMPL type a Rust type converts to and from.
+///
+/// Used for diagnostics and generated builtin declarations.
+///
+pub trait SmplType {
+    fn smpl_type() -> String;
+
+    /// Modules a declaration using `smpl_type()` has to import
+    fn smpl_modules() -> Vec<String> {
+        Vec::new()
+    }
+}
+
+///
+/// Conversion from a SMPL `Value` into a Rust value.
+///
+pub trait FromSmpl: SmplType + Sized {
+    fn from_smpl(value: Value) -> Result<Self, ConversionError>;
+}
+
+///
+/// Conversion from a Rust value into a SMPL `Value`.
+///
+pub trait ToSmpl: SmplType {
+    fn to_smpl(self) -> Value;
+}
+
+///
+/// Name of the kind of value contained in `value`, for diagnostics
+///
+pub fn value_kind(value: &Value) -> &'static str {
+    match *value {
+        Value::Int(..) => "int",
+        Value::Float(..) => "float",
+        Value::Bool(..) => "bool",
+        Value::String(..) => "String",
+        Value::Array(..) => "array",
+        Value::Function(..) => "function",
+        Value::PartialApp(..) => "function",
+        Value::Struct(..) => "struct",
+        Value::Ref(..) => "reference",
+        Value::Unit => "()",
+    }
+}
+
+///
+/// SMPL declaration of a host type, to be included in a module's source.
+///
+/// Implemented by `#[derive(SmplValue)]` (see the `smpli-derive` crate).
+///
+pub trait SmplDeclaration: SmplType {
+    fn smpl_declaration() -> String;
+}
+
+pub(crate) fn mismatch<T: SmplType>(found: &Value) -> ConversionError {
+    ConversionError {
+        expected: T::smpl_type(),
+        found: value_kind(found).to_string(),
+    }
+}
+
+///
+/// Unwraps the struct representation of `T`
+///
+pub fn expect_struct<T: SmplType>(value: Value) -> Result<Struct, ConversionError> {
+    match value {
+        Value::Struct(s) => Ok(s),
+        v => Err(mismatch::<T>(&v)),
+    }
+}
+
+///
+/// Converts the field `name` of the struct representation of `Owner`
+///
+pub fn take_field<Owner, T>(s: &Struct, name: &str) -> Result<T, ConversionError>
+    where
+        Owner: SmplType,
+        T: FromSmpl,
+{
+    s.get_field(name)
+        .ok_or_else(|| ConversionError {
+            expected: Owner::smpl_type(),
+            found: format!("struct without field '{}'", name),
+        })
+        .and_then(T::from_smpl)
+}
+
+macro_rules! impl_scalar {
+    ($rust: ty, $smpl: expr, $variant: ident) => {
+        impl SmplType for $rust {
+            fn smpl_type() -> String {
+                $smpl.to_string()
+            }
+        }
+
+        impl FromSmpl for $rust {
+            fn from_smpl(value: Value) -> Result<Self, ConversionError> {
+                match value {
+                    Value::$variant(v) => Ok(v),
+                    v => Err(mismatch::<Self>(&v)),
+                }
+            }
+        }
+
+        impl ToSmpl for $rust {
+            fn to_smpl(self) -> Value {
+                Value::$variant(self)
+            }
+        }
+    }
+}
+
+impl_scalar!(i64, "int", Int);
+impl_scalar!(f64, "float", Float);
+impl_scalar!(bool, "bool", Bool);
+impl_scalar!(String, "String", String);
+
+impl SmplType for () {
+    fn smpl_type() -> String {
+        "()".to_string()
+    }
+}
+
+impl FromSmpl for () {
+    fn from_smpl(value: Value) -> Result<Self, ConversionError> {
+        match value {
+            Value::Unit => Ok(()),
+            v => Err(mismatch::<Self>(&v)),
+        }
+    }
+}
+
+impl ToSmpl for () {
+    fn to_smpl(self) -> Value {
+        Value::Unit
+    }
+}
+
+impl<T: SmplType> SmplType for Vec<T> {
+    fn smpl_type() -> String {
+        format!("[{}]", T::smpl_type())
+    }
+
+    fn smpl_modules() -> Vec<String> {
+        T::smpl_modules()
+    }
+}
+
+impl<T: FromSmpl> FromSmpl for Vec<T> {
+    fn from_smpl(value: Value) -> Result<Self, ConversionError> {
+        match value {
+            Value::Array(array) => array
+                .into_iter()
+                .map(|element| T::from_smpl(element.clone_value()))
+                .collect(),
+
+            v => Err(mismatch::<Self>(&v)),
+        }
+    }
+}
+
+impl<T: ToSmpl> ToSmpl for Vec<T> {
+    fn to_smpl(self) -> Value {
+        Value::Array(self
+            .into_iter()
+            .map(ToSmpl::to_smpl)
+            .collect::<Array>())
+    }
+}
+
+impl<T: SmplType> SmplType for Option<T> {
+    fn smpl_type() -> String {
+        format!("option::Option(type {})", T::smpl_type())
+    }
+
+    fn smpl_modules() -> Vec<String> {
+        let mut modules = vec!["option".to_string()];
+        modules.extend(T::smpl_modules());
+        modules
+    }
+}
+
+///
+/// Follows the representation used by the `option` std module.
+///
+impl<T: FromSmpl> FromSmpl for Option<T> {
+    fn from_smpl(value: Value) -> Result<Self, ConversionError> {
+        let tag = match value {
+            Value::Struct(ref inner) => inner.get_field(OPTION_TAG_KEY),
+            _ => None,
+        };
+
+        match tag {
+            Some(ref tag) if *tag == option::some_tag() => {
+                let data = match value {
+                    Value::Struct(ref inner) => inner.get_field(OPTION_DATA_KEY),
+                    _ => unreachable!(),
+                };
+
+                data
+                    .ok_or_else(|| mismatch::<Self>(&value))
+                    .and_then(T::from_smpl)
+                    .map(Some)
+            }
+
+            Some(ref tag) if *tag == option::none_tag() => Ok(None),
+
+            _ => Err(mismatch::<Self>(&value)),
+        }
+    }
+}
+
+impl<T: ToSmpl> ToSmpl for Option<T> {
+    fn to_smpl(self) -> Value {
+        match self {
+            Some(v) => option::make_some(v.to_smpl()),
+            None => option::make_none(),
+        }
+    }
+}
+
+///
+/// Tuples are represented as structs with fields named after each element's position
+///   (i.e. "0", "1", ...).
+///
+macro_rules! impl_tuple {
+    ($($element: ident : $index: tt),+) => {
+        impl<$($element: SmplType),+> SmplType for ($($element,)+) {
+            fn smpl_type() -> String {
+                let elements: Vec<String> = vec![$($element::smpl_type()),+];
+                format!("({})", elements.join(", "))
+            }
+
+            fn smpl_modules() -> Vec<String> {
+                let mut modules = Vec::new();
+                $(modules.extend($element::smpl_modules());)+
+                modules
+            }
+        }
+
+        impl<$($element: FromSmpl),+> FromSmpl for ($($element,)+) {
+            fn from_smpl(value: Value) -> Result<Self, ConversionError> {
+                let tuple = expect_struct::<Self>(value)?;
+
+                Ok(($(
+                    take_field::<Self, $element>(&tuple, stringify!($index))?,
+                )+))
+            }
+        }
+
+        impl<$($element: ToSmpl),+> ToSmpl for ($($element,)+) {
+            fn to_smpl(self) -> Value {
+                let mut tuple = Struct::new();
+                $(
+                    tuple.set_field(stringify!($index).to_string(), self.$index.to_smpl());
+                )+
+                Value::Struct(tuple)
+            }
+        }
+    }
+}
+
+impl_tuple!(A: 0);
+impl_tuple!(A: 0, B: 1);
+impl_tuple!(A: 0, B: 1, C: 2);
+impl_tuple!(A: 0, B: 1, C: 2, D: 3);
+impl_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+
+///
+/// Return types of typed builtins.
+///
+/// Implemented for any `T: ToSmpl` and for `Result<T, E>` where `E` converts into
+///   a `failure::Error`.
+///
+pub trait IntoBuiltinResult {
+    /// Type of the value returned to SMPL
+    type Output: SmplType;
+
+    fn into_builtin_result(self) -> BuiltinResult;
+}
+
+impl<T: ToSmpl> IntoBuiltinResult for T {
+    type Output = T;
+
+    fn into_builtin_result(self) -> BuiltinResult {
+        Ok(self.to_smpl())
+    }
+}
+
+impl<T: ToSmpl, E: Into<Error>> IntoBuiltinResult for Result<T, E> {
+    type Output = T;
+
+    fn into_builtin_result(self) -> BuiltinResult {
+        self.map(ToSmpl::to_smpl).map_err(Into::into)
+    }
+}
+
+///
+/// Rust functions which may be registered as builtins with `typed()`.
+///
+/// `Args` is a tuple of the function's parameter types.
+///
+pub trait TypedBuiltin<Args>: Copy + 'static {
+    fn call(self, args: ArgType) -> BuiltinResult;
+}
+
+fn convert_arg<T: FromSmpl>(index: usize, value: Value) -> Result<T, InternalError> {
+    T::from_smpl(value)
+        .map_err(|e| InternalError::InvalidArgType {
+            index,
+            found: e.found,
+            expected: e.expected,
+        })
+}
+
+impl<F, R> TypedBuiltin<()> for F
+    where
+        F: Fn() -> R + Copy + 'static,
+        R: IntoBuiltinResult,
+{
+    fn call(self, args: ArgType) -> BuiltinResult {
+        no_args!(args)?;
+        self().into_builtin_result()
+    }
+}
+
+macro_rules! impl_typed_builtin {
+    ($count: expr; $($arg: ident),+) => {
+        impl<Func, R, $($arg),+> TypedBuiltin<($($arg,)+)> for Func
+            where
+                Func: Fn($($arg),+) -> R + Copy + 'static,
+                R: IntoBuiltinResult,
+                $($arg: FromSmpl),+
+        {
+            #[allow(non_snake_case)]
+            fn call(self, args: ArgType) -> BuiltinResult {
+                let args = exact_args!($count, args)?;
+                let mut args = args.into_iter().enumerate();
+
+                $(
+                    let $arg = {
+                        let (index, value) = args.next().unwrap();
+                        convert_arg::<$arg>(index, value)?
+                    };
+                )+
+
+                self($($arg),+).into_builtin_result()
+            }
+        }
+    }
+}
+
+impl_typed_builtin!(1; A);
+impl_typed_builtin!(2; A, B);
+impl_typed_builtin!(3; A, B, C);
+impl_typed_builtin!(4; A, B, C, D);
+impl_typed_builtin!(5; A, B, C, D, E);
+impl_typed_builtin!(6; A, B, C, D, E, F);
+
+///
+/// Declaration of a checked builtin function. Unit return types are omitted.
+///
+/// ```ignore
+/// builtin_declaration("atan2", &[("r", f64::smpl_type()), ("other", f64::smpl_type())],
+///     f64::smpl_type())
+///
+/// // builtin fn atan2(r: float, other: float) -> float;
+/// ```
+///
+pub fn builtin_declaration(name: &str, params: &[(&str, String)], return_type: String) -> String {
+    let params: Vec<String> = params
+        .iter()
+        .map(|(param, param_type)| format!("{}: {}", param, param_type))
+        .collect();
+
+    if return_type == <()>::smpl_type() {
+        format!("builtin fn {}({});\n", name, params.join(", "))
+    } else {
+        format!("builtin fn {}({}) -> {};\n", name, params.join(", "), return_type)
+    }
+}
+
+///
+/// Source of a module of builtin declarations importing `uses`.
+///
+/// Imports are deduplicated and the module itself is never imported. Types report the
+///   modules they refer to with `SmplType::smpl_modules()`.
+///
+pub fn module_declaration(name: &str, uses: &[String], builtins: &[String]) -> String {
+    let mut imports: Vec<&str> = Vec::new();
+    for module in uses.iter() {
+        if module != name && !imports.contains(&module.as_str()) {
+            imports.push(module);
+        }
+    }
+
+    let mut declaration = format!("mod {};\n\n", name);
+
+    if !imports.is_empty() {
+        for module in imports {
+            declaration.push_str(&format!("use {};\n", module));
+        }
+        declaration.push('\n');
+    }
+
+    for builtin in builtins.iter() {
+        declaration.push_str(builtin);
+    }
+
+    declaration
+}
+
+///
+/// Converts an ordinary Rust function into a `BuiltinFn`.
+///
+/// Arguments are converted with `FromSmpl` and the return value with `ToSmpl`.
+/// Arity and argument type mismatches are reported as `InternalError`s.
+///
+/// ```ignore
+/// fn repeat(s: String, times: i64) -> Result<String, Error> { ... }
+///
+/// module.add_builtin("repeat", typed(repeat))
+/// ```
+///
+pub fn typed<F, Args>(_f: F) -> BuiltinFn
+    where
+        F: TypedBuiltin<Args>,
+{
+    assert_eq!(std::mem::size_of::<F>(), 0);
+    |args| {
+        // F is zero-sized (see erase())
+        let f = unsafe { std::mem::zeroed::<F>() };
+        Box::pin(futures::future::ready(f.call(args)))
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(rustfmt, rustfmt_skip)]
+mod tests {
+    use failure::Error;
+
+    use crate::*;
+    use crate::err::*;
+    use super::*;
+
+    fn round_trip<T: FromSmpl + ToSmpl + Clone + PartialEq + std::fmt::Debug>(v: T) {
+        assert_eq!(v.clone(), T::from_smpl(v.to_smpl()).unwrap());
+    }
+
+    #[test]
+    fn conversion_round_trip() {
+        round_trip(5i64);
+        round_trip(1.5f64);
+        round_trip(true);
+        round_trip("hello".to_string());
+        round_trip(());
+        round_trip(vec![1i64, 2, 3]);
+        round_trip(Some(vec![Some(1i64), None]));
+        round_trip(None::<String>);
+        round_trip((1i64, "a".to_string(), (false, 2.0f64)));
+    }
+
+    #[test]
+    fn conversion_mismatch() {
+        assert_eq!(ConversionError {
+            expected: "int".to_string(),
+            found: "bool".to_string(),
+        }, i64::from_smpl(Value::Bool(true)).unwrap_err());
+
+        assert_eq!(ConversionError {
+            expected: "String".to_string(),
+            found: "int".to_string(),
+        }, Vec::<String>::from_smpl(vec![1i64].to_smpl()).unwrap_err());
+
+        assert!(Option::<i64>::from_smpl(Value::Struct(Struct::new())).is_err());
+    }
+
+    #[test]
+    fn module_declaration_uses() {
+        assert_eq!(Vec::<String>::new(), Vec::<(i64, String)>::smpl_modules());
+        assert_eq!(vec!["option".to_string(), "option".to_string()],
+            Vec::<(bool, Option<Option<i64>>)>::smpl_modules());
+
+        // Imports are taken as given, not read from the declarations
+        let uses = vec![
+            "geo::shapes".to_string(),
+            "option".to_string(),
+            "option".to_string(),
+            "text".to_string(),
+        ];
+        let builtins = vec![
+            "builtin fn area(s: geo::shapes::Square) -> option::Option(type float);\n".to_string(),
+            "builtin fn sep() -> String;\n".to_string(),
+        ];
+
+        assert_eq!(
+"mod text;
+
+use geo::shapes;
+use option;
+
+builtin fn area(s: geo::shapes::Square) -> option::Option(type float);
+builtin fn sep() -> String;
+", module_declaration("text", &uses, &builtins));
+
+        assert_eq!("mod text;\n\n", module_declaration("text", &[], &[]));
+    }
+
+    fn check(i: i64, s: String) -> Result<bool, Error> {
+        if i < 0 {
+            Err(ConversionError {
+                expected: "positive int".to_string(),
+                found: i.to_string(),
+            })?;
+        }
+
+        Ok(s.len() as i64 == i)
+    }
+
+    fn find(haystack: Vec<String>, needle: String) -> Option<i64> {
+        haystack
+            .iter()
+            .position(|s| *s == needle)
+            .map(|i| i as i64)
+    }
+
+    fn call(f: BuiltinFn, args: Vec<Value>) -> BuiltinResult {
+        futures::executor::block_on(f(args))
+    }
+
+    #[test]
+    fn typed_builtin_call() {
+        let f = typed(check);
+
+        assert_eq!(Value::Bool(true),
+            call(f, vec![Value::Int(3), Value::String("abc".to_string())]).unwrap());
+        assert_eq!(Value::Bool(false),
+            call(f, vec![Value::Int(2), Value::String("abc".to_string())]).unwrap());
+        assert!(call(f, vec![Value::Int(-1), Value::String("".to_string())]).is_err());
+    }
+
+    #[test]
+    fn typed_builtin_arg_errors() {
+        let f = typed(check);
+
+        let e = call(f, vec![Value::Int(3)]).unwrap_err();
+        match e.downcast::<InternalError>().unwrap() {
+            InternalError::InvalidArgCount(1, ExpectedArgCount::Exact(2)) => (),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        let e = call(f, vec![Value::Int(3), Value::Int(3)]).unwrap_err();
+        match e.downcast::<InternalError>().unwrap() {
+            InternalError::InvalidArgType { index: 1, .. } => (),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn typed_builtin_interpreter() {
+        let code =
+"mod mod1;
+
+use option;
+
+builtin fn find(haystack: [String; 3], needle: String) -> option::Option(type int);
+
+fn test() -> int {
+    let opt = find([\"a\", \"b\", \"c\"], \"c\");
+    return option::unwrap(type int)(opt);
+}";
+
+        let parsed = parse_module(UnparsedModule::anonymous(code)).unwrap();
+        let module = VmModule::new(parsed)
+            .add_builtin("find", typed(find));
+
+        let avm = AVM::new(Std::std(), vec![module]).unwrap();
+        let handle = avm.query_module("mod1", "test").unwrap().unwrap();
+
+        let result = avm.spawn_executor(handle, vec![], SpawnOptions {
+            type_check: false
+        })
+            .unwrap()
+            .execute_sync()
+            .unwrap();
+
+        assert_eq!(Value::Int(2), result);
+    }
+}
@@ -0,0 +1,209 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use smpl::FnId;
+use smpl::byte_gen::{ Instruction, InstructionPointerType };
+use smpl::module::ModuleSource;
+
+use crate::vm::AVM;
+
+///
+/// Records which instructions were executed and which arms of each branch were taken.
+///
+/// Clones share the same underlying counts so one `Coverage` may be attached to
+///   multiple executors.
+///
+#[derive(Debug, Clone)]
+pub struct Coverage(Rc<RefCell<CoverageData>>);
+
+impl Default for Coverage {
+    fn default() -> Coverage {
+        Coverage::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BranchCount {
+    pub true_arm: u64,
+    pub false_arm: u64,
+}
+
+#[derive(Debug)]
+struct CoverageData {
+    hits: HashMap<FnId, HashMap<InstructionPointerType, u64>>,
+    branches: HashMap<FnId, HashMap<InstructionPointerType, BranchCount>>,
+}
+
+impl Coverage {
+    pub fn new() -> Coverage {
+        Coverage(Rc::new(RefCell::new(CoverageData {
+            hits: HashMap::new(),
+            branches: HashMap::new(),
+        })))
+    }
+
+    pub(crate) fn record(&self, fn_id: FnId, ip: InstructionPointerType,
+                         instruction: &Instruction, jumped: bool) {
+
+        let mut data = self.0.borrow_mut();
+
+        *data.hits
+            .entry(fn_id)
+            .or_default()
+            .entry(ip)
+            .or_default() += 1;
+
+        // Recover the value of the condition from whether or not the jump was taken
+        let condition = match instruction {
+            Instruction::JumpCondition(..)
+            | Instruction::RelJumpCondition(..) => jumped,
+
+            Instruction::JumpNegateCondition(..)
+            | Instruction::RelJumpNegateCondition(..) => !jumped,
+
+            _ => return,
+        };
+
+        let count = data.branches
+            .entry(fn_id)
+            .or_default()
+            .entry(ip)
+            .or_default();
+
+        if condition {
+            count.true_arm += 1;
+        } else {
+            count.false_arm += 1;
+        }
+    }
+
+    ///
+    /// Number of times the instruction at `ip` in function `fn_id` was executed
+    ///
+    pub fn hits(&self, fn_id: FnId, ip: InstructionPointerType) -> u64 {
+        self.0
+            .borrow()
+            .hits
+            .get(&fn_id)
+            .and_then(|map| map.get(&ip))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    ///
+    /// Number of times each arm of the conditional jump at `ip` in function `fn_id` was taken
+    ///
+    pub fn branch(&self, fn_id: FnId, ip: InstructionPointerType) -> BranchCount {
+        self.0
+            .borrow()
+            .branches
+            .get(&fn_id)
+            .and_then(|map| map.get(&ip))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Generates an lcov tracefile record for every module loaded from a file
+    ///   (`ModuleSource::File`).
+    ///
+    /// Lines are taken from the spans of the CFG nodes which generated each instruction.
+    /// Each `BranchSplit` (and loop condition) is reported as one block with
+    ///   branch 0 as the true arm and branch 1 as the false arm.
+    ///
+    pub fn lcov(&self, vm: &AVM) -> HashMap<PathBuf, String> {
+        let mut files: HashMap<PathBuf, FileCoverage> = HashMap::new();
+
+        let mut fns: Vec<_> = vm.compiled().iter().collect();
+        fns.sort_by_key(|(fn_id, _)| **fn_id);
+
+        for (fn_id, byte_code) in fns {
+            let path = match vm.fn_source(*fn_id) {
+                Some(ModuleSource::File(ref path)) => path.clone(),
+
+                _ => continue,
+            };
+
+            let file = files
+                .entry(path)
+                .or_insert_with(FileCoverage::new);
+
+            let source_map = byte_code.source_map();
+            for (ip, span) in source_map.spans() {
+                if let Some(span) = span {
+                    let count = file.lines
+                        .entry(span.start().line)
+                        .or_insert(0);
+
+                    *count = std::cmp::max(*count, self.hits(*fn_id, ip));
+                }
+            }
+
+            for (ip, span) in source_map.branches() {
+                let executed = self.hits(*fn_id, ip) > 0;
+                file.branches.push((span.start().line, executed, self.branch(*fn_id, ip)));
+            }
+        }
+
+        files
+            .into_iter()
+            .map(|(path, file)| {
+                let record = file.into_lcov(&path);
+                (path, record)
+            })
+            .collect()
+    }
+}
+
+struct FileCoverage {
+    lines: BTreeMap<usize, u64>,
+    branches: Vec<(usize, bool, BranchCount)>,
+}
+
+impl FileCoverage {
+    fn new() -> FileCoverage {
+        FileCoverage {
+            lines: BTreeMap::new(),
+            branches: Vec::new(),
+        }
+    }
+
+    fn into_lcov(mut self, path: &Path) -> String {
+        let mut record = String::new();
+
+        writeln!(record, "TN:").unwrap();
+        writeln!(record, "SF:{}", path.display()).unwrap();
+
+        self.branches.sort_by_key(|(line, _, _)| *line);
+
+        let mut branches_hit = 0;
+        for (block, (line, executed, count)) in self.branches.iter().enumerate() {
+            for (branch, taken) in [count.true_arm, count.false_arm].iter().enumerate() {
+                if *executed {
+                    writeln!(record, "BRDA:{},{},{},{}", line, block, branch, taken).unwrap();
+                } else {
+                    writeln!(record, "BRDA:{},{},{},-", line, block, branch).unwrap();
+                }
+
+                if *taken > 0 {
+                    branches_hit += 1;
+                }
+            }
+        }
+        writeln!(record, "BRF:{}", self.branches.len() * 2).unwrap();
+        writeln!(record, "BRH:{}", branches_hit).unwrap();
+
+        for (line, count) in self.lines.iter() {
+            writeln!(record, "DA:{},{}", line, count).unwrap();
+        }
+        writeln!(record, "LF:{}", self.lines.len()).unwrap();
+        writeln!(record, "LH:{}", self.lines.values().filter(|c| **c > 0).count()).unwrap();
+
+        writeln!(record, "end_of_record").unwrap();
+
+        record
+    }
+}
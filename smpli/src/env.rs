@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::mem;
 
 use super::value::{ Value, ReferableValue };
 
@@ -6,6 +7,8 @@ use super::value::{ Value, ReferableValue };
 pub struct Env {
     env: HashMap<String, ReferableValue>,
     tmp_store: HashMap<String, ReferableValue>,
+    /// Module globals shared (not copied) by every forked environment
+    globals: HashMap<String, ReferableValue>,
 }
 
 impl Env {
@@ -13,6 +16,7 @@ impl Env {
         Env {
             env: HashMap::new(),
             tmp_store: HashMap::new(),
+            globals: HashMap::new(),
         }
     }
 
@@ -29,13 +33,32 @@ impl Env {
                 (key.clone(), referable.hard_clone())
             }).collect();
 
+        let globals = self.globals
+            .iter()
+            .map(|(key, referable)| {
+                (key.clone(), referable.ref_clone())
+            }).collect();
+
         Env {
             env: env,
             tmp_store: tmp_store,
+            globals: globals,
         }
     }
 
+    pub fn map_global(&mut self, name: String, value: Value) -> Option<Value> {
+        self.globals
+            .insert(name, ReferableValue::new(value))
+            .map(|rv| rv.clone_value())
+    }
+
     pub fn map_value(&mut self, name: String, value: Value) -> Option<Value> {
+        // Assigning a global updates the value seen by every environment
+        if let Some(global) = self.globals.get(&name) {
+            let old = mem::replace(&mut *global.inner_ref_mut(), value);
+            return Some(old);
+        }
+
         self.env
             .insert(name, ReferableValue::new(value))
             .map(|rv| rv.clone_value())
@@ -48,11 +71,15 @@ impl Env {
     }
 
     pub fn get_value(&self, name: &str) -> Option<Value> {
-        self.env.get(name).map(|r| r.clone_value())
+        self.env.get(name)
+            .or_else(|| self.globals.get(name))
+            .map(|r| r.clone_value())
     }
 
     pub fn ref_value(&self, name: &str) -> Option<ReferableValue> {
-        self.env.get(name).map(|r| r.ref_clone())
+        self.env.get(name)
+            .or_else(|| self.globals.get(name))
+            .map(|r| r.ref_clone())
     }
 
     pub fn get_tmp(&self, name: &str) -> Option<Value> {
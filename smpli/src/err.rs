@@ -1,4 +1,6 @@
-use failure::Fail;
+use std::sync::Arc;
+
+use failure::{ Error, Fail };
 
 use smpl::ModuleId;
 use smpl::error::Error as StaticError;
@@ -11,6 +13,8 @@ pub enum VmError {
     NotABuiltin(ModuleFnPair),
     NotAFn(ModuleFnPair),
     NotAModule(String),
+    /// A static initializer failed at runtime
+    StaticInit(Arc<Error>),
 }
 
 impl<T> From<T> for VmError where T: Into<StaticError> {
@@ -59,6 +63,13 @@ pub enum InternalError {
     RuntimeInstructionError(RuntimeInstructionError),
 }
 
+#[derive(Fail, Debug, Clone, PartialEq)]
+#[fail(display = "Unable to convert {} into {}", found, expected)]
+pub struct ConversionError {
+    pub expected: String,
+    pub found: String,
+}
+
 #[derive(Fail, Debug, Clone)]
 pub enum RuntimeInstructionError {
     // TODO: canonical string representation of instructions
@@ -74,9 +85,40 @@ pub enum RuntimeInstructionError {
     #[fail(display = "Expected function in: {:?}", _0)]
     ExpectedFunction(Instruction),
 
+    #[fail(display = "Expected reference in: {:?}", _0)]
+    ExpectedReference(Instruction),
+
+    #[fail(display = "Expected int, float, bool or String in: {:?}", _0)]
+    ExpectedFormattable(Instruction),
+
+    #[fail(display = "Negative shift amount {} in: {:?}", _1, _0)]
+    NegativeShift(Instruction, i64),
+
     #[fail(display = "No return found for instruction at {}", _0)]
     NoReturnValue(InstructionPointerType),
 
+    #[fail(display = "Expected array in: {:?}", _0)]
+    ExpectedArray(Instruction),
+
+    #[fail(display = "Indexed a value that is not an array")]
+    IndexedNonArray,
+
+    #[fail(display = "Index '{}' is not an int", _0)]
+    NonIntIndex(String),
+
+    #[fail(display = "Index {} out of bounds for array of length {}", index, len)]
+    IndexOutOfBounds {
+        index: i64,
+        len: usize,
+    },
+
+    #[fail(display = "Invalid slice range [{}..{}] for array of length {}", start, end, len)]
+    InvalidSlice {
+        start: i64,
+        end: i64,
+        len: usize,
+    },
+
     #[fail(display = "Attempting to add {} to current IP({}) results in underflow", addition, current)]
     IPUnderflow {
         current: InstructionPointerType,
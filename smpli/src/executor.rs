@@ -10,9 +10,10 @@ use smpl::byte_gen::{ InstructionPointerType, Instruction, Location, Arg, FieldA
 
 use crate::err::*;
 use crate::env::Env;
-use crate::value::{ Value, ReferableValue, Struct, Array };
+use crate::coverage::Coverage;
+use crate::value::{ Value, ReferableValue, Struct, Array, PartialApp, SendValue };
 use crate::vm_i::{ FnHandle, BuiltinFn };
-use crate::vm::{ MappedBuiltins, CompiledProgram };
+use crate::vm::{ MappedBuiltins, CompiledProgram, SharedStatics };
 
 pub enum ExecResult<T, E> {
     Ok(T),
@@ -29,7 +30,9 @@ pub struct Executor {
     builtins: MappedBuiltins,
     return_register: Option<Value>,
     module_env: Env,
+    statics: SharedStatics,
     finished: bool,
+    coverage: Option<Coverage>,
 }
 
 impl Executor {
@@ -37,10 +40,16 @@ impl Executor {
                       fn_handle: FnHandle,
                       compiled: CompiledProgram,
                       builtins: MappedBuiltins,
+                      statics: SharedStatics,
                       args: Vec<Value>) -> Result<Executor, InternalError> {
 
         let mut module_env = Env::new();
 
+        // Statics are shared by every stack frame
+        for (name, value) in statics.lock().expect("Statics lock poisoned").iter() {
+            module_env.map_global(name.clone(), value.clone().into());
+        }
+
         // Make all functions available to the execution environment
         for (fn_id, _) in compiled.iter() {
             let scoped_handle = Value::Function(
@@ -72,12 +81,22 @@ impl Executor {
             builtins: builtins,
             return_register: None,
             module_env: module_env,
+            statics: statics,
             finished: false,
+            coverage: None,
         };
 
         Ok(executor)
     }
 
+    ///
+    /// Record every instruction executed by this executor into `coverage`
+    ///
+    pub fn track_coverage(mut self, coverage: Coverage) -> Executor {
+        self.coverage = Some(coverage);
+        self
+    }
+
     pub fn execute_sync(mut self) -> Result<Value, Error> {
         futures::executor::block_on(self.execute())
     }
@@ -90,6 +109,16 @@ impl Executor {
         Ok(self.return_register.take().unwrap_or(Value::Unit))
     }
 
+    /// Shares the statics with executors spawned later
+    fn store_statics(&self) {
+        let mut statics = self.statics.lock().expect("Statics lock poisoned");
+        for (name, value) in statics.iter_mut() {
+            if let Some(current) = self.module_env.get_value(name) {
+                *value = SendValue::from(&current);
+            }
+        }
+    }
+
     fn create_stack_info(metadata: &Metadata,
                       fn_handle: FnHandle,
                       compiled: CompiledProgram,
@@ -147,6 +176,7 @@ impl Executor {
             }
 
             StackInfo::ByteCodeStack(ByteCodeStack {
+                ref handle,
                 ref current_fn,
                 ref compiled,
                 ref builtins,
@@ -170,6 +200,18 @@ impl Executor {
                     &mut self.return_register,
                 )?;
 
+                if let Some(ref coverage) = self.coverage {
+                    let jumped = match execute_action {
+                        ExecuteAction::SetIP(_) | ExecuteAction::AddIP(_) => true,
+                        _ => false,
+                    };
+
+                    coverage.record(handle.fn_id(),
+                                    *instruction_pointer,
+                                    instruction,
+                                    jumped);
+                }
+
                 match execute_action {
                     ExecuteAction::PushStack(..) | ExecuteAction::IncrementIP => {
                         let (result, overflow) =
@@ -273,6 +315,7 @@ impl Executor {
 
                         self.finished = true;
                         self.return_register = Some(value);
+                        self.store_statics();
                         Ok(())
                     }
                 }
@@ -284,7 +327,7 @@ impl Executor {
         }
     }
 
-    fn fetch(env: &Env, location: &Location) -> ReferableValue {
+    fn fetch(env: &Env, location: &Location) -> Result<ReferableValue, InternalError> {
 
         match location {
             Location::Compound {
@@ -296,28 +339,7 @@ impl Executor {
 
                 let root_ref: ReferableValue = match root_index {
                     Some(index_name) => {
-                        let index_value: Value = env.get(index_name).unwrap();
-                        let index = match index_value {
-                            Value::Int(i) => {
-                                // TODO: Integer range check here error
-                                i as usize
-                            }
-
-                            _ => unimplemented!(),
-                        };
-
-                        let inner_ref = root_ref.inner_ref();
-                        match *inner_ref {
-                            Value::Array(ref v) => {
-                                v
-                                    .get(index)
-                                    // TODO: Array bounds error here
-                                    .expect(&format!("Invalid index: {}", index))
-                                    .ref_clone()
-                            }
-
-                            _ => unimplemented!(),
-                        }
+                        Executor::array_element(env, root_ref, index_name)?
                     },
 
                     None => root_ref,
@@ -328,6 +350,7 @@ impl Executor {
                     match field_access {
                         FieldAccess::Field(ref field_name) => {
                             let new_ref = {
+                                let next_ref = Executor::auto_deref(next_ref);
                                 let inner_ref = next_ref.inner_ref();
                                 match *inner_ref {
                                     Value::Struct(ref internal) => {
@@ -352,6 +375,7 @@ impl Executor {
                         } => {
 
                             let field_ref = {
+                                let next_ref = Executor::auto_deref(next_ref);
                                 let inner_ref = next_ref.inner_ref();
                                 match *inner_ref {
                                     Value::Struct(ref internal) => {
@@ -368,52 +392,78 @@ impl Executor {
                             };
 
 
-                            let new_ref = {
-                                let index_value: Value = env.get(index_tmp).unwrap();
-                                let index = match index_value {
-                                    Value::Int(i) => {
-                                        i as usize
-                                    }
-
-                                    _ => unimplemented!(),
-                                };
-
-                                let inner_ref = field_ref.inner_ref();
-                                match *inner_ref {
-                                    Value::Array(ref v) => {
-                                        v
-                                            .get(index)
-                                            .unwrap()
-                                            .ref_clone()
-                                    }
-
-                                    _ => unimplemented!(),
-                                }
-                            };
-
-                            next_ref = new_ref;
+                            next_ref = Executor::array_element(env, field_ref, index_tmp)?;
                         }
                     }
                 }
 
-                next_ref
+                Ok(next_ref)
             }
 
             Location::Namespace(ref name) => {
-                env.ref_value(name).unwrap()
+                Ok(env.ref_value(name).unwrap())
             }
 
             Location::Tmp(ref name) => {
-                env.ref_tmp(name).unwrap()
+                Ok(env.ref_tmp(name).unwrap())
+            }
+        }
+    }
+
+    /// Arrays are only read, so locations are fetched without copying the array
+    fn array_arg(env: &Env, arg: &Arg) -> Result<ReferableValue, InternalError> {
+        match arg {
+            Arg::Location(ref arg_loc) => Executor::fetch(env, arg_loc),
+            _ => Ok(ReferableValue::new(Executor::arg_to_value(env, arg)?)),
+        }
+    }
+
+    /// Looks up the element of the (auto-dereferenced) array at the index stored in `index_tmp`
+    fn array_element(env: &Env, array_ref: ReferableValue, index_tmp: &str)
+        -> Result<ReferableValue, InternalError> {
+
+        let index = match env.get(index_tmp).unwrap() {
+            Value::Int(i) => i,
+
+            _ => return Err(InternalError::RuntimeInstructionError(
+                RuntimeInstructionError::NonIntIndex(index_tmp.to_string()))),
+        };
+
+        let array_ref = Executor::auto_deref(array_ref);
+        let inner_ref = array_ref.inner_ref();
+        match *inner_ref {
+            Value::Array(ref v) => {
+                if index < 0 || index as usize >= v.len() {
+                    return Err(InternalError::RuntimeInstructionError(
+                        RuntimeInstructionError::IndexOutOfBounds {
+                            index: index,
+                            len: v.len(),
+                        }));
+                }
+
+                Ok(v[index as usize].ref_clone())
             }
+
+            _ => Err(InternalError::RuntimeInstructionError(
+                RuntimeInstructionError::IndexedNonArray)),
         }
     }
 
-    fn store(env: &mut Env, location: &Location, value: Value) {
+    /// Follows references to the referenced value (field access and indexing auto-dereference)
+    fn auto_deref(value: ReferableValue) -> ReferableValue {
+        let referenced = match *value.inner_ref() {
+            Value::Ref(ref referenced) => referenced.ref_clone(),
+            _ => return value.ref_clone(),
+        };
+
+        Executor::auto_deref(referenced)
+    }
+
+    fn store(env: &mut Env, location: &Location, value: Value) -> Result<(), InternalError> {
         match location {
             Location::Compound { .. } => {
                 // Guarenteed to get a reference, assuming program is correct
-                let reference: ReferableValue = Executor::fetch(env, location);
+                let reference: ReferableValue = Executor::fetch(env, location)?;
 
                 *reference.inner_ref_mut() = value;
             }
@@ -427,16 +477,20 @@ impl Executor {
             }
         }
 
+        Ok(())
     }
 
-    fn arg_to_value(env: &Env, arg: &Arg) -> Value {
-        match arg {
-            Arg::Location(ref arg_loc) => Executor::fetch(env, arg_loc).clone_value(),
+    fn arg_to_value(env: &Env, arg: &Arg) -> Result<Value, InternalError> {
+        let value = match arg {
+            Arg::Location(ref arg_loc) => Executor::fetch(env, arg_loc)?.clone_value(),
             Arg::Int(ref i) => Value::Int(*i),
             Arg::Float(ref f) => Value::Float(*f),
             Arg::Bool(ref b) => Value::Bool(*b),
             Arg::String(ref s) => Value::String(s.clone()),
-        }
+            Arg::Unit => Value::Unit,
+        };
+
+        Ok(value)
     }
 
     fn execute_instruction(instruction: &Instruction, ip: InstructionPointerType,
@@ -446,7 +500,7 @@ impl Executor {
         macro_rules! integer_from_arg {
             ($arg: expr, $instr: expr) => {{
                 let from_arg = match $arg {
-                    Arg::Location(ref arg_loc) => Executor::fetch(env, arg_loc).clone_value(),
+                    Arg::Location(ref arg_loc) => Executor::fetch(env, arg_loc)?.clone_value(),
                     Arg::Int(ref i) => Value::Int(*i),
 
                     _ => return Err(InternalError::InvalidInstruction(
@@ -465,7 +519,7 @@ impl Executor {
         macro_rules! float_from_arg {
             ($arg: expr, $instr: expr) => {{
                 let from_arg = match $arg {
-                    Arg::Location(ref arg_loc) => Executor::fetch(env, arg_loc).clone_value(),
+                    Arg::Location(ref arg_loc) => Executor::fetch(env, arg_loc)?.clone_value(),
                     Arg::Float(ref f) => Value::Float(*f),
 
                     _ => return Err(InternalError::InvalidInstruction(
@@ -484,7 +538,7 @@ impl Executor {
         macro_rules! bool_from_arg {
             ($arg: expr, $instr: expr) => {{
                 let from_arg = match $arg {
-                    Arg::Location(ref arg_loc) => Executor::fetch(env, arg_loc).clone_value(),
+                    Arg::Location(ref arg_loc) => Executor::fetch(env, arg_loc)?.clone_value(),
                     Arg::Bool(ref b) => Value::Bool(*b),
 
                     _ => return Err(InternalError::InvalidInstruction(
@@ -507,7 +561,7 @@ impl Executor {
                 let rhs = integer_from_arg!($arg2, $instruction);
 
                 let to_store = Value::Int(lhs $op rhs);
-                Executor::store($env, $store_loc, to_store);
+                Executor::store($env, $store_loc, to_store)?;
 
                 Ok(ExecuteAction::IncrementIP)
             }}
@@ -520,7 +574,7 @@ impl Executor {
                 let rhs = float_from_arg!($arg2, $instruction);
 
                 let to_store = Value::Float(lhs $op rhs);
-                Executor::store($env, $store_loc, to_store);
+                Executor::store($env, $store_loc, to_store)?;
 
                 Ok(ExecuteAction::IncrementIP)
             }}
@@ -533,7 +587,7 @@ impl Executor {
                 let rhs = integer_from_arg!($arg2, $instruction);
 
                 let to_store = Value::Bool(lhs $op rhs);
-                Executor::store($env, $store_loc, to_store);
+                Executor::store($env, $store_loc, to_store)?;
 
                 Ok(ExecuteAction::IncrementIP)
             }}
@@ -546,7 +600,7 @@ impl Executor {
                 let rhs = float_from_arg!($arg2, $instruction);
 
                 let to_store = Value::Bool(lhs $op rhs);
-                Executor::store($env, $store_loc, to_store);
+                Executor::store($env, $store_loc, to_store)?;
 
                 Ok(ExecuteAction::IncrementIP)
             }}
@@ -555,9 +609,9 @@ impl Executor {
 
         match instruction {
             Instruction::Store(ref store_loc, ref arg) => {
-                let to_store = Executor::arg_to_value(env, arg);
+                let to_store = Executor::arg_to_value(env, arg)?;
 
-                Executor::store(env, store_loc, to_store);
+                Executor::store(env, store_loc, to_store)?;
 
                 Ok(ExecuteAction::IncrementIP)
             },
@@ -565,14 +619,12 @@ impl Executor {
             Instruction::StoreStructure(ref store_loc, ref string_value_map) => {
                 let mut internal_struct = Struct::new();
 
-                string_value_map
-                    .iter()
-                    .for_each(|(key, arg)| {
-                        let value = Executor::arg_to_value(env, arg);
-                        internal_struct.set_field(key.clone(), value);
-                    });
+                for (key, arg) in string_value_map.iter() {
+                    let value = Executor::arg_to_value(env, arg)?;
+                    internal_struct.set_field(key.clone(), value);
+                }
 
-                Executor::store(env, store_loc, Value::Struct(internal_struct));
+                Executor::store(env, store_loc, Value::Struct(internal_struct))?;
 
                 Ok(ExecuteAction::IncrementIP)
             }
@@ -581,19 +633,19 @@ impl Executor {
                 let internal_array: Array = value
                     .iter()
                     .map(|arg| {
-                        let raw_value = Executor::arg_to_value(env, arg);
-                        ReferableValue::new(raw_value)
+                        let raw_value = Executor::arg_to_value(env, arg)?;
+                        Ok(ReferableValue::new(raw_value))
                     })
-                    .collect();
+                    .collect::<Result<_, InternalError>>()?;
 
                 let to_store = Value::Array(internal_array);
-                Executor::store(env, store_loc, to_store);
+                Executor::store(env, store_loc, to_store)?;
 
                 Ok(ExecuteAction::IncrementIP)
             },
 
             Instruction::StoreArray2(ref store_loc, ref value, size) => {
-                let cached_value = Executor::arg_to_value(env, value);
+                let cached_value = Executor::arg_to_value(env, value)?;
                 let internal_array: Array = (0..*size)
                     .map(|_index| {
                         ReferableValue::new(cached_value.clone())
@@ -601,7 +653,51 @@ impl Executor {
                     .collect();
 
                 let to_store = Value::Array(internal_array);
-                Executor::store(env, store_loc, to_store);
+                Executor::store(env, store_loc, to_store)?;
+
+                Ok(ExecuteAction::IncrementIP)
+            }
+
+            Instruction::Len(ref store_loc, ref array) => {
+                let array = Executor::auto_deref(Executor::array_arg(env, array)?);
+                let len = match *array.inner_ref() {
+                    Value::Array(ref array) => array.len(),
+                    _ => return Err(InternalError::RuntimeInstructionError(
+                        RuntimeInstructionError::ExpectedArray(instruction.clone()))),
+                };
+
+                Executor::store(env, store_loc, Value::Int(len as i64))?;
+
+                Ok(ExecuteAction::IncrementIP)
+            }
+
+            Instruction::Slice(ref store_loc, ref array, ref start, ref end) => {
+                let start = integer_from_arg!(start, instruction);
+                let end = integer_from_arg!(end, instruction);
+
+                let array = Executor::auto_deref(Executor::array_arg(env, array)?);
+                let sliced: Array = match *array.inner_ref() {
+                    Value::Array(ref array) => {
+                        if start < 0 || start > end || end as usize > array.len() {
+                            return Err(InternalError::RuntimeInstructionError(
+                                RuntimeInstructionError::InvalidSlice {
+                                    start: start,
+                                    end: end,
+                                    len: array.len(),
+                                }));
+                        }
+
+                        array[start as usize..end as usize]
+                            .iter()
+                            .map(|element| element.clone_value())
+                            .collect()
+                    }
+
+                    _ => return Err(InternalError::RuntimeInstructionError(
+                        RuntimeInstructionError::ExpectedArray(instruction.clone()))),
+                };
+
+                Executor::store(env, store_loc, Value::Array(sliced))?;
 
                 Ok(ExecuteAction::IncrementIP)
             }
@@ -641,7 +737,7 @@ impl Executor {
                 let rhs = bool_from_arg!(arg2, instruction);
 
                 let to_store = Value::Bool(lhs && rhs);
-                Executor::store(env, store_loc, to_store);
+                Executor::store(env, store_loc, to_store)?;
 
                 Ok(ExecuteAction::IncrementIP)
             }
@@ -651,7 +747,7 @@ impl Executor {
                 let rhs = bool_from_arg!(arg2, instruction);
 
                 let to_store = Value::Bool(lhs || rhs);
-                Executor::store(env, store_loc, to_store);
+                Executor::store(env, store_loc, to_store)?;
 
                 Ok(ExecuteAction::IncrementIP)
             }
@@ -681,27 +777,27 @@ impl Executor {
                 comp_float_op!(env, instruction, store_loc, arg1, arg2, <),
 
             Instruction::Eq(ref store_loc, ref arg1, ref arg2) => {
-                let v1 = Executor::arg_to_value(env, arg1);
-                let v2 = Executor::arg_to_value(env, arg2);
+                let v1 = Executor::arg_to_value(env, arg1)?;
+                let v2 = Executor::arg_to_value(env, arg2)?;
 
                 let to_store = Value::Bool(v1 == v2);
-                Executor::store(env, store_loc, to_store);
+                Executor::store(env, store_loc, to_store)?;
 
                 Ok(ExecuteAction::IncrementIP)
             }
 
             Instruction::InEq(ref store_loc, ref arg1, ref arg2) => {
-                let v1 = Executor::arg_to_value(env, arg1);
-                let v2 = Executor::arg_to_value(env, arg2);
+                let v1 = Executor::arg_to_value(env, arg1)?;
+                let v2 = Executor::arg_to_value(env, arg2)?;
 
                 let to_store = Value::Bool(v1 != v2);
-                Executor::store(env, store_loc, to_store);
+                Executor::store(env, store_loc, to_store)?;
 
                 Ok(ExecuteAction::IncrementIP)
             }
 
             Instruction::Negate(ref store_loc, ref arg1) => {
-                let to_store = match Executor::arg_to_value(env, arg1) {
+                let to_store = match Executor::arg_to_value(env, arg1)? {
                     Value::Int(i) => Value::Int(-i),
                     Value::Float(f) => Value::Float(-f),
 
@@ -710,7 +806,7 @@ impl Executor {
 
                 };
 
-                Executor::store(env, store_loc, to_store);
+                Executor::store(env, store_loc, to_store)?;
 
                 Ok(ExecuteAction::IncrementIP)
             }
@@ -719,13 +815,108 @@ impl Executor {
                 let b = bool_from_arg!(arg1, instruction);
 
                 let to_store = Value::Bool(!b);
-                Executor::store(env, store_loc, to_store);
+                Executor::store(env, store_loc, to_store)?;
+
+                Ok(ExecuteAction::IncrementIP)
+            }
+
+            Instruction::BitAnd(ref store_loc, ref arg1, ref arg2) =>
+                int_op!(env, instruction, store_loc, arg1, arg2, &),
+
+            Instruction::BitOr(ref store_loc, ref arg1, ref arg2) =>
+                int_op!(env, instruction, store_loc, arg1, arg2, |),
+
+            Instruction::BitXor(ref store_loc, ref arg1, ref arg2) =>
+                int_op!(env, instruction, store_loc, arg1, arg2, ^),
+
+            Instruction::Shl(ref store_loc, ref arg1, ref arg2)
+            | Instruction::Shr(ref store_loc, ref arg1, ref arg2) => {
+                let value = integer_from_arg!(arg1, instruction);
+                let amount = integer_from_arg!(arg2, instruction);
+
+                // Shifting by 64 or more bits shifts out every bit (except the sign for '>>')
+                let shift = match amount {
+                    0..=63 => amount,
+                    64..=std::i64::MAX => 63,
+                    _ => return Err(InternalError::RuntimeInstructionError(
+                        RuntimeInstructionError::NegativeShift(instruction.clone(), amount))),
+                };
+
+                let to_store = match instruction {
+                    Instruction::Shl(..) if amount > 63 => 0,
+                    Instruction::Shl(..) => value << shift,
+                    _ => value >> shift,
+                };
+                Executor::store(env, store_loc, Value::Int(to_store))?;
+
+                Ok(ExecuteAction::IncrementIP)
+            }
+
+            Instruction::BitNot(ref store_loc, ref arg1) => {
+                let i = integer_from_arg!(arg1, instruction);
+
+                Executor::store(env, store_loc, Value::Int(!i))?;
+
+                Ok(ExecuteAction::IncrementIP)
+            }
+
+            Instruction::Format(ref store_loc, ref args) => {
+                let mut formatted = String::new();
+                for arg in args {
+                    match Executor::arg_to_value(env, arg)? {
+                        value @ Value::Int(_)
+                        | value @ Value::Float(_)
+                        | value @ Value::Bool(_)
+                        | value @ Value::String(_) => formatted.push_str(&value.to_string()),
+
+                        _ => return Err(InternalError::RuntimeInstructionError(
+                            RuntimeInstructionError::ExpectedFormattable(instruction.clone()))),
+                    }
+                }
+
+                Executor::store(env, store_loc, Value::String(formatted))?;
+
+                Ok(ExecuteAction::IncrementIP)
+            }
+
+            Instruction::TakeRef(ref store_loc, ref referent_loc) => {
+                let referent = Executor::fetch(env, referent_loc)?;
+
+                Executor::store(env, store_loc, Value::Ref(referent))?;
+
+                Ok(ExecuteAction::IncrementIP)
+            }
+
+            Instruction::Deref(ref store_loc, ref arg1) => {
+                let to_store = match Executor::arg_to_value(env, arg1)? {
+                    Value::Ref(ref referenced) => referenced.clone_value(),
+
+                    _ => return Err(InternalError::RuntimeInstructionError(
+                        RuntimeInstructionError::ExpectedReference(instruction.clone()))),
+                };
+
+                Executor::store(env, store_loc, to_store)?;
+
+                Ok(ExecuteAction::IncrementIP)
+            }
+
+            Instruction::StoreDeref(ref store_loc, ref arg1) => {
+                let to_store = Executor::arg_to_value(env, arg1)?;
+
+                let referenced = match *Executor::fetch(env, store_loc)?.inner_ref() {
+                    Value::Ref(ref referenced) => referenced.ref_clone(),
+
+                    _ => return Err(InternalError::RuntimeInstructionError(
+                        RuntimeInstructionError::ExpectedReference(instruction.clone()))),
+                };
+
+                *referenced.inner_ref_mut() = to_store;
 
                 Ok(ExecuteAction::IncrementIP)
             }
 
             Instruction::FnCall(ref fn_loc, ref args) => {
-                let func = Executor::fetch(env, fn_loc);
+                let func = Executor::fetch(env, fn_loc)?;
 
                 let inner = func.inner_ref();
                 match *inner {
@@ -736,22 +927,52 @@ impl Executor {
                             let args = args
                                 .iter()
                                 .map(|a| Executor::arg_to_value(env, a))
-                                .collect();
+                                .collect::<Result<_, _>>()?;
                             Some(args)
                         };
 
                         Ok(ExecuteAction::PushStack(handle.clone(), args.unwrap_or(vec![])))
                     }
 
+                    Value::PartialApp(ref partial) => {
+                        let args = args
+                            .iter()
+                            .map(|a| Executor::arg_to_value(env, a))
+                            .collect::<Result<_, _>>()?;
+
+                        Ok(ExecuteAction::PushStack(partial.handle(), partial.call_args(args)))
+                    }
+
                     _ => Err(InternalError::RuntimeInstructionError(
                             RuntimeInstructionError::ExpectedFunction(instruction.clone()))),
                 }
             },
 
+            Instruction::PartialApp(ref store_loc, ref fn_loc, ref args) => {
+                let args = args
+                    .iter()
+                    .map(|a| a.as_ref().map(|a| Executor::arg_to_value(env, a)).transpose())
+                    .collect::<Result<_, _>>()?;
+
+                let partial = match *Executor::fetch(env, fn_loc)?.inner_ref() {
+                    Value::Function(handle) => PartialApp::new(handle, args),
+
+                    Value::PartialApp(ref partial) => partial.bind(args),
+
+                    _ => return Err(InternalError::RuntimeInstructionError(
+                            RuntimeInstructionError::ExpectedFunction(instruction.clone()))),
+                };
+
+                Executor::store(env, store_loc, Value::PartialApp(partial))?;
+
+                Ok(ExecuteAction::IncrementIP)
+            }
+
             Instruction::Return(ref return_value) => {
                 let return_value = return_value
                     .as_ref()
                     .map(|a| Executor::arg_to_value(env, a))
+                    .transpose()?
                     .unwrap_or(Value::Unit);
                 Ok(ExecuteAction::PopStack(return_value))
             }
@@ -762,7 +983,7 @@ impl Executor {
                     .ok_or(InternalError::RuntimeInstructionError(
                             RuntimeInstructionError::NoReturnValue(ip)))?;
 
-                Executor::store(env, store_loc, to_store);
+                Executor::store(env, store_loc, to_store)?;
 
                 Ok(ExecuteAction::IncrementIP)
             }
@@ -1,3 +1,4 @@
+extern crate self as smpli;
 extern crate failure;
 #[macro_use]
 extern crate failure_derive;
@@ -21,12 +22,15 @@ mod builtins;
 mod std_options;
 mod module;
 mod executor;
+mod coverage;
+mod conversion;
 
 pub use value:: {
     ReferableValue,
     Value,
     Struct,
     Array,
+    PartialApp,
 };
 
 pub use vm_i::{
@@ -41,9 +45,28 @@ pub use module::VmModule;
 
 pub use std_options::*;
 
-pub use vm::{ SpawnOptions, AVM };
+pub use vm::{ QueryOptions, SpawnOptions, AVM };
 pub use executor::Executor;
+pub use coverage::{ Coverage, BranchCount };
+pub use conversion::{
+    SmplType,
+    SmplDeclaration,
+    FromSmpl,
+    ToSmpl,
+    IntoBuiltinResult,
+    TypedBuiltin,
+    typed,
+    value_kind,
+};
 
 pub use smpl::prelude::{ ParsedModule, UnparsedModule, parse_module };
 
+pub use smpli_derive::{ SmplValue, smpl_module };
+
 pub use builtins::erase;
+
+/// Used by code generated in `smpli-derive`
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::conversion::{ expect_struct, take_field, builtin_declaration, module_declaration };
+}
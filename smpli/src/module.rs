@@ -3,6 +3,7 @@ use smpl::prelude::ParsedModule;
 
 use crate::vm_i::BuiltinFn;
 
+#[derive(Clone)]
 pub struct VmModule {
     pub parsed: ParsedModule,
     pub builtins: Vec<(String, BuiltinFn)>,
@@ -11,6 +11,8 @@ macro_rules! include {
 
 #[derive(Builder, Clone)]
 pub struct Std {
+    #[builder(default = "false")]
+    array: bool,
     #[builder(default = "false")]
     convert: bool,
     #[builder(default = "false")]
@@ -30,6 +32,7 @@ pub struct Std {
 impl Std {
     pub fn std() -> Std {
         Std {
+            array: true,
             convert: true,
             err: true,
             log: true,
@@ -42,6 +45,7 @@ impl Std {
 
     pub fn no_std() -> Std {
         Std {
+            array: false,
             convert: false,
             err: false,
             log: false,
@@ -53,6 +57,7 @@ impl Std {
     }
 
     pub fn include(&self, v: &mut Vec<VmModule>) {
+        include!(v, self, array, builtins::array::vm_module());
         include!(v, self, convert, builtins::convert::vm_module());
         include!(v, self, err, builtins::err::vm_module());
         include!(v, self, log, builtins::log::vm_module());
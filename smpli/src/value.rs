@@ -47,7 +47,9 @@ pub enum Value {
     String(String),
     Array(Array),
     Function(FnHandle),
+    PartialApp(PartialApp),
     Struct(Struct),
+    Ref(ReferableValue),
     Unit,
 }
 
@@ -70,8 +72,13 @@ impl Clone for Value {
 
             Value::Function(f) => Value::Function(f),
 
+            Value::PartialApp(ref p) => Value::PartialApp(p.clone()),
+
             Value::Struct(ref s) => Value::Struct(s.clone()),
 
+            // References alias the referenced value
+            Value::Ref(ref r) => Value::Ref(r.ref_clone()),
+
             Value::Unit => Value::Unit,
         }
     }
@@ -102,11 +109,145 @@ impl fmt::Display for Value {
 
             Value::Function(..) => write!(f, "Function"), // TODO: Add more information
 
+            Value::PartialApp(..) => write!(f, "Function"),
+
+            Value::Ref(ref r) => write!(f, "&{}", r.0.borrow()),
+
             Value::Unit => write!(f, "()"),
         }
     }
 }
 
+///
+/// Deep copy of a `Value` that can be sent between threads.
+///   References are copied as the value they refer to.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SendValue {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+    Array(Vec<SendValue>),
+    Function(FnHandle),
+    PartialApp(FnHandle, Vec<Option<SendValue>>),
+    Struct(HashMap<String, SendValue>),
+    Ref(Box<SendValue>),
+    Unit,
+}
+
+impl<'a> From<&'a Value> for SendValue {
+    fn from(value: &Value) -> SendValue {
+        match *value {
+            Value::Int(i) => SendValue::Int(i),
+            Value::Float(f) => SendValue::Float(f),
+            Value::Bool(b) => SendValue::Bool(b),
+            Value::String(ref s) => SendValue::String(s.clone()),
+
+            Value::Array(ref a) => SendValue::Array(
+                a.iter()
+                    .map(|rc| SendValue::from(&*rc.inner_ref()))
+                    .collect(),
+            ),
+
+            Value::Function(f) => SendValue::Function(f),
+
+            Value::PartialApp(ref p) => SendValue::PartialApp(
+                p.handle,
+                p.args
+                    .iter()
+                    .map(|arg| arg.as_ref().map(SendValue::from))
+                    .collect(),
+            ),
+
+            Value::Struct(ref s) => SendValue::Struct(
+                s.0.iter()
+                    .map(|(key, rc)| (key.clone(), SendValue::from(&*rc.inner_ref())))
+                    .collect(),
+            ),
+
+            Value::Ref(ref r) => SendValue::Ref(Box::new(SendValue::from(&*r.inner_ref()))),
+
+            Value::Unit => SendValue::Unit,
+        }
+    }
+}
+
+impl From<SendValue> for Value {
+    fn from(value: SendValue) -> Value {
+        match value {
+            SendValue::Int(i) => Value::Int(i),
+            SendValue::Float(f) => Value::Float(f),
+            SendValue::Bool(b) => Value::Bool(b),
+            SendValue::String(s) => Value::String(s),
+
+            SendValue::Array(a) => Value::Array(a.into_iter().map(Value::from).collect()),
+
+            SendValue::Function(f) => Value::Function(f),
+
+            SendValue::PartialApp(handle, args) => Value::PartialApp(PartialApp::new(
+                handle,
+                args.into_iter().map(|arg| arg.map(Value::from)).collect(),
+            )),
+
+            SendValue::Struct(s) => Value::Struct(Struct::new_init(
+                s.into_iter()
+                    .map(|(key, v)| (key, ReferableValue::new(Value::from(v))))
+                    .collect(),
+            )),
+
+            SendValue::Ref(r) => Value::Ref(ReferableValue::new(Value::from(*r))),
+
+            SendValue::Unit => Value::Unit,
+        }
+    }
+}
+
+///
+/// Function with some of its arguments bound. Calls fill the `None` arguments in order.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialApp {
+    handle: FnHandle,
+    args: Vec<Option<Value>>,
+}
+
+impl PartialApp {
+    pub(crate) fn new(handle: FnHandle, args: Vec<Option<Value>>) -> PartialApp {
+        PartialApp { handle, args }
+    }
+
+    pub(crate) fn handle(&self) -> FnHandle {
+        self.handle
+    }
+
+    /// Partially applying a partial application binds its remaining arguments.
+    pub(crate) fn bind(&self, args: Vec<Option<Value>>) -> PartialApp {
+        PartialApp {
+            handle: self.handle,
+            args: self.fill(args),
+        }
+    }
+
+    pub(crate) fn call_args(&self, args: Vec<Value>) -> Vec<Value> {
+        self.fill(args.into_iter().map(Some).collect())
+            .into_iter()
+            .map(|arg| arg.expect("Partial application called with too few arguments"))
+            .collect()
+    }
+
+    fn fill(&self, args: Vec<Option<Value>>) -> Vec<Option<Value>> {
+        let mut args = args.into_iter();
+        self.args
+            .iter()
+            .map(|bound| match *bound {
+                Some(ref value) => Some(value.clone()),
+                None => args.next().unwrap_or(None),
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Struct(HashMap<String, ReferableValue>);
 
@@ -1,14 +1,16 @@
 use failure::Error;
 use smpl::prelude::{ Program, FnId, ModuleId };
 use smpl::metadata::Metadata;
+use smpl::module::ModuleSource;
+use smpl::error::analysis_error::AnalysisError;
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{ Arc, Mutex };
 
 use crate::err::*;
 use crate::module::VmModule;
 use crate::std_options::Std;
-use crate::value::Value;
+use crate::value::{ Value, SendValue };
 use crate::executor::Executor;
 use crate::vm_i::*;
 
@@ -18,34 +20,81 @@ pub type CompiledProgram =
     Arc<HashMap<FnId, Arc<byte_gen::ByteCodeFunction>>>;
 pub type MappedBuiltins =
     Arc<HashMap<FnId, Arc<BuiltinFn>>>;
+/// Static values by name. Executors load them when spawned and store them back when finished.
+pub type SharedStatics =
+    Arc<Mutex<HashMap<String, SendValue>>>;
 
 #[derive(Debug, Clone)]
 pub struct SpawnOptions {
     pub type_check: bool
 }
 
+#[derive(Debug, Clone)]
+pub struct QueryOptions {
+    /// Only find functions declared 'pub'
+    pub public_only: bool,
+}
+
 #[derive(Clone)]
 pub struct AVM {
     metadata: Arc<Metadata>,
     compiled: CompiledProgram,
     builtins: MappedBuiltins,
+    fn_sources: Arc<HashMap<FnId, ModuleSource>>,
+    modules: Arc<Vec<VmModule>>,
+    /// Statics of every module, initialized once and shared by every executor
+    statics: SharedStatics,
 }
 
 impl AVM {
     pub fn new(std: Std, mut modules: Vec<VmModule>) -> Result<AVM, VmError> {
         std.include(&mut modules);
 
-        let mut builtins = Vec::new();
-        let modules = modules
-            .into_iter()
-            .map(|vmmod| {
-                builtins.push((vmmod.id(), vmmod.builtins));
-                vmmod.parsed
-            });
+        let program = Program::from_parsed(
+            modules.iter().map(|vmmod| vmmod.parsed.clone()))?;
 
-        let program = Program::from_parsed(modules)?;
+        AVM::from_program(program, modules)
+    }
+
+    ///
+    /// Re-analyze the program with `module` replacing the loaded module of the same name.
+    ///
+    /// Functions keep their `FnId` (and any `FnHandle` to them stays valid) if their
+    ///   module and name did not change.
+    /// Fails with an analysis error if a function of `module` changed its signature.
+    ///   The AVM is left unchanged on failure.
+    ///
+    /// Executors spawned before the reload keep running the old code. Statics are
+    ///   initialized again.
+    ///
+    pub fn reload_module(&mut self, module: VmModule) -> Result<(), VmError> {
+        let name = module.parsed
+            .name()
+            .ok_or(AnalysisError::MissingModName)?
+            .to_string();
+
+        let index = self.modules
+            .iter()
+            .position(|vmmod| vmmod.parsed.name() == Some(name.as_str()))
+            .ok_or(VmError::NotAModule(name))?;
+
+        let mut modules = (*self.modules).clone();
+        let previous_module = std::mem::replace(&mut modules[index], module);
+
+        let program = Program::reload(
+            modules.iter().map(|vmmod| vmmod.parsed.clone()),
+            &previous_module.parsed,
+            &self.metadata)?;
+
+        *self = AVM::from_program(program, modules)?;
+
+        Ok(())
+    }
 
+    fn from_program(program: Program, modules: Vec<VmModule>) -> Result<AVM, VmError> {
         let mut compiled_fns = HashMap::new();
+        let mut fn_sources = HashMap::new();
+        let mut fn_modules = HashMap::new();
 
         for module in program.compilable_modules() {
             for compilable_fn in module.compilable_fns() {
@@ -54,6 +103,9 @@ impl AVM {
                 if compiled_fns.insert(fn_id, Arc::new(compiled)).is_some() {
                     panic!("Multiple functions with ID {}. Should not have passed check_program()", fn_id);
                 }
+
+                fn_sources.insert(fn_id, module.source().clone());
+                fn_modules.insert(fn_id, module.id());
             }
 
         }
@@ -62,17 +114,49 @@ impl AVM {
             metadata: Arc::new(program.metadata().clone()),
             compiled: Arc::new(compiled_fns),
             builtins: Arc::new(HashMap::new()),
+            fn_sources: Arc::new(fn_sources),
+            modules: Arc::new(Vec::new()),
+            statics: Arc::new(Mutex::new(HashMap::new())),
         };
 
-        for (mod_id, map) in builtins.into_iter() {
-            for (name, builtin) in map.into_iter() {
-                vm.map_builtin(mod_id, name, builtin)?;
+        for vmmod in modules.iter() {
+            for (name, builtin) in vmmod.builtins.iter() {
+                vm.map_builtin(vmmod.id(), name.clone(), *builtin)?;
             }
         }
 
+        vm.modules = Arc::new(modules);
+        vm.init_statics(&fn_modules)?;
+
         Ok(vm)
     }
 
+    ///
+    /// Runs every static initializer once, in module dependency order.
+    ///
+    fn init_statics(&self, fn_modules: &HashMap<FnId, ModuleId>) -> Result<(), VmError> {
+        {
+            let mut statics = self.statics.lock().expect("Statics lock poisoned");
+            for (static_id, _) in self.metadata.static_inits() {
+                statics.insert(byte_gen::to_static_id(*static_id), SendValue::Unit);
+            }
+        }
+
+        for (_, init_fn) in self.metadata.static_inits() {
+            Executor::new(self.metadata.clone(),
+                          FnHandle::new(fn_modules[init_fn], *init_fn),
+                          self.compiled.clone(),
+                          self.builtins.clone(),
+                          self.statics.clone(),
+                          Vec::new())
+                .map_err(|e| VmError::StaticInit(Arc::new(e.into())))?
+                .execute_sync()
+                .map_err(|e| VmError::StaticInit(Arc::new(e)))?;
+        }
+
+        Ok(())
+    }
+
     // Only callable during initialization OR when all executors dropped
     //   due to Arc::get_mut(self.builtins) requirement
     fn map_builtin(
@@ -115,6 +199,17 @@ impl AVM {
     }
 
     pub fn query_module(&self, module: &str, name: &str) -> Result<Option<FnHandle>, String> {
+        self.query_module_with(module, name, QueryOptions {
+            public_only: false,
+        })
+    }
+
+    ///
+    /// With `public_only`, private functions are reported as missing so hosts can only
+    ///   reach a module's 'pub' entry points.
+    ///
+    pub fn query_module_with(&self, module: &str, name: &str, options: QueryOptions)
+        -> Result<Option<FnHandle>, String> {
         let mod_id = self.metadata
             .mod_metadata()
             .get_module(module.to_string());
@@ -123,12 +218,21 @@ impl AVM {
             Some(mod_id) => Ok(self
                 .metadata
                 .module_fn(mod_id, name.to_string())
+                .filter(|fn_id| !options.public_only || self.metadata.is_public_fn(*fn_id))
                 .map(|fn_id| FnHandle::new(mod_id, fn_id))),
 
             None => Err(format!("Module '{}' does not exist", module)),
         }
     }
 
+    pub(crate) fn compiled(&self) -> &CompiledProgram {
+        &self.compiled
+    }
+
+    pub(crate) fn fn_source(&self, fn_id: FnId) -> Option<&ModuleSource> {
+        self.fn_sources.get(&fn_id)
+    }
+
     pub fn spawn_executor(&self, fn_handle: FnHandle,
                           args: Vec<Value>,
                           spawn_options: SpawnOptions) -> Result<Executor, InternalError> {
@@ -140,6 +244,7 @@ impl AVM {
                           fn_handle,
                           self.compiled.clone(),
                           self.builtins.clone(),
+                          self.statics.clone(),
                           args)
         }
     }
@@ -326,6 +326,62 @@ expect_value!(interpreter_while_loop,
     expect :: Value::Int(137)
 );
 
+expect_value!(interpreter_references,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(10 + (20 + 102) + 5)
+);
+
+expect_value!(interpreter_for_loop,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int((1 + 2 + 3 + 4) + (0 + 100 + 300 + 400) + 3000)
+);
+
+expect_value!(interpreter_generic_references,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(6)
+);
+
+expect_value!(interpreter_type_arg_inference,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(1 + 2 + 3 + 10 + 100 + 1000)
+);
+
+expect_value!(interpreter_tuples,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(3 + 2 + 10 + 100 + 1000 - 2)
+);
+
+expect_value!(interpreter_methods,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(30 + 3 + 100)
+);
+
+expect_value!(interpreter_method_receivers,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(12)
+);
+
+expect_value!(interpreter_traits,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(5 + 100 + 5 + 3 + 10)
+);
+
 #[test]
 fn interpreter_bind_fn_type_app_mod_access() {
     let mod1 =
@@ -386,3 +442,407 @@ expect_value!(interpreter_array_path_assignment,
         Value::Array(array)
     }
 );
+
+#[test]
+fn interpreter_coverage() {
+    use std::path::PathBuf;
+
+    let code = include_test!("interpreter_coverage.smpl");
+    let path = PathBuf::from("interpreter_coverage.smpl");
+
+    let module = UnparsedModule::file(path.clone(), code);
+    let module = VmModule::new(parse_module(module).unwrap());
+
+    let avm = AVM::new(Std::no_std(), vec![module]).unwrap();
+    let fn_handle = avm.query_module("mod1", "foo").unwrap().unwrap();
+
+    let coverage = Coverage::new();
+    let result = avm.spawn_executor(fn_handle, vec![Value::Int(1)], SpawnOptions {
+        type_check: false
+    })
+        .unwrap()
+        .track_coverage(coverage.clone())
+        .execute_sync()
+        .unwrap();
+
+    assert_eq!(Value::Int(3), result);
+
+    let report = coverage.lcov(&avm);
+    let record = report.get(&path).expect("No lcov record for file module");
+
+    assert!(record.starts_with("TN:\nSF:interpreter_coverage.smpl\n"));
+    assert!(record.ends_with("end_of_record\n"));
+
+    // Loop condition: true 3 times, false once
+    assert!(record.contains("BRDA:6,0,0,3\n"));
+    assert!(record.contains("BRDA:6,0,1,1\n"));
+
+    // If condition: only the false arm taken
+    assert!(record.contains("BRDA:10,1,0,0\n"));
+    assert!(record.contains("BRDA:10,1,1,1\n"));
+    assert!(record.contains("BRF:4\nBRH:3\n"));
+
+    assert!(record.contains("DA:7,3\n"));
+    assert!(record.contains("DA:11,0\n"));
+    assert!(record.contains("DA:13,1\n"));
+}
+
+#[test]
+fn interpreter_reload_module() {
+    let mod1 =
+"mod mod1;
+
+fn value() -> int {
+    return 1;
+}";
+
+    let mod1_reloaded =
+"mod mod1;
+
+fn value() -> int {
+    return 2;
+}";
+
+    let mod2 =
+"mod mod2;
+
+use mod1;
+
+fn test() -> int {
+    return mod1::value() + 10;
+}";
+
+    let m1 = VmModule::new(parse_module(wrap_input!(mod1)).unwrap());
+    let m2 = VmModule::new(parse_module(wrap_input!(mod2)).unwrap());
+
+    let mut avm = AVM::new(Std::no_std(), vec![m1, m2]).unwrap();
+
+    let value_handle = avm.query_module("mod1", "value").unwrap().unwrap();
+    let test_handle = avm.query_module("mod2", "test").unwrap().unwrap();
+
+    let old_executor = avm.spawn_executor(value_handle, vec![], SpawnOptions {
+        type_check: false,
+    }).unwrap();
+
+    let reloaded = VmModule::new(parse_module(wrap_input!(mod1_reloaded)).unwrap());
+    avm.reload_module(reloaded).unwrap();
+
+    // Handles from before the reload stay valid
+    let reloaded_handle = avm.query_module("mod1", "value").unwrap().unwrap();
+    assert_eq!(value_handle.fn_id(), reloaded_handle.fn_id());
+
+    let run = |handle| {
+        avm.spawn_executor(handle, vec![], SpawnOptions {
+            type_check: false,
+        })
+            .unwrap()
+            .execute_sync()
+            .unwrap()
+    };
+
+    assert_eq!(Value::Int(2), run(value_handle));
+    assert_eq!(Value::Int(12), run(test_handle));
+
+    // Already spawned executors keep the old code
+    assert_eq!(Value::Int(1), old_executor.execute_sync().unwrap());
+}
+
+#[test]
+fn interpreter_reload_module_incompatible() {
+    use smpl::error::Error as StaticError;
+    use smpl::error::analysis_error::{AnalysisError, TopLevelError};
+    use crate::err::VmError;
+
+    let mod1 =
+"mod mod1;
+
+fn value() -> int {
+    return 1;
+}";
+
+    let mod1_reloaded =
+"mod mod1;
+
+fn value(a: int) -> int {
+    return a;
+}";
+
+    let m1 = VmModule::new(parse_module(wrap_input!(mod1)).unwrap());
+    let mut avm = AVM::new(Std::no_std(), vec![m1]).unwrap();
+
+    let reloaded = VmModule::new(parse_module(wrap_input!(mod1_reloaded)).unwrap());
+    match avm.reload_module(reloaded) {
+        Err(VmError::StaticError(StaticError::Analysis(
+            AnalysisError::TopLevelError(TopLevelError::IncompatibleSignature(..))))) => (),
+
+        Err(e) => panic!("Expected an incompatible signature error. Found {:?}", e),
+        Ok(_) => panic!("Expected an incompatible signature error. Found OK"),
+    }
+
+    // Failed reloads leave the AVM untouched
+    let handle = avm.query_module("mod1", "value").unwrap().unwrap();
+    let result = avm.spawn_executor(handle, vec![], SpawnOptions {
+        type_check: false,
+    })
+        .unwrap()
+        .execute_sync()
+        .unwrap();
+
+    assert_eq!(Value::Int(1), result);
+}
+
+expect_value!(interpreter_globals,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(27 + 2 + (21 + 25) + 50)
+);
+
+#[test]
+fn interpreter_static_init_error() {
+    use crate::err::{ InternalError, VmError };
+
+    let mod1 =
+"mod mod1;
+
+static C: int = outside();
+
+fn outside() -> int {
+    let a = [1, 2];
+    return a[5];
+}";
+
+    let m1 = VmModule::new(parse_module(wrap_input!(mod1)).unwrap());
+    match AVM::new(Std::no_std(), vec![m1]) {
+        Err(VmError::StaticInit(cause)) => {
+            assert!(cause.downcast_ref::<InternalError>().is_some())
+        }
+
+        _ => panic!("Expected VmError::StaticInit"),
+    }
+}
+
+#[test]
+fn avm_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    // Shared statics must not stop an AVM from being moved or shared between threads
+    assert_send_sync::<AVM>();
+}
+
+#[test]
+fn interpreter_globals_persist() {
+    let mod1 =
+"mod mod1;
+
+static C: int = 0;
+
+fn test() -> int {
+    C = C + 1;
+    return C;
+}";
+
+    let m1 = VmModule::new(parse_module(wrap_input!(mod1)).unwrap());
+    let avm = AVM::new(Std::no_std(), vec![m1]).unwrap();
+
+    let fn_handle = avm.query_module("mod1", "test").unwrap().unwrap();
+
+    // Statics are initialized once and shared by every executor
+    for expected in 1..3 {
+        let result = avm.spawn_executor(fn_handle, vec![], SpawnOptions {
+            type_check: false,
+        })
+            .unwrap()
+            .execute_sync()
+            .unwrap();
+
+        assert_eq!(Value::Int(expected), result);
+    }
+}
+
+expect_value!(interpreter_compound_assignment,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(2 + 12 + 101 + 20 + 1000 + 14)
+);
+
+expect_value!(interpreter_nested_loop_jumps,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int((4 * 3 + 3 * 100) * 10 + 4)
+);
+
+expect_value!(interpreter_compound_assignment_index,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(1 + 3 * 10 + 6 * 100 + 1 * 1000)
+);
+
+expect_value!(interpreter_bitwise,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(1 + 30 + 900 + 2000 - 1 + 0 - 1)
+);
+
+expect_value!(interpreter_if_expr,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(1 + 22 + 300 + 12 + 1000 + 5000 - 1 + 0 + 1)
+);
+
+expect_value!(interpreter_value_expr_locals,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(210 + 3 + 7000)
+);
+
+expect_value!(interpreter_numeric_literals,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(255 + 10 + 15 + 1_000_000)
+);
+
+expect_value!(interpreter_format_string,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::String("HP: 7/10 0.5 true {17} (1, -2) <Ann [7]> inner".to_string())
+);
+
+expect_value!(interpreter_selective_imports,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::String("A3".to_string())
+);
+
+#[test]
+fn interpreter_query_public_only() {
+    let mod1 =
+"#[explicit_pub]
+mod mod1;
+
+fn helper() -> int {
+    return 2;
+}
+
+pub fn run() -> int {
+    return helper() * 10;
+}";
+
+    let mod2 =
+"mod mod2;
+
+fn helper() -> int {
+    return 3;
+}
+
+pub fn run() -> int {
+    return helper();
+}";
+
+    let m1 = VmModule::new(parse_module(wrap_input!(mod1)).unwrap());
+    let m2 = VmModule::new(parse_module(wrap_input!(mod2)).unwrap());
+    let avm = AVM::new(Std::no_std(), vec![m1, m2]).unwrap();
+
+    let public_only = || QueryOptions {
+        public_only: true,
+    };
+
+    // Private functions are hidden from the host
+    assert!(avm.query_module_with("mod1", "helper", public_only()).unwrap().is_none());
+    assert!(avm.query_module("mod1", "helper").unwrap().is_some());
+
+    // Modules without #[explicit_pub] keep everything public
+    assert!(avm.query_module_with("mod2", "helper", public_only()).unwrap().is_some());
+
+    let run_handle = avm.query_module_with("mod1", "run", public_only()).unwrap().unwrap();
+    let result = avm.spawn_executor(run_handle, vec![], SpawnOptions {
+        type_check: false,
+    })
+        .unwrap()
+        .execute_sync()
+        .unwrap();
+
+    assert_eq!(result, Value::Int(20));
+}
+
+expect_value!(interpreter_type_aliases,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(43)
+);
+
+expect_value!(interpreter_partial_app,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(603)
+);
+
+expect_value!(interpreter_labeled_loops,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(3204082546)
+);
+
+expect_value!(interpreter_struct_defaults,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(12011841)
+);
+
+expect_value!(interpreter_growable_arrays,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(794541219)
+);
+
+#[test]
+fn interpreter_array_bounds_error() {
+    use crate::err::{InternalError, RuntimeInstructionError};
+
+    let mod1 =
+"mod mod1;
+
+fn test() -> int {
+    let values: [int] = [1, 2, 3];
+    return values[3];
+}";
+
+    let m1 = VmModule::new(parse_module(wrap_input!(mod1)).unwrap());
+    let avm = AVM::new(Std::no_std(), vec![m1]).unwrap();
+
+    let handle = avm.query_module("mod1", "test").unwrap().unwrap();
+    let result = avm.spawn_executor(handle, vec![], SpawnOptions {
+        type_check: false,
+    })
+        .unwrap()
+        .execute_sync();
+
+    match result.map_err(|e| e.downcast::<InternalError>()) {
+        Err(Ok(InternalError::RuntimeInstructionError(
+            RuntimeInstructionError::IndexOutOfBounds { index: 3, len: 3 }))) => (),
+
+        Err(e) => panic!("Expected an index out of bounds error. Found {:?}", e),
+        Ok(v) => panic!("Expected an index out of bounds error. Found {:?}", v),
+    }
+}
+
+expect_value!(interpreter_for_range_order,
+    module :: "mod1",
+    eval :: "test",
+    args :: vec![],
+    expect :: Value::Int(120)
+);